@@ -0,0 +1,401 @@
+use std::{
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::UNIX_EPOCH,
+};
+
+use distro::Language;
+use itertools::Itertools;
+use parser::SyntaxConfig;
+use rowan::{GreenNode, GreenNodeData, GreenToken, NodeOrToken, TextLen};
+use rustc_hash::{FxHashMap, FxHasher};
+use serde::{Deserialize, Serialize};
+use syntax::{bibtex, latex};
+
+use crate::{
+    semantics, AuxDocumentData, BibDocumentData, Config, Document, DocumentData, DocumentParams,
+    TexDocumentData,
+};
+
+/// Stores the syntax trees and the semantics of the documents loaded from disk,
+/// so unchanged files do not need to be parsed again after a restart.
+///
+/// An entry is only used if the modification time and the hash of the file
+/// as well as the syntax configuration match the values at the time it was created.
+/// New entries are kept in memory until [`DocumentCache::flush`] is called.
+#[derive(Debug, Clone)]
+pub struct DocumentCache {
+    dir: PathBuf,
+    pending: Arc<Mutex<FxHashMap<PathBuf, Entry>>>,
+}
+
+const CACHE_FORMAT: &str = "texlab-document-cache-1";
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct EntryKey {
+    format: String,
+    path: PathBuf,
+    modified: (u64, u32),
+    text_hash: u64,
+    config_hash: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    key: EntryKey,
+    data: EntryData,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum EntryData {
+    Tex(Vec<u32>, semantics::tex::Semantics),
+    Bib(Vec<u32>, semantics::bib::Semantics),
+    Aux(Vec<u32>, semantics::auxiliary::Semantics),
+}
+
+impl DocumentCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            pending: Arc::default(),
+        }
+    }
+
+    /// Returns the cached document for the file at `path` or parses it and queues a new entry.
+    pub fn load(&self, path: &Path, params: DocumentParams) -> Document {
+        if !matches!(
+            params.language,
+            Language::Tex | Language::Bib | Language::Aux
+        ) {
+            return Document::parse(params);
+        }
+
+        let Some(key) = EntryKey::new(path, &params.text, params.config) else {
+            return Document::parse(params);
+        };
+
+        let file = self.entry_file(path);
+        if let Some(data) = Self::read(&file, &key, &params.text) {
+            return Document::with_data(params, data);
+        }
+
+        let document = Document::parse(params);
+        if let Some(entry) = Entry::new(key, &document.data) {
+            self.pending.lock().unwrap().insert(file, entry);
+        }
+
+        document
+    }
+
+    fn entry_file(&self, path: &Path) -> PathBuf {
+        let mut hasher = FxHasher::default();
+        path.hash(&mut hasher);
+        self.dir.join(format!("{:016x}.json", hasher.finish()))
+    }
+
+    fn read(file: &Path, key: &EntryKey, text: &str) -> Option<DocumentData> {
+        let json = std::fs::read(file).ok()?;
+        let entry: Entry = serde_json::from_slice(&json).ok()?;
+        if entry.key != *key {
+            return None;
+        }
+
+        let data = match entry.data {
+            EntryData::Tex(tree, semantics) => {
+                let green = decode_tree(&tree, text, latex::ROOT as u16)?;
+                DocumentData::Tex(TexDocumentData { green, semantics })
+            }
+            EntryData::Bib(tree, semantics) => {
+                let green = decode_tree(&tree, text, bibtex::ROOT as u16)?;
+                DocumentData::Bib(BibDocumentData { green, semantics })
+            }
+            EntryData::Aux(tree, semantics) => {
+                let green = decode_tree(&tree, text, latex::ROOT as u16)?;
+                DocumentData::Aux(AuxDocumentData { green, semantics })
+            }
+        };
+
+        Some(data)
+    }
+
+    /// Writes the entries that have been queued since the last call to disk.
+    pub fn flush(&self) {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        if pending.is_empty() {
+            return;
+        }
+
+        if let Err(why) = std::fs::create_dir_all(&self.dir) {
+            log::warn!(
+                "Unable to create the cache at {}: {}",
+                self.dir.display(),
+                why
+            );
+            return;
+        }
+
+        for (file, entry) in pending {
+            let result = serde_json::to_vec(&entry)
+                .map_err(std::io::Error::from)
+                .and_then(|json| std::fs::write(&file, json));
+
+            if let Err(why) = result {
+                log::warn!(
+                    "Unable to cache document {}: {}",
+                    entry.key.path.display(),
+                    why
+                );
+            }
+        }
+    }
+}
+
+impl Entry {
+    fn new(key: EntryKey, data: &DocumentData) -> Option<Self> {
+        let data = match data {
+            DocumentData::Tex(data) => {
+                EntryData::Tex(encode_tree(&data.green), data.semantics.clone())
+            }
+            DocumentData::Bib(data) => {
+                EntryData::Bib(encode_tree(&data.green), data.semantics.clone())
+            }
+            DocumentData::Aux(data) => {
+                EntryData::Aux(encode_tree(&data.green), data.semantics.clone())
+            }
+            _ => return None,
+        };
+
+        Some(Self { key, data })
+    }
+}
+
+impl EntryKey {
+    fn new(path: &Path, text: &str, config: &Config) -> Option<Self> {
+        let modified = std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?;
+
+        let mut hasher = FxHasher::default();
+        text.hash(&mut hasher);
+        let text_hash = hasher.finish();
+
+        Some(Self {
+            format: cache_format(),
+            path: path.to_path_buf(),
+            modified: (modified.as_secs(), modified.subsec_nanos()),
+            text_hash,
+            config_hash: hash_syntax_config(&config.syntax),
+        })
+    }
+}
+
+/// Identifies the encoding of the entries. The number of syntax kinds is included
+/// because the trees store the raw kinds, which shift whenever a kind is added or removed.
+fn cache_format() -> String {
+    format!(
+        "{CACHE_FORMAT};{};{};{}",
+        parser::VERSION,
+        latex::ROOT as u16,
+        bibtex::ROOT as u16
+    )
+}
+
+/// The sets of the configuration are sorted because their iteration order is unspecified.
+fn hash_syntax_config(config: &SyntaxConfig) -> u64 {
+    let mut hasher = FxHasher::default();
+    config.follow_package_links.hash(&mut hasher);
+    for set in [
+        &config.math_environments,
+        &config.enum_environments,
+        &config.verbatim_environments,
+        &config.citation_commands,
+        &config.label_reference_commands,
+    ] {
+        set.iter().sorted().collect_vec().hash(&mut hasher);
+    }
+
+    hasher.finish()
+}
+
+/// Flattens the tree into pairs of `(kind << 1, child count)` for nodes
+/// and `(kind << 1 | 1, length)` for tokens. The text of the tokens is taken from the document.
+fn encode_tree(root: &GreenNodeData) -> Vec<u32> {
+    fn visit(node: &GreenNodeData, tree: &mut Vec<u32>) {
+        tree.push(u32::from(node.kind().0) << 1);
+        tree.push(node.children().len() as u32);
+        for child in node.children() {
+            match child {
+                NodeOrToken::Node(node) => visit(node, tree),
+                NodeOrToken::Token(token) => {
+                    tree.push(u32::from(token.kind().0) << 1 | 1);
+                    tree.push(u32::from(token.text_len()));
+                }
+            }
+        }
+    }
+
+    let mut tree = Vec::new();
+    visit(root, &mut tree);
+    tree
+}
+
+/// Rebuilds a tree created by [`encode_tree`]. Kinds above `max_kind` are rejected
+/// because converting them to the syntax kinds of the language would panic.
+fn decode_tree(tree: &[u32], text: &str, max_kind: u16) -> Option<GreenNode> {
+    fn visit(
+        tree: &mut impl Iterator<Item = u32>,
+        text: &str,
+        max_kind: u16,
+        offset: &mut usize,
+    ) -> Option<NodeOrToken<GreenNode, GreenToken>> {
+        let tag = tree.next()?;
+        let value = tree.next()?;
+        let kind = u16::try_from(tag >> 1)
+            .ok()
+            .filter(|kind| *kind <= max_kind)?;
+        let kind = rowan::SyntaxKind(kind);
+        if tag & 1 == 1 {
+            let end = *offset + value as usize;
+            let token = GreenToken::new(kind, text.get(*offset..end)?);
+            *offset = end;
+            Some(NodeOrToken::Token(token))
+        } else {
+            let children = (0..value)
+                .map(|_| visit(tree, text, max_kind, offset))
+                .collect::<Option<Vec<_>>>()?;
+
+            Some(NodeOrToken::Node(GreenNode::new(kind, children)))
+        }
+    }
+
+    let mut offset = 0;
+    let root = visit(&mut tree.iter().copied(), text, max_kind, &mut offset)?.into_node()?;
+    (offset == text.len() && root.text_len() == text.text_len()).then_some(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use parser::{parse_bibtex, parse_latex, SyntaxConfig};
+    use rowan::GreenNode;
+    use syntax::{bibtex, latex};
+
+    use super::{decode_tree, encode_tree, DocumentCache, EntryKey};
+    use crate::{Config, DocumentParams, Owner};
+
+    #[test]
+    fn test_tree_latex() {
+        let text = "\\documentclass{article}\n\\begin{document}\n\\section{Foo}\n\\end{document}\n";
+        let green = parse_latex(text, &SyntaxConfig::default());
+        let tree = encode_tree(&green);
+        assert_eq!(decode_tree(&tree, text, latex::ROOT as u16), Some(green));
+    }
+
+    #[test]
+    fn test_tree_bibtex() {
+        let text = "@article{foo, author = {Foo Bar}, year = 2020}\n";
+        let green = parse_bibtex(text);
+        let tree = encode_tree(&green);
+        assert_eq!(decode_tree(&tree, text, bibtex::ROOT as u16), Some(green));
+    }
+
+    #[test]
+    fn test_tree_text_changed() {
+        let green = parse_latex("\\foo", &SyntaxConfig::default());
+        let tree = encode_tree(&green);
+        assert_eq!(decode_tree(&tree, "\\fooo", latex::ROOT as u16), None);
+        assert_eq!(decode_tree(&tree, "\\fo", latex::ROOT as u16), None);
+    }
+
+    #[test]
+    fn test_tree_unknown_kind() {
+        let green = GreenNode::new(rowan::SyntaxKind(latex::ROOT as u16 + 1), []);
+        let tree = encode_tree(&green);
+        assert_eq!(decode_tree(&tree, "", latex::ROOT as u16), None);
+    }
+
+    #[test]
+    fn test_tree_truncated() {
+        let text = "\\foo";
+        let green = parse_latex(text, &SyntaxConfig::default());
+        let tree = encode_tree(&green);
+        assert_eq!(
+            decode_tree(&tree[..tree.len() - 1], text, latex::ROOT as u16),
+            None
+        );
+    }
+
+    #[test]
+    fn test_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.tex");
+        std::fs::write(&path, "foo").unwrap();
+
+        let config = Config::default();
+        let key = EntryKey::new(&path, "foo", &config).unwrap();
+        assert_eq!(EntryKey::new(&path, "foo", &config), Some(key));
+    }
+
+    #[test]
+    fn test_key_text_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.tex");
+        std::fs::write(&path, "foo").unwrap();
+
+        let config = Config::default();
+        let key = EntryKey::new(&path, "foo", &config).unwrap();
+        assert_ne!(EntryKey::new(&path, "bar", &config), Some(key));
+    }
+
+    #[test]
+    fn test_key_config_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.tex");
+        std::fs::write(&path, "foo").unwrap();
+
+        let key = EntryKey::new(&path, "foo", &Config::default()).unwrap();
+        let mut config = Config::default();
+        config.syntax.math_environments.insert("foo".into());
+        assert_ne!(EntryKey::new(&path, "foo", &config), Some(key));
+    }
+
+    #[test]
+    fn test_key_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.tex");
+        assert_eq!(EntryKey::new(&path, "foo", &Config::default()), None);
+    }
+
+    #[test]
+    fn test_flush() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("main.tex");
+        std::fs::write(&path, "\\foo").unwrap();
+
+        let cache_dir = dir.path().join("cache");
+        let cache = DocumentCache::new(cache_dir.clone());
+        let config = Config::default();
+        let params = || DocumentParams {
+            uri: url::Url::from_file_path(&path).unwrap(),
+            text: "\\foo".into(),
+            language: distro::Language::Tex,
+            owner: Owner::Server,
+            cursor: line_index::LineCol { line: 0, col: 0 },
+            config: &config,
+        };
+
+        cache.load(&path, params());
+        assert!(!cache_dir.exists());
+
+        cache.flush();
+        let file = cache.entry_file(&path);
+        assert!(file.exists());
+
+        cache.load(&path, params());
+        std::fs::remove_file(&file).unwrap();
+        cache.flush();
+        assert!(!file.exists());
+    }
+}
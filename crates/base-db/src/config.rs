@@ -14,6 +14,8 @@ pub struct Config {
     pub syntax: SyntaxConfig,
     pub completion: CompletionConfig,
     pub inlay_hints: InlayHintConfig,
+    pub citation: CitationConfig,
+    pub workspace: WorkspaceConfig,
 }
 
 #[derive(Debug)]
@@ -21,11 +23,21 @@ pub struct BuildConfig {
     pub program: String,
     pub args: Vec<String>,
     pub on_save: bool,
+    pub on_idle: bool,
+    pub idle_delay: Duration,
     pub forward_search_after: bool,
     pub aux_dir: String,
     pub log_dir: String,
     pub pdf_dir: String,
     pub output_filename: Option<String>,
+    pub profiles: Vec<BuildProfile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BuildProfile {
+    pub name: String,
+    pub program: String,
+    pub args: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -81,11 +93,32 @@ pub struct SymbolConfig {
 pub struct InlayHintConfig {
     pub label_definitions: bool,
     pub label_references: bool,
+    pub citations: bool,
+    pub environment_ends: bool,
+    pub parameter_names: bool,
 }
 
 #[derive(Debug)]
 pub struct CompletionConfig {
     pub matcher: MatchingAlgo,
+    pub citation_ranking: CitationRanking,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CitationRanking {
+    Relevance,
+    Recency,
+    Usage,
+}
+
+#[derive(Debug)]
+pub struct CitationConfig {
+    pub style: citeproc::CitationStyle,
+}
+
+#[derive(Debug, Default)]
+pub struct WorkspaceConfig {
+    pub exclude: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -108,6 +141,8 @@ impl Default for Config {
             syntax: SyntaxConfig::default(),
             completion: CompletionConfig::default(),
             inlay_hints: InlayHintConfig::default(),
+            citation: CitationConfig::default(),
+            workspace: WorkspaceConfig::default(),
         }
     }
 }
@@ -121,15 +156,73 @@ impl Default for BuildConfig {
                 .map(String::from)
                 .collect(),
             on_save: false,
+            on_idle: false,
+            idle_delay: Duration::from_millis(1000),
             forward_search_after: false,
             aux_dir: String::from("."),
             log_dir: String::from("."),
             pdf_dir: String::from("."),
             output_filename: None,
+            profiles: vec![
+                BuildProfile::new(
+                    "draft",
+                    "pdflatex",
+                    &["-interaction=nonstopmode", "-synctex=1", "%f"],
+                ),
+                BuildProfile::new(
+                    "final",
+                    "latexmk",
+                    &[
+                        "-pdf",
+                        "-bibtex",
+                        "-interaction=nonstopmode",
+                        "-synctex=1",
+                        "%f",
+                    ],
+                ),
+                BuildProfile::new(
+                    "pdflatex",
+                    "latexmk",
+                    &["-pdf", "-interaction=nonstopmode", "-synctex=1", "%f"],
+                ),
+                BuildProfile::new(
+                    "lualatex",
+                    "latexmk",
+                    &["-lualatex", "-interaction=nonstopmode", "-synctex=1", "%f"],
+                ),
+                BuildProfile::new(
+                    "xelatex",
+                    "latexmk",
+                    &["-xelatex", "-interaction=nonstopmode", "-synctex=1", "%f"],
+                ),
+            ],
         }
     }
 }
 
+impl BuildProfile {
+    pub fn new(name: &str, program: &str, args: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            program: program.into(),
+            args: args.iter().copied().map(String::from).collect(),
+        }
+    }
+}
+
+impl BuildConfig {
+    /// Returns `true` if the user did not configure a custom build command.
+    pub fn has_default_command(&self) -> bool {
+        let default = Self::default();
+        self.program == default.program && self.args == default.args
+    }
+
+    /// Finds the build profile with the given name.
+    pub fn profile(&self, name: &str) -> Option<&BuildProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+}
+
 impl Default for DiagnosticsConfig {
     fn default() -> Self {
         Self {
@@ -186,6 +279,9 @@ impl Default for InlayHintConfig {
         Self {
             label_definitions: true,
             label_references: true,
+            citations: true,
+            environment_ends: true,
+            parameter_names: true,
         }
     }
 }
@@ -194,6 +290,15 @@ impl Default for CompletionConfig {
     fn default() -> Self {
         Self {
             matcher: MatchingAlgo::SkimIgnoreCase,
+            citation_ranking: CitationRanking::Relevance,
+        }
+    }
+}
+
+impl Default for CitationConfig {
+    fn default() -> Self {
+        Self {
+            style: citeproc::CitationStyle::Default,
         }
     }
 }
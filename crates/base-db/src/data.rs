@@ -1,11 +1,13 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct BibtexEntryType<'a> {
-   pub name: &'a str,
-   pub category: BibtexEntryTypeCategory,
-   pub documentation: Option<&'a str>,
+    pub name: &'a str,
+    pub category: BibtexEntryTypeCategory,
+    pub documentation: Option<&'a str>,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
 pub enum BibtexEntryTypeCategory {
     Misc,
     String,
@@ -18,23 +20,28 @@ pub enum BibtexEntryTypeCategory {
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct BibtexFieldType<'a> {
-   pub name: &'a str,
-   pub documentation: &'a str,
+    pub name: &'a str,
+    pub documentation: &'a str,
 }
 
 impl<'a> BibtexEntryType<'a> {
     pub fn find(name: &str) -> Option<Self> {
-        BIBTEX_ENTRY_TYPES.iter().find(|ty| ty.name.eq_ignore_ascii_case(name)).copied()
+        BIBTEX_ENTRY_TYPES
+            .iter()
+            .find(|ty| ty.name.eq_ignore_ascii_case(name))
+            .copied()
     }
 }
 
 impl<'a> BibtexFieldType<'a> {
     pub fn find(name: &str) -> Option<Self> {
-        BIBTEX_FIELD_TYPES.iter().find(|ty| ty.name.eq_ignore_ascii_case(name)).copied()
+        BIBTEX_FIELD_TYPES
+            .iter()
+            .find(|ty| ty.name.eq_ignore_ascii_case(name))
+            .copied()
     }
 }
 
-
 pub static BIBTEX_ENTRY_TYPES: &[BibtexEntryType<'static>] = &[
     BibtexEntryType {
         name: "@preamble",
@@ -3,7 +3,9 @@ use std::path::PathBuf;
 use distro::Language;
 use line_index::{LineCol, LineIndex};
 use rowan::TextRange;
-use syntax::{bibtex, latex, latexmkrc::LatexmkrcData, BuildError};
+use syntax::{
+    bibtex, fls::FlsData, latex, latexmkrc::LatexmkrcData, tectonic::TectonicData, BuildError,
+};
 use url::Url;
 
 use crate::{semantics, Config};
@@ -40,6 +42,14 @@ pub struct Document {
 
 impl Document {
     pub fn parse(params: DocumentParams) -> Self {
+        let data = DocumentData::parse(&params.text, params.language, params.config);
+        Self::with_data(params, data)
+    }
+
+    /// Creates a document from data which has been computed before (e.g. by the [`DocumentCache`]).
+    ///
+    /// [`DocumentCache`]: crate::DocumentCache
+    pub fn with_data(params: DocumentParams, data: DocumentData) -> Self {
         let DocumentParams { uri, text, .. } = params;
 
         let dir = uri.join(".").unwrap();
@@ -52,37 +62,6 @@ impl Document {
 
         let line_index = LineIndex::new(&text);
 
-        let data = match params.language {
-            Language::Tex => {
-                let green = parser::parse_latex(&text, &params.config.syntax);
-                let mut semantics = semantics::tex::Semantics::default();
-                semantics.process_root(&latex::SyntaxNode::new_root(green.clone()));
-                DocumentData::Tex(TexDocumentData { green, semantics })
-            }
-            Language::Bib => {
-                let green = parser::parse_bibtex(&text);
-                let mut semantics = semantics::bib::Semantics::default();
-                semantics.process_root(&bibtex::SyntaxNode::new_root(green.clone()));
-                DocumentData::Bib(BibDocumentData { green, semantics })
-            }
-            Language::Aux => {
-                let green = parser::parse_latex(&text, &params.config.syntax);
-                let mut semantics = semantics::auxiliary::Semantics::default();
-                semantics.process_root(&latex::SyntaxNode::new_root(green.clone()));
-                DocumentData::Aux(AuxDocumentData { green, semantics })
-            }
-            Language::Log => {
-                let errors = parser::parse_build_log(&text).errors;
-                DocumentData::Log(LogDocumentData { errors })
-            }
-            Language::Root => DocumentData::Root,
-            Language::Latexmkrc => {
-                let data = parser::parse_latexmkrc(&text).unwrap_or_default();
-                DocumentData::Latexmkrc(data)
-            }
-            Language::Tectonic => DocumentData::Tectonic,
-        };
-
         Self {
             uri,
             dir,
@@ -135,12 +114,55 @@ pub enum DocumentData {
     Bib(BibDocumentData),
     Aux(AuxDocumentData),
     Log(LogDocumentData),
+    Fls(FlsData),
     Root,
     Latexmkrc(LatexmkrcData),
-    Tectonic,
+    Tectonic(TectonicData),
 }
 
 impl DocumentData {
+    pub fn parse(text: &str, language: Language, config: &Config) -> Self {
+        match language {
+            Language::Tex => {
+                let green = parser::parse_latex(text, &config.syntax);
+                let mut semantics = semantics::tex::Semantics::default();
+                semantics.process_root(&latex::SyntaxNode::new_root(green.clone()));
+                DocumentData::Tex(TexDocumentData { green, semantics })
+            }
+            Language::Bib => {
+                let green = parser::parse_bibtex(text);
+                let mut semantics = semantics::bib::Semantics::default();
+                semantics.process_root(&bibtex::SyntaxNode::new_root(green.clone()));
+                DocumentData::Bib(BibDocumentData { green, semantics })
+            }
+            Language::Aux => {
+                let green = parser::parse_latex(text, &config.syntax);
+                let mut semantics = semantics::auxiliary::Semantics::default();
+                semantics.process_root(&latex::SyntaxNode::new_root(green.clone()));
+                DocumentData::Aux(AuxDocumentData { green, semantics })
+            }
+            Language::Log => {
+                let errors = parser::parse_build_log(text).errors;
+                DocumentData::Log(LogDocumentData { errors })
+            }
+            Language::Blg => {
+                let errors = parser::parse_bib_log(text).errors;
+                DocumentData::Log(LogDocumentData { errors })
+            }
+            Language::Ilg => {
+                let errors = parser::parse_index_log(text).errors;
+                DocumentData::Log(LogDocumentData { errors })
+            }
+            Language::Fls => DocumentData::Fls(parser::parse_fls(text)),
+            Language::Root => DocumentData::Root,
+            Language::Latexmkrc => {
+                let data = parser::parse_latexmkrc(text);
+                DocumentData::Latexmkrc(data)
+            }
+            Language::Tectonic => DocumentData::Tectonic(parser::parse_tectonic(text)),
+        }
+    }
+
     pub fn as_tex(&self) -> Option<&TexDocumentData> {
         if let DocumentData::Tex(data) = self {
             Some(data)
@@ -173,6 +195,14 @@ impl DocumentData {
         }
     }
 
+    pub fn as_fls(&self) -> Option<&FlsData> {
+        if let DocumentData::Fls(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
+
     pub fn as_latexmkrc(&self) -> Option<&LatexmkrcData> {
         if let DocumentData::Latexmkrc(data) = self {
             Some(data)
@@ -180,6 +210,14 @@ impl DocumentData {
             None
         }
     }
+
+    pub fn as_tectonic(&self) -> Option<&TectonicData> {
+        if let DocumentData::Tectonic(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1,23 +1,67 @@
-use std::{ffi::OsStr, path::PathBuf};
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
 
 use distro::Language;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 use percent_encoding::percent_decode_str;
 use rustc_hash::FxHashSet;
+use syntax::fls::FlsData;
 use url::Url;
 
 use crate::{semantics, Document, DocumentData, Workspace};
 
 pub static HOME_DIR: Lazy<Option<PathBuf>> = Lazy::new(dirs::home_dir);
 
+/// Returns the directory containing the input files of a `Tectonic.toml` project.
+pub fn tectonic_src_dir(document: &Document) -> Option<Url> {
+    if document.language != Language::Tectonic {
+        return None;
+    }
+
+    document.dir.join("src/").ok()
+}
+
+/// Returns the input files of all outputs of a `Tectonic.toml` project.
+pub fn tectonic_inputs(document: &Document) -> Vec<Url> {
+    let (Some(src_dir), Some(data)) = (tectonic_src_dir(document), document.data.as_tectonic())
+    else {
+        return Vec::new();
+    };
+
+    data.outputs
+        .iter()
+        .flat_map(|output| &output.inputs)
+        .filter_map(|input| src_dir.join(input).ok())
+        .collect()
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct Edge<'a> {
     pub source: &'a Document,
     pub target: &'a Document,
+    pub kind: EdgeKind,
     pub weight: Option<EdgeWeight<'a>>,
 }
 
+/// Describes how the target of an edge was found.
+/// Only artifacts are not traversed when building the graph.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum EdgeKind {
+    /// An explicit link like `\input{...}`, which always has an [`EdgeWeight`].
+    Link,
+    /// A document which declares the source as its root using a `% !TEX root = ...` comment.
+    MagicRoot,
+    /// An input file of a `Tectonic.toml` project.
+    TectonicInput,
+    /// The `.fls` file of a document or a file which has been recorded by the engine.
+    Recorded,
+    /// An output file of the engine like the `.aux` or `.log` file.
+    Artifact,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct EdgeWeight<'a> {
     pub link: &'a semantics::tex::Link,
@@ -49,13 +93,21 @@ impl<'a> Graph<'a> {
         while let Some((source, base_dir)) = stack.pop() {
             let index = graph.edges.len();
             graph.add_explicit_edges(source, &base_dir);
+            graph.add_magic_root_edges(source);
+            graph.add_recorder_edges(source, &base_dir);
             for edge in &graph.edges[index..] {
-                let Some(weight) = edge.weight.as_ref() else {
-                    continue;
+                let new_base_dir = match (edge.kind, edge.weight.as_ref()) {
+                    (EdgeKind::Link, Some(weight)) => weight.new_base_dir.clone(),
+                    (EdgeKind::MagicRoot | EdgeKind::Recorded, _) => base_dir.clone(),
+                    (EdgeKind::TectonicInput, _) => match tectonic_src_dir(source) {
+                        Some(src_dir) => src_dir,
+                        None => continue,
+                    },
+                    _ => continue,
                 };
 
                 if visited.insert(&edge.target.uri) {
-                    stack.push((edge.target, weight.new_base_dir.clone()));
+                    stack.push((edge.target, new_base_dir));
                 }
             }
 
@@ -72,6 +124,16 @@ impl<'a> Graph<'a> {
     }
 
     fn add_explicit_edges(&mut self, source: &'a Document, base_dir: &Url) {
+        if source.language == Language::Tectonic {
+            self.add_tectonic_inputs(source);
+            return;
+        }
+
+        if let DocumentData::Fls(data) = &source.data {
+            self.add_recorded_files(source, base_dir, data);
+            return;
+        }
+
         let DocumentData::Tex(data) = &source.data else {
             return;
         };
@@ -130,6 +192,7 @@ impl<'a> Graph<'a> {
                     self.edges.push(Edge {
                         source,
                         target,
+                        kind: EdgeKind::Link,
                         weight,
                     });
 
@@ -142,6 +205,115 @@ impl<'a> Graph<'a> {
         }
     }
 
+    /// Adds edges to the documents which declare the source as their root
+    /// using a `% !TEX root = ...` comment.
+    fn add_magic_root_edges(&mut self, source: &'a Document) {
+        for target in self.workspace.magic_children(source) {
+            if self
+                .edges
+                .iter()
+                .any(|edge| edge.source == source && edge.target == target)
+            {
+                continue;
+            }
+
+            self.edges.push(Edge {
+                source,
+                target,
+                kind: EdgeKind::MagicRoot,
+                weight: None,
+            });
+        }
+    }
+
+    fn add_tectonic_inputs(&mut self, source: &'a Document) {
+        for target_uri in tectonic_inputs(source) {
+            match self.workspace.lookup(&target_uri) {
+                Some(target) => {
+                    self.edges.push(Edge {
+                        source,
+                        target,
+                        kind: EdgeKind::TectonicInput,
+                        weight: None,
+                    });
+                }
+                None => {
+                    self.missing.push(target_uri);
+                }
+            }
+        }
+    }
+
+    /// Adds an edge to the `.fls` file which is written when running the engine with `-recorder`.
+    /// Unlike the other artifacts, the recorder file is traversed to find the files loaded by the engine.
+    fn add_recorder_edges(&mut self, source: &'a Document, base_dir: &Url) {
+        if source.language != Language::Tex {
+            return;
+        }
+
+        let aux_dir = self.workspace.aux_dir(base_dir);
+        let relative_path = base_dir.make_relative(&source.uri).unwrap();
+        self.add_artifact(
+            source,
+            &aux_dir.join(&relative_path).unwrap(),
+            "fls",
+            EdgeKind::Recorded,
+        );
+        self.add_artifact(source, &aux_dir, "fls", EdgeKind::Recorded);
+        self.add_artifact(source, base_dir, "fls", EdgeKind::Recorded);
+    }
+
+    /// Adds edges to the files of the project which have been read or written by the engine.
+    /// Files outside of the working directory of the engine (e.g. from the TeX distro) are ignored.
+    fn add_recorded_files(&mut self, source: &'a Document, base_dir: &Url, data: &FlsData) {
+        let pwd = data.pwd.as_deref().map(Path::new);
+        let pwd_uri = pwd
+            .and_then(|path| Url::from_directory_path(path).ok())
+            .unwrap_or_else(|| base_dir.clone());
+
+        for path in data.inputs.iter().chain(&data.outputs).map(Path::new) {
+            let target_uri = if path.is_absolute() {
+                if !pwd.is_some_and(|pwd| path.starts_with(pwd)) {
+                    continue;
+                }
+
+                Url::from_file_path(path).ok()
+            } else {
+                pwd_uri.join(&path.to_string_lossy()).ok()
+            };
+
+            let Some(target_uri) = target_uri else {
+                continue;
+            };
+
+            if target_uri == source.uri
+                || target_uri == self.start.uri
+                || self.edges.iter().any(|edge| edge.target.uri == target_uri)
+            {
+                continue;
+            }
+
+            match self.workspace.lookup(&target_uri) {
+                Some(target) => {
+                    self.edges.push(Edge {
+                        source,
+                        target,
+                        kind: EdgeKind::Recorded,
+                        weight: None,
+                    });
+                }
+                None if matches!(
+                    Language::from_path(path),
+                    Some(Language::Tex | Language::Bib)
+                ) =>
+                {
+                    self.missing.push(target_uri);
+                }
+                None => {}
+            }
+        }
+    }
+
     fn add_implicit_edges(&mut self, source: &'a Document, base_dir: &Url) {
         if source.language == Language::Tex {
             let aux_dir = self.workspace.aux_dir(base_dir);
@@ -149,17 +321,38 @@ impl<'a> Graph<'a> {
 
             let relative_path = base_dir.make_relative(&source.uri).unwrap();
 
-            self.add_artifact(source, &aux_dir.join(&relative_path).unwrap(), "aux");
-            self.add_artifact(source, &aux_dir, "aux");
-            self.add_artifact(source, base_dir, "aux");
+            self.add_artifact(
+                source,
+                &aux_dir.join(&relative_path).unwrap(),
+                "aux",
+                EdgeKind::Artifact,
+            );
+            self.add_artifact(source, &aux_dir, "aux", EdgeKind::Artifact);
+            self.add_artifact(source, base_dir, "aux", EdgeKind::Artifact);
 
-            self.add_artifact(source, &log_dir.join(&relative_path).unwrap(), "log");
-            self.add_artifact(source, &log_dir, "log");
-            self.add_artifact(source, base_dir, "log");
+            self.add_artifact(
+                source,
+                &log_dir.join(&relative_path).unwrap(),
+                "log",
+                EdgeKind::Artifact,
+            );
+            self.add_artifact(source, &log_dir, "log", EdgeKind::Artifact);
+            self.add_artifact(source, base_dir, "log", EdgeKind::Artifact);
+
+            for extension in ["blg", "ilg"] {
+                self.add_artifact(source, &aux_dir, extension, EdgeKind::Artifact);
+                self.add_artifact(source, base_dir, extension, EdgeKind::Artifact);
+            }
         }
     }
 
-    fn add_artifact(&mut self, source: &'a Document, base_dir: &Url, extension: &str) {
+    fn add_artifact(
+        &mut self,
+        source: &'a Document,
+        base_dir: &Url,
+        extension: &str,
+        kind: EdgeKind,
+    ) {
         let mut path = PathBuf::from(
             percent_decode_str(source.uri.path())
                 .decode_utf8_lossy()
@@ -180,6 +373,7 @@ impl<'a> Graph<'a> {
                 self.edges.push(Edge {
                     source,
                     target,
+                    kind,
                     weight: None,
                 });
             }
@@ -1,3 +1,4 @@
+mod cache;
 mod config;
 pub mod data;
 mod document;
@@ -6,7 +7,7 @@ pub mod semantics;
 pub mod util;
 mod workspace;
 
-pub use self::{config::*, document::*, workspace::*};
+pub use self::{cache::DocumentCache, config::*, document::*, workspace::*};
 
 #[derive(Debug)]
 pub struct FeatureParams<'a> {
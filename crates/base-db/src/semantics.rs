@@ -2,7 +2,9 @@ pub mod auxiliary;
 pub mod bib;
 pub mod tex;
 
-#[derive(PartialEq, Eq, Clone, Hash)]
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct Span {
     pub text: String,
     pub range: rowan::TextRange,
@@ -1,8 +1,9 @@
 use rowan::ast::AstNode;
 use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
 use syntax::latex;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Semantics {
     pub label_numbers: FxHashMap<String, String>,
 }
@@ -24,7 +25,10 @@ impl Semantics {
         let Some(name) = label_number
             .name()
             .and_then(|group| group.key())
-            .map(|key| key.to_string()) else { return };
+            .map(|key| key.to_string())
+        else {
+            return;
+        };
 
         let Some(text) = label_number
             .text()
@@ -32,7 +36,10 @@ impl Semantics {
             .into_iter()
             .flatten()
             .find(|node| node.kind() == latex::TEXT || node.kind() == latex::MIXED_GROUP)
-            .map(|node| node.text().to_string()) else { return };
+            .map(|node| node.text().to_string())
+        else {
+            return;
+        };
 
         self.label_numbers.insert(name, text);
     }
@@ -1,13 +1,14 @@
-use bibtex_utils::field::text::TextFieldData;
+use bibtex_utils::field::{author::AuthorFieldData, date::DateFieldData, text::TextFieldData};
 use itertools::Itertools;
 use rowan::{ast::AstNode, TextRange};
+use serde::{Deserialize, Serialize};
 use syntax::bibtex::{self, HasName, HasType, HasValue};
 
 use crate::data::{BibtexEntryType, BibtexEntryTypeCategory};
 
 use super::Span;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Semantics {
     pub entries: Vec<Entry>,
     pub strings: Vec<StringDef>,
@@ -30,15 +31,30 @@ impl Semantics {
             let category = BibtexEntryType::find(type_token.text())
                 .map_or(BibtexEntryTypeCategory::Misc, |ty| ty.category);
 
-            let field_values = entry
+            let fields: Vec<(String, String)> = entry
                 .fields()
-                .filter_map(|field| Some(TextFieldData::parse(&field.value()?)?.text));
+                .filter_map(|field| {
+                    let name = field.name_token()?.text().to_lowercase();
+                    let value = TextFieldData::parse(&field.value()?)?.text;
+                    Some((name, value))
+                })
+                .collect();
 
             let keywords = [name.text().into(), type_token.text().into()]
                 .into_iter()
-                .chain(field_values)
+                .chain(fields.iter().map(|(_, value)| value.clone()))
                 .join(" ");
 
+            let year = entry
+                .fields()
+                .filter(|field| {
+                    field.name_token().is_some_and(|name| {
+                        let name = name.text();
+                        name.eq_ignore_ascii_case("year") || name.eq_ignore_ascii_case("date")
+                    })
+                })
+                .find_map(|field| parse_year(&field.value()?));
+
             self.entries.push(Entry {
                 name: Span {
                     range: name.text_range(),
@@ -46,7 +62,11 @@ impl Semantics {
                 },
                 full_range: entry.syntax().text_range(),
                 category,
+                entry_type: type_token.text()[1..].to_lowercase(),
                 keywords,
+                fields,
+                year,
+                identity: EntryIdentity::new(&entry),
             });
         }
     }
@@ -64,15 +84,162 @@ impl Semantics {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct Entry {
     pub name: Span,
     pub full_range: TextRange,
     pub keywords: String,
     pub category: BibtexEntryTypeCategory,
+    /// The lowercase entry type without the leading `@`.
+    pub entry_type: String,
+    /// The text of each field together with the lowercase field name.
+    pub fields: Vec<(String, String)>,
+    pub year: Option<i32>,
+    pub identity: EntryIdentity,
+}
+
+impl Entry {
+    /// Returns the text of the field with the given lowercase name.
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Normalized bibliographic data which identifies the work described by an entry.
+/// Two entries with different keys but a matching identity most likely refer to the same work.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Hash, Serialize, Deserialize)]
+pub struct EntryIdentity {
+    pub doi: Option<String>,
+    pub eprint: Option<String>,
+    pub title_author_year: Option<String>,
+}
+
+impl EntryIdentity {
+    fn new(entry: &bibtex::Entry) -> Self {
+        let mut doi = None;
+        let mut eprint = None;
+        let mut eprint_type = None;
+        let mut title = None;
+        let mut author = None;
+        let mut year = None;
+        for field in entry.fields() {
+            let (Some(name), Some(value)) = (field.name_token(), field.value()) else {
+                continue;
+            };
+
+            match name.text().to_ascii_lowercase().as_str() {
+                "doi" => doi = TextFieldData::parse(&value).map(|data| data.text),
+                "eprint" => eprint = TextFieldData::parse(&value).map(|data| data.text),
+                "eprinttype" | "archiveprefix" => {
+                    eprint_type = TextFieldData::parse(&value).map(|data| data.text);
+                }
+                "title" => title = TextFieldData::parse(&value).map(|data| data.text),
+                "author" => {
+                    author = AuthorFieldData::parse(&value)
+                        .and_then(|data| Some(data.authors.first()?.surname().to_lowercase()));
+                }
+                "year" | "date" => year = year.or_else(|| parse_year(&value)),
+                _ => {}
+            };
+        }
+
+        let doi = doi
+            .map(|doi| normalize_doi(&doi))
+            .filter(|doi| !doi.is_empty());
+
+        let is_arxiv = eprint_type.map_or(true, |ty| ty.eq_ignore_ascii_case("arxiv"));
+        let eprint = eprint
+            .filter(|_| is_arxiv)
+            .map(|eprint| normalize_arxiv_id(&eprint))
+            .filter(|eprint| !eprint.is_empty());
+
+        let title_author_year = title
+            .map(|title| normalize_title(&title))
+            .filter(|title| !title.is_empty())
+            .zip(author)
+            .zip(year)
+            .map(|((title, author), year)| format!("{title}|{author}|{year}"));
+
+        Self {
+            doi,
+            eprint,
+            title_author_year,
+        }
+    }
+
+    /// Returns `true` if both identities share at least one identifier.
+    pub fn matches(&self, other: &Self) -> bool {
+        fn eq(a: &Option<String>, b: &Option<String>) -> bool {
+            a.is_some() && a == b
+        }
+
+        eq(&self.doi, &other.doi)
+            || eq(&self.eprint, &other.eprint)
+            || eq(&self.title_author_year, &other.title_author_year)
+    }
+}
+
+fn parse_year(value: &bibtex::Value) -> Option<i32> {
+    match DateFieldData::parse(value)? {
+        DateFieldData::Year(year) => Some(year),
+        DateFieldData::Date(date) => date.format("%Y").to_string().parse().ok(),
+        DateFieldData::Month(_) | DateFieldData::Other(_) => None,
+    }
+}
+
+/// Removes the resolver URL or the `doi:` prefix from a DOI.
+pub fn strip_doi_prefix(doi: &str) -> &str {
+    [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "http://dx.doi.org/",
+        "doi:",
+    ]
+    .iter()
+    .find_map(|prefix| {
+        doi.get(..prefix.len())
+            .filter(|start| start.eq_ignore_ascii_case(prefix))
+            .map(|_| &doi[prefix.len()..])
+    })
+    .unwrap_or(doi)
+}
+
+fn normalize_doi(doi: &str) -> String {
+    strip_doi_prefix(doi.trim()).trim().to_lowercase()
+}
+
+fn normalize_arxiv_id(eprint: &str) -> String {
+    let eprint = eprint.trim().to_lowercase();
+    let eprint = eprint.strip_prefix("arxiv:").unwrap_or(&eprint);
+
+    // Strip the version suffix (e.g. `2101.00001v2`).
+    match eprint.rfind('v') {
+        Some(index)
+            if index > 0
+                && eprint.len() > index + 1
+                && eprint[index + 1..].chars().all(|c| c.is_ascii_digit()) =>
+        {
+            eprint[..index].to_string()
+        }
+        _ => eprint.to_string(),
+    }
+}
+
+fn normalize_title(title: &str) -> String {
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect::<String>()
+        .split_whitespace()
+        .join(" ")
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StringDef {
     pub name: Span,
     pub full_range: TextRange,
@@ -1,10 +1,11 @@
-use rowan::{ast::AstNode, TextLen, TextRange};
+use rowan::{ast::AstNode, TextLen, TextRange, TextSize};
 use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
 use syntax::latex::{self, HasBrack, HasCurly};
 
 use super::Span;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Semantics {
     pub links: Vec<Link>,
     pub labels: Vec<Label>,
@@ -13,18 +14,26 @@ pub struct Semantics {
     pub environments: Vec<Span>,
     pub theorem_definitions: Vec<TheoremDefinition>,
     pub graphics_paths: FxHashSet<String>,
+    pub magic_comments: Vec<MagicComment>,
     pub can_be_root: bool,
     pub can_be_compiled: bool,
 }
 
 impl Semantics {
     pub fn process_root(&mut self, root: &latex::SyntaxNode) {
+        let mut is_header = true;
         for node in root.descendants_with_tokens() {
             match node {
                 latex::SyntaxElement::Node(node) => {
                     self.process_node(&node);
                 }
                 latex::SyntaxElement::Token(token) => {
+                    match token.kind() {
+                        latex::COMMENT if is_header => self.process_magic_comment(&token),
+                        latex::COMMENT | latex::WHITESPACE | latex::LINE_BREAK => {}
+                        _ => is_header = false,
+                    };
+
                     if token.kind() == latex::COMMAND_NAME {
                         let range = token.text_range();
                         let range = TextRange::new(range.start() + "\\".text_len(), range.end());
@@ -64,6 +73,51 @@ impl Semantics {
         }
     }
 
+    /// Processes TeXShop-style comments like `% !TEX root = main.tex`
+    /// at the beginning of the document.
+    fn process_magic_comment(&mut self, token: &latex::SyntaxToken) {
+        let text = token.text();
+        let rest = text.trim_start_matches('%').trim_start();
+        let Some(prefix) = rest.get(..4).map(str::to_ascii_lowercase) else {
+            return;
+        };
+
+        let Some((key, value)) = rest[4..].split_once('=') else {
+            return;
+        };
+
+        let key = key.trim().to_ascii_lowercase();
+        let kind = match (prefix.as_str(), key.as_str()) {
+            ("!tex", "root") => MagicCommentKind::Root,
+            ("!tex", "program" | "ts-program") => MagicCommentKind::Program,
+            ("!tex", "spellcheck") => MagicCommentKind::Spellcheck,
+            ("!bib", "program" | "ts-program") => MagicCommentKind::BibProgram,
+            _ => return,
+        };
+
+        let value_start = text.len() - value.trim_start().len();
+        let value = value.trim();
+        if value.is_empty() {
+            return;
+        }
+
+        let range_start = token.text_range().start() + TextSize::try_from(value_start).unwrap();
+        self.magic_comments.push(MagicComment {
+            kind,
+            value: Span {
+                text: value.into(),
+                range: TextRange::at(range_start, value.text_len()),
+            },
+        });
+    }
+
+    pub fn magic_comment(&self, kind: MagicCommentKind) -> Option<&Span> {
+        self.magic_comments
+            .iter()
+            .find(|comment| comment.kind == kind)
+            .map(|comment| &comment.value)
+    }
+
     fn process_include(&mut self, include: latex::Include) {
         let Some(list) = include.path_list() else {
             return;
@@ -278,7 +332,7 @@ impl Semantics {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Serialize, Deserialize)]
 pub enum LinkKind {
     Sty,
     Cls,
@@ -297,7 +351,7 @@ impl LinkKind {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct Link {
     pub kind: LinkKind,
     pub path: Span,
@@ -314,14 +368,14 @@ impl Link {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Serialize, Deserialize)]
 pub enum LabelKind {
     Definition,
     Reference,
     ReferenceRange,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Label {
     pub kind: LabelKind,
     pub name: Span,
@@ -329,13 +383,13 @@ pub struct Label {
     pub full_range: TextRange,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LabelTarget {
     pub object: LabelObject,
     pub range: TextRange,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LabelObject {
     Section {
         prefix: String,
@@ -349,14 +403,91 @@ pub enum LabelObject {
     },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TheoremDefinition {
     pub name: Span,
     pub heading: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum MagicCommentKind {
+    Root,
+    Program,
+    BibProgram,
+    Spellcheck,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MagicComment {
+    pub kind: MagicCommentKind,
+    pub value: Span,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Citation {
     pub name: Span,
     pub full_range: TextRange,
 }
+
+#[cfg(test)]
+mod tests {
+    use parser::{parse_latex, SyntaxConfig};
+    use syntax::latex;
+
+    use super::{MagicCommentKind, Semantics};
+
+    fn magic_comments(text: &str) -> Vec<(MagicCommentKind, String, String)> {
+        let root = latex::SyntaxNode::new_root(parse_latex(text, &SyntaxConfig::default()));
+        let mut semantics = Semantics::default();
+        semantics.process_root(&root);
+        semantics
+            .magic_comments
+            .into_iter()
+            .map(|comment| {
+                let range = std::ops::Range::<usize>::from(comment.value.range);
+                (comment.kind, comment.value.text, text[range].to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_magic_comments() {
+        let text = "% !TEX root = ../main.tex\n% !TeX TS-program = lualatex\n% !BIB program = biber\n% !TEX spellcheck = en_US\n\\documentclass{article}\n";
+        let comment = |kind, value: &str| (kind, value.to_string(), value.to_string());
+        assert_eq!(
+            magic_comments(text),
+            vec![
+                comment(MagicCommentKind::Root, "../main.tex"),
+                comment(MagicCommentKind::Program, "lualatex"),
+                comment(MagicCommentKind::BibProgram, "biber"),
+                comment(MagicCommentKind::Spellcheck, "en_US"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_magic_comments_bib_ts_program() {
+        assert_eq!(
+            magic_comments("%!BIB TS-program=bibtex\n"),
+            vec![(
+                MagicCommentKind::BibProgram,
+                "bibtex".to_string(),
+                "bibtex".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_magic_comments_after_header() {
+        let text = "\\documentclass{article}\n% !TEX spellcheck = de_DE\n% !BIB program = biber\n";
+        assert_eq!(magic_comments(text), Vec::new());
+    }
+
+    #[test]
+    fn test_magic_comments_unknown() {
+        assert_eq!(
+            magic_comments("% !BIB root = main.tex\n% !TEX foo = bar\n"),
+            Vec::new()
+        );
+    }
+}
@@ -1,8 +1,15 @@
+mod frames;
 mod label;
+pub mod matchers;
+mod options;
 pub mod queries;
 mod regex_filter;
+mod scan;
 
 pub use self::{
+    frames::{find_frames, find_project_frames, Frame},
     label::{render_label, FloatKind, RenderedLabel, RenderedObject},
+    options::find_options,
     regex_filter::filter_regex_patterns,
+    scan::scan_folder,
 };
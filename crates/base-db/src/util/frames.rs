@@ -0,0 +1,177 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rowan::{ast::AstNode, Direction, TextLen};
+use rustc_hash::FxHashSet;
+use syntax::latex::{self, HasCurly};
+use url::Url;
+
+use crate::{
+    graph::{EdgeKind, Graph},
+    Document, Workspace,
+};
+
+static OVERLAY_SPEC_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new("<[^<>]*>").unwrap());
+
+/// A Beamer `frame` environment.
+#[derive(Clone)]
+pub struct Frame {
+    pub environment: latex::Environment,
+    pub title: Option<latex::CurlyGroup>,
+}
+
+impl Frame {
+    pub fn cast(environment: latex::Environment) -> Option<Self> {
+        let name = environment.begin()?.name()?.key()?;
+        if name.to_string() != "frame" {
+            return None;
+        }
+
+        let mut frame = Self {
+            environment,
+            title: None,
+        };
+
+        frame.title = Self::find_frame_title(&frame.environment).or_else(|| frame.begin_title());
+        Some(frame)
+    }
+
+    /// Returns the title which is passed as an argument to `\begin{frame}`.
+    pub fn begin_title(&self) -> Option<latex::CurlyGroup> {
+        let begin = self.environment.begin()?;
+        next_curly_group(begin.syntax())
+    }
+
+    /// Returns the title of the frame without pauses and overlay specifications.
+    pub fn title_text(&self) -> Option<String> {
+        let group = self.title.as_ref()?;
+        group.left_curly()?;
+        group.right_curly()?;
+
+        let mut text = String::new();
+        for token in group
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+            .filter(|token| token.kind() != latex::COMMENT && token.text() != "\\pause")
+        {
+            text.push_str(token.text());
+        }
+
+        let text = text.trim();
+        let text = &text[1..text.len() - 1];
+        let text = OVERLAY_SPEC_REGEX.replace_all(text, "");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        Some(text).filter(|text| !text.is_empty())
+    }
+
+    /// `\frametitle` takes precedence over the title passed to `\begin{frame}`.
+    fn find_frame_title(environment: &latex::Environment) -> Option<latex::CurlyGroup> {
+        let command = environment
+            .syntax()
+            .children()
+            .filter_map(latex::GenericCommand::cast)
+            .find(|command| {
+                command
+                    .name()
+                    .is_some_and(|name| name.text() == "\\frametitle")
+            })?;
+
+        command
+            .syntax()
+            .children()
+            .find_map(latex::CurlyGroup::cast)
+            .or_else(|| next_curly_group(command.syntax()))
+    }
+}
+
+/// Finds all frames of the given document in the order of their appearance.
+pub fn find_frames(root: &latex::SyntaxNode) -> impl Iterator<Item = Frame> {
+    root.descendants()
+        .filter_map(latex::Environment::cast)
+        .filter_map(Frame::cast)
+}
+
+/// Finds all frames of the project in the order in which they are typeset.
+/// Starting at the root document, the linked documents are visited at the position of their link.
+pub fn find_project_frames<'a>(
+    workspace: &'a Workspace,
+    root: &'a Document,
+) -> Vec<(&'a Document, Frame)> {
+    let graph = Graph::new(workspace, root);
+    let mut visited = FxHashSet::default();
+    let mut frames = Vec::new();
+    visit_project_frames(&graph, root, &mut visited, &mut frames);
+    frames
+}
+
+fn visit_project_frames<'a>(
+    graph: &Graph<'a>,
+    document: &'a Document,
+    visited: &mut FxHashSet<&'a Url>,
+    frames: &mut Vec<(&'a Document, Frame)>,
+) {
+    if !visited.insert(&document.uri) {
+        return;
+    }
+
+    let Some(data) = document.data.as_tex() else {
+        return;
+    };
+
+    // Documents which declare this document as their root are placed at the end.
+    let mut children: Vec<_> = graph
+        .edges
+        .iter()
+        .filter(|edge| edge.source == document)
+        .filter_map(|edge| match (edge.kind, edge.weight.as_ref()) {
+            (EdgeKind::Link, Some(weight)) => Some((weight.link.path.range.start(), edge.target)),
+            (EdgeKind::MagicRoot, _) => Some((document.text.text_len(), edge.target)),
+            _ => None,
+        })
+        .collect();
+
+    children.sort_by_key(|(offset, _)| *offset);
+    let mut children = children.into_iter().peekable();
+    for frame in find_frames(&data.root_node()) {
+        let start = frame.environment.syntax().text_range().start();
+        while let Some((_, child)) = children.next_if(|(offset, _)| *offset < start) {
+            visit_project_frames(graph, child, visited, frames);
+        }
+
+        frames.push((document, frame));
+    }
+
+    for (_, child) in children {
+        visit_project_frames(graph, child, visited, frames);
+    }
+}
+
+/// Skips the overlay specifications and options (`<2->[fragile]`) which may precede a title.
+fn next_curly_group(node: &latex::SyntaxNode) -> Option<latex::CurlyGroup> {
+    for element in node.siblings_with_tokens(Direction::Next).skip(1) {
+        match element {
+            rowan::NodeOrToken::Token(token)
+                if matches!(
+                    token.kind(),
+                    latex::WHITESPACE | latex::LINE_BREAK | latex::COMMENT
+                ) =>
+            {
+                continue
+            }
+            rowan::NodeOrToken::Token(_) => return None,
+            rowan::NodeOrToken::Node(node) => match node.kind() {
+                latex::CURLY_GROUP => return latex::CurlyGroup::cast(node),
+                latex::MIXED_GROUP => continue,
+                latex::TEXT if is_overlay_spec(&node.text().to_string()) => continue,
+                _ => return None,
+            },
+        }
+    }
+
+    None
+}
+
+fn is_overlay_spec(text: &str) -> bool {
+    let text = text.trim();
+    text.starts_with('<') && OVERLAY_SPEC_REGEX.replace_all(text, "").trim().is_empty()
+}
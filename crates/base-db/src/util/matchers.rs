@@ -1,3 +1,7 @@
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+use crate::MatchingAlgo;
+
 pub trait Matcher: Send + Sync {
     fn score(&self, choice: &str, pattern: &str) -> Option<i32>;
 }
@@ -40,3 +44,13 @@ impl Matcher for PrefixIgnoreCase {
         Some(-(choice.len() as i32))
     }
 }
+
+/// Creates the matcher which is used to filter completion items and workspace symbols.
+pub fn create(algo: &MatchingAlgo) -> Box<dyn Matcher> {
+    match algo {
+        MatchingAlgo::Skim => Box::<SkimMatcherV2>::default(),
+        MatchingAlgo::SkimIgnoreCase => Box::new(SkimMatcherV2::default().ignore_case()),
+        MatchingAlgo::Prefix => Box::new(Prefix),
+        MatchingAlgo::PrefixIgnoreCase => Box::new(PrefixIgnoreCase),
+    }
+}
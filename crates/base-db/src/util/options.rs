@@ -0,0 +1,47 @@
+use completion_data::{PackageOption, DATABASE};
+use rowan::ast::AstNode;
+use syntax::latex;
+
+/// Returns the options which are accepted by the key-value `group`,
+/// e.g. the options of a package or the keys of `\includegraphics`.
+pub fn find_options(group: &latex::SyntaxNode) -> Option<Vec<&'static PackageOption<'static>>> {
+    let parent = group.parent()?;
+    let mut options: Vec<_> = match parent.kind() {
+        latex::PACKAGE_INCLUDE | latex::CLASS_INCLUDE
+            if group.kind() == latex::BRACK_GROUP_KEY_VALUE =>
+        {
+            let extension = if parent.kind() == latex::PACKAGE_INCLUDE {
+                "sty"
+            } else {
+                "cls"
+            };
+
+            latex::Include::cast(parent)?
+                .path_list()?
+                .keys()
+                .filter_map(|key| {
+                    DATABASE.package_options(&format!("{}.{extension}", key.to_string()))
+                })
+                .flatten()
+                .collect()
+        }
+        latex::GRAPHICS_INCLUDE if group.kind() == latex::BRACK_GROUP_KEY_VALUE => DATABASE
+            .command_options("includegraphics")?
+            .iter()
+            .collect(),
+        latex::GENERIC_COMMAND if group.kind() == latex::CURLY_GROUP => {
+            let command = latex::GenericCommand::cast(parent)?;
+            let name = command.name()?;
+            DATABASE
+                .command_options(&name.text()[1..])?
+                .iter()
+                .collect()
+        }
+        _ => return None,
+    };
+
+    // The options of `\usepackage` are passed to all listed packages.
+    options.sort_by_key(|option| option.name);
+    options.dedup_by_key(|option| option.name);
+    Some(options)
+}
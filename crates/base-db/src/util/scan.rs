@@ -0,0 +1,160 @@
+use std::path::{Path, PathBuf};
+
+use distro::Language;
+use ignore::{overrides::OverrideBuilder, WalkBuilder};
+
+/// Finds the TeX, BibTeX and root marker files of a workspace folder.
+///
+/// Hidden directories are skipped, as well as the files matching the `.gitignore` files
+/// of the visited directories or the given `exclude` patterns (relative to the folder).
+pub fn scan_folder(folder: &Path, exclude: &[String]) -> Vec<(PathBuf, Language)> {
+    let mut overrides = OverrideBuilder::new(folder);
+    for pattern in exclude {
+        if let Err(why) = overrides.add(&format!("!{pattern}")) {
+            log::warn!("Invalid exclude pattern {pattern}: {why}");
+        }
+    }
+
+    let overrides = overrides.build().unwrap_or_else(|why| {
+        log::warn!("Invalid exclude patterns: {why}");
+        ignore::overrides::Override::empty()
+    });
+
+    // Hidden files like `.latexmkrc` are part of the project, so only hidden directories are skipped.
+    let walker = WalkBuilder::new(folder)
+        .hidden(false)
+        .require_git(false)
+        .overrides(overrides)
+        .sort_by_file_name(Ord::cmp)
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_some_and(|type_| type_.is_dir())
+                || !entry.file_name().to_string_lossy().starts_with('.')
+        })
+        .build();
+
+    walker
+        .flatten()
+        .filter(|entry| entry.file_type().is_some_and(|type_| type_.is_file()))
+        .filter_map(|entry| {
+            let path = entry.into_path();
+            let language = Language::from_path(&path)?;
+            matches!(
+                language,
+                Language::Tex
+                    | Language::Bib
+                    | Language::Root
+                    | Language::Tectonic
+                    | Language::Latexmkrc
+            )
+            .then_some((path, language))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::scan_folder;
+
+    fn check(files: &[(&str, &str)], exclude: &[&str], expected: &[&str]) {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, text) in files {
+            let path = dir.path().join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, text).unwrap();
+        }
+
+        let exclude: Vec<_> = exclude.iter().map(|pattern| pattern.to_string()).collect();
+        let actual: Vec<_> = scan_folder(dir.path(), &exclude)
+            .into_iter()
+            .map(|(path, _)| relative_path(dir.path(), &path))
+            .collect();
+
+        assert_eq!(actual, expected);
+    }
+
+    fn relative_path(dir: &Path, path: &Path) -> String {
+        let path = path.strip_prefix(dir).unwrap();
+        path.to_string_lossy().replace('\\', "/")
+    }
+
+    #[test]
+    fn test_languages() {
+        check(
+            &[
+                (".latexmkrc", ""),
+                ("main.tex", ""),
+                ("main.pdf", ""),
+                ("refs.bib", ""),
+                ("Tectonic.toml", ""),
+            ],
+            &[],
+            &[".latexmkrc", "Tectonic.toml", "main.tex", "refs.bib"],
+        );
+    }
+
+    #[test]
+    fn test_hidden_dirs() {
+        check(
+            &[
+                (".git/main.tex", ""),
+                (".cache/foo.tex", ""),
+                ("main.tex", ""),
+            ],
+            &[],
+            &["main.tex"],
+        );
+    }
+
+    #[test]
+    fn test_gitignore() {
+        check(
+            &[
+                (
+                    ".gitignore",
+                    "build/\n*.bak.tex\n\\#foo.tex\n[ab].tex\n!b.tex\n",
+                ),
+                ("#foo.tex", ""),
+                ("a.tex", ""),
+                ("b.tex", ""),
+                ("build/main.tex", ""),
+                ("chapters/.gitignore", "/intro.tex\n"),
+                ("chapters/intro.tex", ""),
+                ("chapters/sub/intro.tex", ""),
+                ("main.bak.tex", ""),
+                ("main.tex", ""),
+            ],
+            &[],
+            &["b.tex", "chapters/sub/intro.tex", "main.tex"],
+        );
+    }
+
+    #[test]
+    fn test_gitignore_parent_dir() {
+        check(
+            &[
+                (".gitignore", "build/\n!build/main.tex\n"),
+                ("build/main.tex", ""),
+                ("main.tex", ""),
+            ],
+            &[],
+            &["main.tex"],
+        );
+    }
+
+    #[test]
+    fn test_exclude() {
+        check(
+            &[
+                ("drafts/main.tex", ""),
+                ("main.tex", ""),
+                ("notes/a.tex", ""),
+                ("notes/b.tex", ""),
+            ],
+            &["drafts/", "notes/a.tex"],
+            &["main.tex", "notes/b.tex"],
+        );
+    }
+}
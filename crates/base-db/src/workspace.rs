@@ -6,12 +6,16 @@ use std::{
 use distro::{Distro, Language};
 use itertools::Itertools;
 use line_index::LineCol;
+use once_cell::sync::OnceCell;
 use rowan::{TextLen, TextRange};
-use rustc_hash::FxHashSet;
-use syntax::latexmkrc::LatexmkrcData;
+use rustc_hash::{FxHashMap, FxHashSet};
+use syntax::{latexmkrc::LatexmkrcData, tectonic::TectonicOutput};
 use url::Url;
 
-use crate::{graph, Config, Document, DocumentData, DocumentParams, Owner};
+use crate::{
+    graph, semantics::tex::MagicCommentKind, Config, Document, DocumentCache, DocumentData,
+    DocumentParams, Owner,
+};
 
 #[derive(Debug, Default)]
 pub struct Workspace {
@@ -19,6 +23,8 @@ pub struct Workspace {
     config: Config,
     distro: Distro,
     folders: Vec<PathBuf>,
+    cache: Option<DocumentCache>,
+    magic_children: OnceCell<FxHashMap<Url, Vec<Url>>>,
 }
 
 impl Workspace {
@@ -56,6 +62,7 @@ impl Workspace {
         cursor: LineCol,
     ) {
         log::debug!("Opening document {uri}...");
+        self.magic_children.take();
         self.documents.remove(&uri);
         self.documents.insert(Document::parse(DocumentParams {
             uri,
@@ -88,7 +95,23 @@ impl Workspace {
             }
         }
 
-        self.open(uri, text, language, owner, LineCol { line: 0, col: 0 });
+        let params = DocumentParams {
+            uri,
+            text,
+            language,
+            owner,
+            cursor: LineCol { line: 0, col: 0 },
+            config: &self.config,
+        };
+
+        let document = match &self.cache {
+            Some(cache) => cache.load(path, params),
+            None => Document::parse(params),
+        };
+
+        self.magic_children.take();
+        self.documents.remove(&document.uri);
+        self.documents.insert(document);
         Ok(())
     }
 
@@ -114,12 +137,16 @@ impl Workspace {
         Some(())
     }
 
+    /// Watches the directories of the documents and their output directories.
+    /// Directories which no longer contain any document (e.g. of a removed workspace folder)
+    /// are not watched anymore.
     pub fn watch(
         &mut self,
         watcher: &mut dyn notify::Watcher,
         watched_dirs: &mut FxHashSet<PathBuf>,
     ) {
-        self.iter()
+        let dirs: FxHashSet<PathBuf> = self
+            .iter()
             .filter(|document| document.uri.scheme() == "file")
             .flat_map(|document| {
                 let current_dir = &self.current_dir(&document.dir);
@@ -129,12 +156,23 @@ impl Workspace {
                 [aux_dir, log_dir, doc_dir]
             })
             .flatten()
-            .for_each(|path| {
-                if !watched_dirs.contains(&path) {
-                    let _ = watcher.watch(&path, notify::RecursiveMode::NonRecursive);
-                    watched_dirs.insert(path);
-                }
-            });
+            .collect();
+
+        watched_dirs.retain(|path| {
+            if dirs.contains(path) {
+                return true;
+            }
+
+            let _ = watcher.unwatch(path);
+            false
+        });
+
+        for path in dirs {
+            if !watched_dirs.contains(&path) {
+                let _ = watcher.watch(&path, notify::RecursiveMode::NonRecursive);
+                watched_dirs.insert(path);
+            }
+        }
     }
 
     pub fn current_dir(&self, base_dir: &Url) -> Url {
@@ -144,7 +182,12 @@ impl Workspace {
         }
 
         self.iter()
-            .filter(|document| matches!(document.data, DocumentData::Root | DocumentData::Tectonic))
+            .filter(|document| {
+                matches!(
+                    document.data,
+                    DocumentData::Root | DocumentData::Tectonic(_)
+                )
+            })
             .flat_map(|document| document.uri.join("."))
             .find(|root_dir| base_dir.as_str().starts_with(root_dir.as_str()))
             .unwrap_or_else(|| base_dir.clone())
@@ -164,7 +207,7 @@ impl Workspace {
         self.output_dir(base_dir, &self.config.build.pdf_dir, |_| None)
     }
 
-    fn current_latexmkrc(&self, base_dir: &Url) -> Option<&LatexmkrcData> {
+    pub fn current_latexmkrc(&self, base_dir: &Url) -> Option<&LatexmkrcData> {
         self.documents
             .iter()
             .filter(|document| document.language == Language::Latexmkrc)
@@ -212,13 +255,59 @@ impl Workspace {
         Project { documents }
     }
 
+    /// Resolves the root document referenced by a `% !TEX root = ...` comment.
+    pub fn magic_root(&self, document: &Document) -> Option<&Document> {
+        let data = document.data.as_tex()?;
+        let path = &data.semantics.magic_comment(MagicCommentKind::Root)?.text;
+        let uri = document.dir.join(path).ok()?;
+        self.lookup(&uri).or_else(|| {
+            let uri = document.dir.join(&format!("{path}.tex")).ok()?;
+            self.lookup(&uri)
+        })
+    }
+
+    /// Returns the documents which declare the given document as their root.
+    /// The mapping is computed once for every state of the workspace.
+    pub fn magic_children(&self, root: &Document) -> Vec<&Document> {
+        let magic_children = self.magic_children.get_or_init(|| {
+            let mut magic_children: FxHashMap<Url, Vec<Url>> = FxHashMap::default();
+            for child in self.iter() {
+                if let Some(root) = self.magic_root(child).filter(|root| *root != child) {
+                    magic_children
+                        .entry(root.uri.clone())
+                        .or_default()
+                        .push(child.uri.clone());
+                }
+            }
+
+            magic_children
+        });
+
+        magic_children
+            .get(&root.uri)
+            .into_iter()
+            .flatten()
+            .filter_map(|uri| self.lookup(uri))
+            .collect()
+    }
+
     pub fn parents(&self, child: &Document) -> FxHashSet<&Document> {
+        if let Some(root) = self.magic_root(child).filter(|root| *root != child) {
+            return std::iter::once(root).collect();
+        }
+
+        let tectonic_inputs: FxHashSet<&Document> = self
+            .iter()
+            .flat_map(graph::tectonic_inputs)
+            .filter_map(|uri| self.lookup(&uri))
+            .collect();
+
         self.iter()
             .filter(|document| {
                 let DocumentData::Tex(data) = &document.data else {
                     return false;
                 };
-                data.semantics.can_be_root
+                data.semantics.can_be_root || tectonic_inputs.contains(document)
             })
             .filter(|parent| {
                 let graph = graph::Graph::new(self, parent);
@@ -228,6 +317,29 @@ impl Workspace {
             .collect()
     }
 
+    /// Finds the `Tectonic.toml` file and the output which the given document belongs to.
+    pub fn tectonic_output(&self, child: &Document) -> Option<(&Document, &TectonicOutput)> {
+        self.iter()
+            .filter_map(|document| Some((document, document.data.as_tectonic()?)))
+            .find_map(|(manifest, data)| {
+                if manifest == child {
+                    return Some((manifest, data.outputs.first()?));
+                }
+
+                let src_dir = graph::tectonic_src_dir(manifest)?;
+                let output = data.outputs.iter().find(|output| {
+                    output
+                        .inputs
+                        .iter()
+                        .filter_map(|input| src_dir.join(input).ok())
+                        .filter_map(|uri| self.lookup(&uri))
+                        .any(|input| graph::Graph::new(self, input).preorder().contains(&child))
+                })?;
+
+                Some((manifest, output))
+            })
+    }
+
     pub fn set_config(&mut self, config: Config) {
         self.config = config;
         self.reload();
@@ -238,10 +350,53 @@ impl Workspace {
         self.reload();
     }
 
+    pub fn folders(&self) -> &[PathBuf] {
+        &self.folders
+    }
+
     pub fn set_folders(&mut self, folders: Vec<PathBuf>) {
         self.folders = folders;
     }
 
+    pub fn add_folder(&mut self, folder: PathBuf) {
+        if !self.folders.contains(&folder) {
+            self.folders.push(folder);
+        }
+    }
+
+    /// Removes the folder from the workspace and drops the documents which are no longer
+    /// part of any workspace folder. Documents opened by the client are kept.
+    pub fn remove_folder(&mut self, folder: &Path) -> Vec<Url> {
+        self.folders.retain(|dir| dir != folder);
+
+        let removed = self
+            .iter()
+            .filter(|document| document.owner == Owner::Server)
+            .filter(|document| {
+                document.path.as_deref().is_some_and(|path| {
+                    path.starts_with(folder)
+                        && !self.folders.iter().any(|dir| path.starts_with(dir))
+                })
+            })
+            .map(|document| document.uri.clone())
+            .collect::<Vec<_>>();
+
+        self.magic_children.take();
+        for uri in &removed {
+            self.documents.remove(uri);
+        }
+
+        removed
+    }
+
+    pub fn cache(&self) -> Option<&DocumentCache> {
+        self.cache.as_ref()
+    }
+
+    pub fn set_cache(&mut self, cache: DocumentCache) {
+        self.cache = Some(cache);
+    }
+
     pub fn set_cursor(&mut self, uri: &Url, cursor: LineCol) -> Option<()> {
         let mut document = self.lookup(uri)?.clone();
         document.cursor = cursor;
@@ -271,6 +426,7 @@ impl Workspace {
     }
 
     pub fn remove(&mut self, uri: &Url) {
+        self.magic_children.take();
         self.documents.remove(uri);
     }
 
@@ -2,6 +2,7 @@ use line_index::LineIndex;
 use rowan::{ast::AstNode, NodeOrToken};
 use syntax::bibtex::{self, HasName, HasType, HasValue};
 
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Options {
     pub insert_spaces: bool,
     pub tab_size: usize,
@@ -9,7 +10,7 @@ pub struct Options {
 }
 
 impl Options {
-    fn indent(&self) -> String {
+    pub fn indent(&self) -> String {
         if self.insert_spaces {
             std::iter::repeat(' ').take(self.tab_size).collect()
         } else {
@@ -13,6 +13,7 @@ use url::Url;
 use super::{
     entry::{EntryData, EntryKind},
     output::{Inline, InlineBuilder, Punct},
+    style::{self, CitationStyle},
 };
 
 #[derive(Debug, Default)]
@@ -21,8 +22,17 @@ pub struct Driver {
 }
 
 impl Driver {
-    pub fn process(&mut self, entry: &bibtex::Entry) {
+    pub fn process(&mut self, entry: &bibtex::Entry, citation_style: CitationStyle) {
         let entry = EntryData::from(entry);
+        match citation_style {
+            CitationStyle::Default => {}
+            CitationStyle::Apa => return style::apa(&mut self.builder, entry),
+            CitationStyle::Ieee => return style::ieee(&mut self.builder, entry),
+            CitationStyle::ChicagoAuthorDate => {
+                return style::chicago_author_date(&mut self.builder, entry);
+            }
+        };
+
         match entry.kind {
             EntryKind::Article
             | EntryKind::DataSet
@@ -1,23 +1,41 @@
 mod driver;
 mod entry;
 mod output;
+mod style;
 
 use syntax::bibtex;
 use unicode_normalization::UnicodeNormalization;
 
 use self::{driver::Driver, output::Inline};
 
+pub use self::style::CitationStyle;
+
+/// Renders the entry as Markdown.
+#[must_use]
+pub fn render(entry: &bibtex::Entry, style: CitationStyle) -> Option<String> {
+    render_with(entry, style, true)
+}
+
+/// Renders the entry as plain text without any markup.
 #[must_use]
-pub fn render(entry: &bibtex::Entry) -> Option<String> {
+pub fn render_plain(entry: &bibtex::Entry, style: CitationStyle) -> Option<String> {
+    render_with(entry, style, false)
+}
+
+fn render_with(entry: &bibtex::Entry, style: CitationStyle, markdown: bool) -> Option<String> {
     let mut output = String::new();
+    let mut ends_with_link = false;
     let mut driver = Driver::default();
-    driver.process(entry);
+    driver.process(entry, style);
     driver.finish().for_each(|(inline, punct)| {
+        ends_with_link = matches!(inline, Inline::Link { .. });
         let text = match inline {
             Inline::Regular(text) => text,
-            Inline::Italic(text) => format!("*{text}*"),
+            Inline::Italic(text) if markdown => format!("*{text}*"),
+            Inline::Italic(text) => text,
             Inline::Quoted(text) => format!("\"{text}\""),
-            Inline::Link { url, alt } => format!("[{alt}]({url})"),
+            Inline::Link { url, alt } if markdown => format!("[{alt}]({url})"),
+            Inline::Link { alt, .. } => alt,
         };
         output.push_str(&text);
         output.push_str(punct.as_str());
@@ -26,7 +44,12 @@ pub fn render(entry: &bibtex::Entry) -> Option<String> {
     if output.is_empty() {
         None
     } else {
-        output.push('.');
+        // APA does not terminate the reference with a period if it ends with a DOI or a URL.
+        let omit_period = style == CitationStyle::Apa && ends_with_link;
+        if !omit_period && !output.ends_with('.') {
+            output.push('.');
+        }
+
         Some(output.nfc().collect())
     }
 }
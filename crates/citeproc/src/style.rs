@@ -0,0 +1,593 @@
+use bibtex_utils::field::{
+    author::{AuthorField, AuthorFieldData},
+    date::{DateField, DateFieldData},
+    number::{NumberField, NumberFieldData},
+    text::TextField,
+};
+use human_name::Name;
+use itertools::Itertools;
+
+use super::{
+    entry::{EntryData, EntryKind},
+    output::{Inline, InlineBuilder, Punct},
+};
+
+/// The citation style used to render bibliography entries.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub enum CitationStyle {
+    /// A compact style similar to the `biblatex` standard style.
+    #[default]
+    Default,
+    /// American Psychological Association (7th edition)
+    Apa,
+    /// Institute of Electrical and Electronics Engineers
+    Ieee,
+    /// Chicago Manual of Style (author-date)
+    ChicagoAuthorDate,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Layout {
+    Article,
+    Book,
+    InBook,
+}
+
+impl From<EntryKind> for Layout {
+    fn from(kind: EntryKind) -> Self {
+        match kind {
+            EntryKind::Book
+            | EntryKind::MVBook
+            | EntryKind::Booklet
+            | EntryKind::Collection
+            | EntryKind::MVCollection
+            | EntryKind::Manual
+            | EntryKind::Proceedings
+            | EntryKind::MVProceedings
+            | EntryKind::Reference
+            | EntryKind::MVReference
+            | EntryKind::Report
+            | EntryKind::TechReport
+            | EntryKind::Thesis
+            | EntryKind::MasterThesis
+            | EntryKind::PhdThesis => Self::Book,
+            EntryKind::InBook
+            | EntryKind::BookInBook
+            | EntryKind::SuppBook
+            | EntryKind::InCollection
+            | EntryKind::SuppCollection
+            | EntryKind::InProceedings
+            | EntryKind::Conference
+            | EntryKind::InReference => Self::InBook,
+            EntryKind::Article
+            | EntryKind::DataSet
+            | EntryKind::Misc
+            | EntryKind::Online
+            | EntryKind::Electronic
+            | EntryKind::Www
+            | EntryKind::Patent
+            | EntryKind::Periodical
+            | EntryKind::SuppPeriodical
+            | EntryKind::Set
+            | EntryKind::Software
+            | EntryKind::Unknown => Self::Article,
+        }
+    }
+}
+
+pub fn apa(builder: &mut InlineBuilder, mut entry: EntryData) {
+    let layout = Layout::from(entry.kind);
+    if let Some(author) = entry.author.remove(&AuthorField::Author) {
+        let names = author.authors.iter().map(surname_initials).collect_vec();
+        builder.push(
+            Inline::Regular(join_names(&names, ", &", ", &")),
+            Punct::Nothing,
+            Punct::Space,
+        );
+    }
+
+    let year = take_year(&mut entry).unwrap_or_else(|| "n.d.".into());
+    builder.push(
+        Inline::Regular(format!("({year})")),
+        Punct::Space,
+        Punct::Dot,
+    );
+
+    let title = take_title(&mut entry, TextField::Title, TextField::Subtitle);
+    match layout {
+        Layout::Article => {
+            if let Some(title) = title {
+                builder.push(Inline::Regular(title), Punct::Dot, Punct::Dot);
+            }
+
+            if let Some(journal) = take_journal(&mut entry) {
+                builder.push(Inline::Italic(journal), Punct::Dot, Punct::Comma);
+                if let Some(volume) = entry.number.remove(&NumberField::Volume) {
+                    let number = entry.number.remove(&NumberField::Number);
+                    let trailing = if number.is_some() {
+                        Punct::Nothing
+                    } else {
+                        Punct::Comma
+                    };
+
+                    builder.push(Inline::Italic(volume.to_string()), Punct::Comma, trailing);
+                    if let Some(number) = number {
+                        builder.push(
+                            Inline::Regular(format!("({number})")),
+                            Punct::Nothing,
+                            Punct::Comma,
+                        );
+                    }
+                }
+
+                if let Some(pages) = entry.number.remove(&NumberField::Pages) {
+                    builder.push(Inline::Regular(pages.to_string()), Punct::Comma, Punct::Dot);
+                }
+            }
+        }
+        Layout::Book => {
+            if let Some(title) = title {
+                builder.push(Inline::Italic(title), Punct::Dot, Punct::Space);
+            }
+
+            if let Some(edition) = take_edition(&mut entry) {
+                builder.push(
+                    Inline::Regular(format!("({edition} ed.)")),
+                    Punct::Space,
+                    Punct::Dot,
+                );
+            }
+
+            if let Some(publisher) = entry.text.remove(&TextField::Publisher) {
+                builder.push(Inline::Regular(publisher.text), Punct::Dot, Punct::Dot);
+            }
+        }
+        Layout::InBook => {
+            if let Some(title) = title {
+                builder.push(Inline::Regular(title), Punct::Dot, Punct::Dot);
+            }
+
+            let editors = take_names(&mut entry, AuthorField::Editor).map(|editor| {
+                let names = editor.authors.iter().map(initials_surname).collect_vec();
+                let suffix = if names.len() > 1 { "Eds." } else { "Ed." };
+                format!(" {} ({suffix}),", join_names(&names, " &", ", &"))
+            });
+
+            if let Some(book) =
+                take_title(&mut entry, TextField::BookTitle, TextField::BookSubtitle)
+            {
+                builder.push(
+                    Inline::Regular(format!("In{}", editors.unwrap_or_default())),
+                    Punct::Dot,
+                    Punct::Space,
+                );
+
+                builder.push(Inline::Italic(book), Punct::Space, Punct::Space);
+            }
+
+            if let Some(pages) = entry.number.remove(&NumberField::Pages) {
+                builder.push(
+                    Inline::Regular(format!("(pp. {pages})")),
+                    Punct::Space,
+                    Punct::Dot,
+                );
+            }
+
+            if let Some(publisher) = entry.text.remove(&TextField::Publisher) {
+                builder.push(Inline::Regular(publisher.text), Punct::Dot, Punct::Dot);
+            }
+        }
+    }
+
+    if let Some(doi) = entry.text.remove(&TextField::Doi) {
+        let url = doi_url(&doi.text);
+        builder.push(
+            Inline::Link {
+                url: url.clone(),
+                alt: url,
+            },
+            Punct::Dot,
+            Punct::Nothing,
+        );
+    } else if let Some(url) = entry.text.remove(&TextField::Url) {
+        builder.push(
+            Inline::Link {
+                url: url.text.clone(),
+                alt: url.text,
+            },
+            Punct::Dot,
+            Punct::Nothing,
+        );
+    }
+}
+
+pub fn ieee(builder: &mut InlineBuilder, mut entry: EntryData) {
+    let layout = Layout::from(entry.kind);
+    if let Some(author) = entry.author.remove(&AuthorField::Author) {
+        let names = author.authors.iter().map(initials_surname).collect_vec();
+        builder.push(
+            Inline::Regular(join_names(&names, " and", ", and")),
+            Punct::Nothing,
+            Punct::Comma,
+        );
+    }
+
+    let title = take_title(&mut entry, TextField::Title, TextField::Subtitle);
+    match layout {
+        Layout::Article | Layout::InBook => {
+            if let Some(title) = title {
+                builder.push(
+                    Inline::Regular(format!("\"{title},\"")),
+                    Punct::Comma,
+                    Punct::Space,
+                );
+            }
+
+            let container = if layout == Layout::Article {
+                take_journal(&mut entry)
+            } else {
+                take_title(&mut entry, TextField::BookTitle, TextField::BookSubtitle)
+            };
+
+            if let Some(container) = container {
+                if layout == Layout::InBook {
+                    builder.push(Inline::Regular("in".into()), Punct::Space, Punct::Space);
+                }
+
+                builder.push(Inline::Italic(container), Punct::Space, Punct::Comma);
+            }
+
+            if let Some(volume) = entry.number.remove(&NumberField::Volume) {
+                builder.push(
+                    Inline::Regular(format!("vol. {volume}")),
+                    Punct::Comma,
+                    Punct::Comma,
+                );
+            }
+
+            if let Some(number) = entry.number.remove(&NumberField::Number) {
+                builder.push(
+                    Inline::Regular(format!("no. {number}")),
+                    Punct::Comma,
+                    Punct::Comma,
+                );
+            }
+
+            if layout == Layout::InBook {
+                if let Some(publisher) = entry.text.remove(&TextField::Publisher) {
+                    builder.push(Inline::Regular(publisher.text), Punct::Comma, Punct::Comma);
+                }
+            }
+
+            if let Some(pages) = entry.number.remove(&NumberField::Pages) {
+                builder.push(
+                    Inline::Regular(format!("{} {pages}", pages_prefix(&pages))),
+                    Punct::Comma,
+                    Punct::Comma,
+                );
+            }
+
+            if let Some(date) = take_date(&mut entry) {
+                builder.push(
+                    Inline::Regular(date.to_string()),
+                    Punct::Comma,
+                    Punct::Comma,
+                );
+            }
+        }
+        Layout::Book => {
+            if let Some(title) = title {
+                builder.push(Inline::Italic(title), Punct::Comma, Punct::Comma);
+            }
+
+            if let Some(edition) = take_edition(&mut entry) {
+                builder.push(
+                    Inline::Regular(format!("{edition} ed")),
+                    Punct::Comma,
+                    Punct::Dot,
+                );
+            }
+
+            let location = entry.text.remove(&TextField::Location);
+            let publisher = entry.text.remove(&TextField::Publisher);
+            let text = match (location, publisher) {
+                (Some(location), Some(publisher)) => {
+                    Some(format!("{}: {}", location.text, publisher.text))
+                }
+                (None, Some(data)) | (Some(data), None) => Some(data.text),
+                (None, None) => None,
+            };
+
+            if let Some(text) = text {
+                builder.push(Inline::Regular(text), Punct::Dot, Punct::Comma);
+            }
+
+            if let Some(date) = take_year(&mut entry) {
+                builder.push(Inline::Regular(date), Punct::Comma, Punct::Comma);
+            }
+        }
+    }
+
+    if let Some(doi) = entry.text.remove(&TextField::Doi) {
+        builder.push(
+            Inline::Regular(format!("doi: {}", strip_doi(&doi.text))),
+            Punct::Comma,
+            Punct::Dot,
+        );
+    } else if let Some(url) = entry.text.remove(&TextField::Url) {
+        builder.push(
+            Inline::Regular("[Online]. Available".into()),
+            Punct::Dot,
+            Punct::Colon,
+        );
+        builder.push(
+            Inline::Link {
+                url: url.text.clone(),
+                alt: url.text,
+            },
+            Punct::Colon,
+            Punct::Nothing,
+        );
+    }
+}
+
+pub fn chicago_author_date(builder: &mut InlineBuilder, mut entry: EntryData) {
+    let layout = Layout::from(entry.kind);
+    if let Some(author) = entry.author.remove(&AuthorField::Author) {
+        let names = author
+            .authors
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                if i == 0 {
+                    surname_given(name)
+                } else {
+                    given_surname(name)
+                }
+            })
+            .collect_vec();
+
+        builder.push(
+            // The first name is inverted, so the conjunction needs a comma even for two names.
+            Inline::Regular(
+                join_names(&names, ", and", ", and")
+                    .trim_end_matches('.')
+                    .into(),
+            ),
+            Punct::Nothing,
+            Punct::Dot,
+        );
+    }
+
+    let year = take_year(&mut entry).unwrap_or_else(|| "n.d.".into());
+    builder.push(Inline::Regular(year), Punct::Dot, Punct::Dot);
+
+    let title = take_title(&mut entry, TextField::Title, TextField::Subtitle);
+    match layout {
+        Layout::Article => {
+            if let Some(title) = title {
+                builder.push(
+                    Inline::Regular(format!("\"{title}.\"")),
+                    Punct::Dot,
+                    Punct::Space,
+                );
+            }
+
+            if let Some(journal) = take_journal(&mut entry) {
+                builder.push(Inline::Italic(journal), Punct::Space, Punct::Space);
+            }
+
+            if let Some(volume) = entry.number.remove(&NumberField::Volume) {
+                let number = entry
+                    .number
+                    .remove(&NumberField::Number)
+                    .map_or(String::new(), |number| format!(" ({number})"));
+
+                builder.push(
+                    Inline::Regular(format!("{volume}{number}")),
+                    Punct::Space,
+                    Punct::Space,
+                );
+            }
+
+            if let Some(pages) = entry.number.remove(&NumberField::Pages) {
+                builder.push(Inline::Regular(pages.to_string()), Punct::Colon, Punct::Dot);
+            }
+        }
+        Layout::Book | Layout::InBook => {
+            if layout == Layout::InBook {
+                if let Some(title) = title {
+                    builder.push(
+                        Inline::Regular(format!("\"{title}.\"")),
+                        Punct::Dot,
+                        Punct::Space,
+                    );
+                }
+
+                if let Some(book) =
+                    take_title(&mut entry, TextField::BookTitle, TextField::BookSubtitle)
+                {
+                    builder.push(Inline::Regular("In".into()), Punct::Space, Punct::Space);
+                    builder.push(Inline::Italic(book), Punct::Space, Punct::Dot);
+                }
+
+                if let Some(editor) = take_names(&mut entry, AuthorField::Editor) {
+                    let names = editor.authors.iter().map(given_surname).collect_vec();
+                    builder.push(
+                        Inline::Regular(format!(
+                            "Edited by {}",
+                            join_names(&names, " and", ", and")
+                        )),
+                        Punct::Dot,
+                        Punct::Dot,
+                    );
+                }
+
+                if let Some(pages) = entry.number.remove(&NumberField::Pages) {
+                    builder.push(Inline::Regular(pages.to_string()), Punct::Comma, Punct::Dot);
+                }
+            } else if let Some(title) = title {
+                builder.push(Inline::Italic(title), Punct::Dot, Punct::Dot);
+            }
+
+            if let Some(edition) = take_edition(&mut entry) {
+                builder.push(
+                    Inline::Regular(format!("{edition} ed")),
+                    Punct::Dot,
+                    Punct::Dot,
+                );
+            }
+
+            let location = entry.text.remove(&TextField::Location);
+            let publisher = entry.text.remove(&TextField::Publisher);
+            let text = match (location, publisher) {
+                (Some(location), Some(publisher)) => {
+                    Some(format!("{}: {}", location.text, publisher.text))
+                }
+                (None, Some(data)) | (Some(data), None) => Some(data.text),
+                (None, None) => None,
+            };
+
+            if let Some(text) = text {
+                builder.push(Inline::Regular(text), Punct::Dot, Punct::Dot);
+            }
+        }
+    }
+
+    if let Some(doi) = entry.text.remove(&TextField::Doi) {
+        let url = doi_url(&doi.text);
+        builder.push(
+            Inline::Link {
+                url: url.clone(),
+                alt: url,
+            },
+            Punct::Dot,
+            Punct::Nothing,
+        );
+    } else if let Some(url) = entry.text.remove(&TextField::Url) {
+        builder.push(
+            Inline::Link {
+                url: url.text.clone(),
+                alt: url.text,
+            },
+            Punct::Dot,
+            Punct::Nothing,
+        );
+    }
+}
+
+fn take_names(entry: &mut EntryData, field: AuthorField) -> Option<AuthorFieldData> {
+    entry
+        .author
+        .remove(&field)
+        .filter(|data| !data.authors.is_empty())
+}
+
+fn take_title(entry: &mut EntryData, title: TextField, subtitle: TextField) -> Option<String> {
+    let title = entry.text.remove(&title)?.text;
+    Some(match entry.text.remove(&subtitle) {
+        Some(subtitle) => format!("{title}: {}", subtitle.text),
+        None => title,
+    })
+}
+
+fn take_journal(entry: &mut EntryData) -> Option<String> {
+    entry
+        .text
+        .remove(&TextField::Journal)
+        .or_else(|| entry.text.remove(&TextField::JournalTitle))
+        .map(|data| data.text)
+}
+
+fn take_date(entry: &mut EntryData) -> Option<DateFieldData> {
+    [DateField::Date, DateField::Year, DateField::Month]
+        .iter()
+        .filter_map(|field| entry.date.remove(field))
+        .reduce(|a, b| a + b)
+}
+
+fn take_year(entry: &mut EntryData) -> Option<String> {
+    match take_date(entry)? {
+        DateFieldData::Year(year) => Some(year.to_string()),
+        DateFieldData::Date(date) => Some(date.format("%Y").to_string()),
+        DateFieldData::Other(text) => Some(text),
+        DateFieldData::Month(_) => None,
+    }
+}
+
+fn take_edition(entry: &mut EntryData) -> Option<String> {
+    Some(match entry.number.remove(&NumberField::Edition)? {
+        NumberFieldData::Scalar(1) => "1st".into(),
+        NumberFieldData::Scalar(2) => "2nd".into(),
+        NumberFieldData::Scalar(3) => "3rd".into(),
+        NumberFieldData::Scalar(number) => format!("{number}th"),
+        NumberFieldData::Range(_, _) => return None,
+        NumberFieldData::Other(text) => text,
+    })
+}
+
+fn pages_prefix(pages: &NumberFieldData) -> &'static str {
+    match pages {
+        NumberFieldData::Range(_, _) => "pp.",
+        NumberFieldData::Scalar(_) | NumberFieldData::Other(_) => "p.",
+    }
+}
+
+fn strip_doi(doi: &str) -> &str {
+    ["https://doi.org/", "http://doi.org/", "https://dx.doi.org/"]
+        .iter()
+        .find_map(|prefix| doi.strip_prefix(prefix))
+        .unwrap_or(doi)
+}
+
+fn doi_url(doi: &str) -> String {
+    format!("https://doi.org/{}", strip_doi(doi))
+}
+
+/// Formats the initials of a name like `J. R. R.`.
+fn dotted_initials(name: &Name) -> String {
+    name.initials().chars().map(|c| format!("{c}.")).join(" ")
+}
+
+/// Formats the given names of a name, falling back to the initials if the given name is unknown.
+fn given_names(name: &Name) -> String {
+    let Some(given) = name.given_name() else {
+        return dotted_initials(name);
+    };
+
+    match (name.middle_name(), name.middle_initials()) {
+        (Some(middle), _) => format!("{given} {middle}"),
+        (None, Some(initials)) => {
+            let initials = initials.chars().map(|c| format!("{c}.")).join(" ");
+            format!("{given} {initials}")
+        }
+        (None, None) => given.to_string(),
+    }
+}
+
+fn surname_initials(name: &Name) -> String {
+    format!("{}, {}", name.surname(), dotted_initials(name))
+}
+
+fn initials_surname(name: &Name) -> String {
+    format!("{} {}", dotted_initials(name), name.surname())
+}
+
+fn surname_given(name: &Name) -> String {
+    format!("{}, {}", name.surname(), given_names(name))
+}
+
+fn given_surname(name: &Name) -> String {
+    format!("{} {}", given_names(name), name.surname())
+}
+
+/// Joins a list of names using commas.
+/// The last two names are separated by `pair` if there are only two names and by `list` otherwise.
+fn join_names(names: &[String], pair: &str, list: &str) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.clone(),
+        [first, second] => format!("{first}{pair} {second}"),
+        [init @ .., last] => format!("{}{list} {last}", init.join(", ")),
+    }
+}
@@ -3,11 +3,17 @@ use parser::parse_bibtex;
 use rowan::ast::AstNode;
 use syntax::bibtex;
 
+use crate::CitationStyle;
+
 fn check(input: &str, expect: Expect) {
+    check_style(input, CitationStyle::Default, expect);
+}
+
+fn check_style(input: &str, style: CitationStyle, expect: Expect) {
     let green = parse_bibtex(input);
     let root = bibtex::Root::cast(bibtex::SyntaxNode::new_root(green)).unwrap();
     let entry = root.entries().next().unwrap();
-    let output = super::render(&entry).unwrap();
+    let output = super::render(&entry, style).unwrap();
     expect.assert_eq(&output);
 }
 
@@ -392,3 +398,113 @@ fn test_patent_almendro_1998() {
         ]],
     );
 }
+
+const RIVEST_1978: &str = r#"
+@article{10.1145/359340.359342,
+    author = {Rivest, R. L. and Shamir, A. and Adleman, L.},
+    title = {A Method for Obtaining Digital Signatures and Public-Key Cryptosystems},
+    year = {1978},
+    volume = {21},
+    number = {2},
+    doi = {10.1145/359340.359342},
+    journal = {Commun. ACM},
+    month = {feb},
+    pages = {120-126},
+}"#;
+
+const KNUTH_1997: &str = r#"
+@book{knuth,
+    author = {Donald E. Knuth},
+    title = {The Art of Computer Programming},
+    subtitle = {Fundamental Algorithms},
+    edition = {3},
+    publisher = {Addison-Wesley},
+    location = {Reading, MA},
+    year = {1997},
+}"#;
+
+#[test]
+fn test_apa_article() {
+    check_style(
+        RIVEST_1978,
+        CitationStyle::Apa,
+        expect![[
+            r#"Rivest, R. L., Shamir, A., & Adleman, L. (1978). A Method for Obtaining Digital Signatures and Public-Key Cryptosystems. *Commun. ACM*, *21*(2), 120-126. [https://doi.org/10.1145/359340.359342](https://doi.org/10.1145/359340.359342)"#
+        ]],
+    );
+}
+
+#[test]
+fn test_apa_book() {
+    check_style(
+        KNUTH_1997,
+        CitationStyle::Apa,
+        expect![[
+            r#"Knuth, D. E. (1997). *The Art of Computer Programming: Fundamental Algorithms* (3rd ed.). Addison-Wesley."#
+        ]],
+    );
+}
+
+#[test]
+fn test_ieee_article() {
+    check_style(
+        RIVEST_1978,
+        CitationStyle::Ieee,
+        expect![[
+            r#"R. L. Rivest, A. Shamir, and L. Adleman, "A Method for Obtaining Digital Signatures and Public-Key Cryptosystems," *Commun. ACM*, vol. 21, no. 2, pp. 120-126, Feb. 1978, doi: 10.1145/359340.359342."#
+        ]],
+    );
+}
+
+#[test]
+fn test_ieee_book() {
+    check_style(
+        KNUTH_1997,
+        CitationStyle::Ieee,
+        expect![[
+            r#"D. E. Knuth, *The Art of Computer Programming: Fundamental Algorithms*, 3rd ed. Reading, MA: Addison-Wesley, 1997."#
+        ]],
+    );
+}
+
+#[test]
+fn test_chicago_article() {
+    check_style(
+        RIVEST_1978,
+        CitationStyle::ChicagoAuthorDate,
+        expect![[
+            r#"Rivest, R. L., A. Shamir, and L. Adleman. 1978. "A Method for Obtaining Digital Signatures and Public-Key Cryptosystems." *Commun. ACM* 21 (2): 120-126. [https://doi.org/10.1145/359340.359342](https://doi.org/10.1145/359340.359342)."#
+        ]],
+    );
+}
+
+#[test]
+fn test_chicago_book() {
+    check_style(
+        KNUTH_1997,
+        CitationStyle::ChicagoAuthorDate,
+        expect![[
+            r#"Knuth, Donald E. 1997. *The Art of Computer Programming: Fundamental Algorithms*. 3rd ed. Reading, MA: Addison-Wesley."#
+        ]],
+    );
+}
+
+#[test]
+fn test_chicago_incollection() {
+    check_style(
+        r#"
+@incollection{foo,
+    author = {Jane Doe and John Smith},
+    title = {A Chapter},
+    booktitle = {A Collection},
+    editor = {Max Mustermann},
+    pages = {10--20},
+    publisher = {Springer},
+    year = {2020},
+}"#,
+        CitationStyle::ChicagoAuthorDate,
+        expect![[
+            r#"Doe, Jane, and John Smith. 2020. "A Chapter." In *A Collection*. Edited by Max Mustermann, 10-20. Springer."#
+        ]],
+    );
+}
@@ -6,13 +6,13 @@ use std::{
 };
 
 use anyhow::Result;
-use base_db::Workspace;
+use base_db::{semantics::tex::MagicCommentKind, Document, Workspace};
 use bstr::io::BufReadExt;
 use crossbeam_channel::Sender;
 use thiserror::Error;
 use url::Url;
 
-use crate::placeholders::replace_placeholders;
+use crate::{placeholders::replace_placeholders, shadow::ShadowDirectory};
 
 #[derive(Debug, Error)]
 pub enum BuildError {
@@ -22,48 +22,178 @@ pub enum BuildError {
     #[error("Document \"{0}\" does not exist on the local file system")]
     NotLocal(Url),
 
+    #[error("Build profile \"{0}\" is not configured")]
+    UnknownProfile(String),
+
+    #[error("Document \"{0}\" cannot be compiled in a shadow directory")]
+    ShadowUnsupported(Url),
+
     #[error("Unable to run compiler: {0}")]
     Compile(#[from] std::io::Error),
 }
 
+/// The engines which can be selected by a `% !TEX program` comment without a build profile.
+const KNOWN_ENGINES: &[&str] = &[
+    "pdflatex", "xelatex", "lualatex", "latex", "uplatex", "platex", "pdftex", "xetex", "luatex",
+    "tex", "context",
+];
+
 #[derive(Debug)]
 pub struct BuildCommand {
+    pub root: Url,
     program: String,
     args: Vec<String>,
     working_dir: PathBuf,
+    env: Vec<(String, String)>,
 }
 
 impl BuildCommand {
-    pub fn new(workspace: &Workspace, uri: &Url) -> Result<Self, BuildError> {
+    pub fn new(
+        workspace: &Workspace,
+        uri: &Url,
+        profile: Option<&str>,
+    ) -> Result<Self, BuildError> {
         let Some(document) = workspace.lookup(uri) else {
             return Err(BuildError::NotFound(uri.clone()));
         };
 
-        let document = workspace
-            .parents(document)
-            .into_iter()
-            .next()
-            .unwrap_or(document);
+        let config = &workspace.config().build;
+        let profile = profile
+            .map(|name| {
+                config
+                    .profile(name)
+                    .ok_or_else(|| BuildError::UnknownProfile(name.into()))
+            })
+            .transpose()?;
+
+        if profile.is_none() && config.has_default_command() {
+            if let Some((manifest, _)) = workspace.tectonic_output(document) {
+                return Self::tectonic(manifest);
+            }
+        }
+
+        let document = Self::find_root(workspace, document);
 
         let Some(path) = document.path.as_deref().and_then(Path::to_str) else {
             return Err(BuildError::NotLocal(document.uri.clone()));
         };
 
-        let config = &workspace.config().build;
-        let program = config.program.clone();
-        let args = replace_placeholders(&config.args, &[('f', path)]);
+        let (program, args) = match profile {
+            Some(profile) => (profile.program.clone(), profile.args.clone()),
+            None => Self::magic_program(workspace, document)
+                .unwrap_or_else(|| (config.program.clone(), config.args.clone())),
+        };
+
+        let args = replace_placeholders(&args, &[('f', path)]);
 
         let Ok(working_dir) = workspace.current_dir(&document.dir).to_file_path() else {
             return Err(BuildError::NotLocal(document.uri.clone()));
         };
 
         Ok(Self {
+            root: document.uri.clone(),
             program,
             args,
             working_dir,
+            env: Vec::new(),
         })
     }
 
+    /// Selects the engine from a `% !TEX program = <engine>` comment of the root document.
+    /// Engines without a build profile of the same name are only invoked directly
+    /// if they are one of the well-known TeX engines.
+    fn magic_program(workspace: &Workspace, document: &Document) -> Option<(String, Vec<String>)> {
+        let semantics = &document.data.as_tex()?.semantics;
+        let engine = semantics.magic_comment(MagicCommentKind::Program)?;
+
+        if let Some(profile) = workspace.config().build.profile(&engine.text) {
+            log::debug!("Using build profile {} from magic comment", engine.text);
+            return Some((profile.program.clone(), profile.args.clone()));
+        }
+
+        if !KNOWN_ENGINES.contains(&engine.text.as_str()) {
+            log::warn!("Ignoring unknown engine {} from magic comment", engine.text);
+            return None;
+        }
+
+        log::debug!("Using engine {} from magic comment", engine.text);
+        let args = ["-interaction=nonstopmode", "-synctex=1", "%f"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        Some((engine.text.clone(), args))
+    }
+
+    /// Picks the document to compile, preferring the `@default_files` of the `latexmkrc` file.
+    fn find_root<'a>(workspace: &'a Workspace, document: &'a Document) -> &'a Document {
+        let parents = workspace.parents(document);
+        let base_dir = workspace.current_dir(&document.dir);
+        workspace
+            .current_latexmkrc(&base_dir)
+            .into_iter()
+            .flat_map(|data| &data.default_files)
+            .filter_map(|file| base_dir.join(file).ok())
+            .filter_map(|uri| workspace.lookup(&uri))
+            .find(|root| parents.contains(root))
+            .or_else(|| parents.into_iter().next())
+            .unwrap_or(document)
+    }
+
+    fn tectonic(manifest: &Document) -> Result<Self, BuildError> {
+        let Some(working_dir) = manifest.path.as_deref().and_then(Path::parent) else {
+            return Err(BuildError::NotLocal(manifest.uri.clone()));
+        };
+
+        Ok(Self {
+            root: manifest.uri.clone(),
+            program: String::from("tectonic"),
+            args: vec![String::from("-X"), String::from("build")],
+            working_dir: working_dir.to_path_buf(),
+            env: Vec::new(),
+        })
+    }
+
+    /// Compiles the unsaved state of the project inside of a shadow directory instead.
+    /// Files which are not opened in the editor are found through the search paths of the compiler.
+    pub fn into_shadow(self, workspace: &Workspace) -> Result<(Self, ShadowDirectory), BuildError> {
+        let shadow = ShadowDirectory::new(&self.root, self.working_dir.clone());
+        self.with_shadow(workspace, shadow)
+    }
+
+    fn with_shadow(
+        mut self,
+        workspace: &Workspace,
+        shadow: ShadowDirectory,
+    ) -> Result<(Self, ShadowDirectory), BuildError> {
+        if workspace
+            .lookup(&self.root)
+            .map_or(true, |document| document.data.as_tectonic().is_some())
+        {
+            return Err(BuildError::ShadowUnsupported(self.root));
+        }
+
+        // Only the copied files are passed to the compiler because the others
+        // (like a root document which is not opened in the editor) do not exist in the shadow directory.
+        let written = shadow.write(workspace, &self.root)?;
+        for arg in &mut self.args {
+            if let Some(path) = shadow
+                .map(Path::new(arg.as_str()))
+                .filter(|path| written.contains(path))
+            {
+                *arg = path.to_string_lossy().into_owned();
+            }
+        }
+
+        self.working_dir = shadow.dir.clone();
+        self.env = ["TEXINPUTS", "BIBINPUTS"]
+            .into_iter()
+            .map(|variable| (String::from(variable), shadow.search_path(variable)))
+            .collect();
+
+        Ok((self, shadow))
+    }
+
     pub fn spawn(self, sender: Sender<String>) -> Result<Child, BuildError> {
         log::debug!(
             "Spawning compiler {} {:#?} in directory {}",
@@ -86,6 +216,7 @@ impl BuildCommand {
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .current_dir(&self.working_dir)
+            .envs(self.env.iter().cloned())
             .spawn()
             .map_err(Into::into)
     }
@@ -99,6 +230,7 @@ impl BuildCommand {
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .current_dir(&self.working_dir)
+            .envs(self.env.iter().cloned())
             .process_group(0)
             .spawn()
             .map_err(Into::into)
@@ -138,3 +270,262 @@ fn track_output(
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use base_db::Owner;
+    use distro::Language;
+    use expect_test::{expect, Expect};
+    use line_index::LineCol;
+    use test_utils::fixture::Fixture;
+
+    use crate::shadow::ShadowDirectory;
+
+    use super::BuildCommand;
+
+    fn check(fixture: &str, profile: Option<&str>, expect: Expect) {
+        let fixture = Fixture::parse(fixture);
+        let uri = &fixture.documents[0].uri;
+        let command = BuildCommand::new(&fixture.workspace, uri, profile)
+            .map(|command| (command.program, command.args));
+        expect.assert_debug_eq(&command);
+    }
+
+    #[test]
+    fn test_magic_comment() {
+        check(
+            r#"
+%! main.tex
+% !TEX program = lualatex
+\documentclass{article}
+"#,
+            None,
+            expect![[r#"
+                Ok(
+                    (
+                        "latexmk",
+                        [
+                            "-lualatex",
+                            "-interaction=nonstopmode",
+                            "-synctex=1",
+                            "/texlab/main.tex",
+                        ],
+                    ),
+                )
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_magic_comment_unknown_engine() {
+        check(
+            r#"
+%! main.tex
+% !TeX TS-program = uplatex
+\documentclass{article}
+"#,
+            None,
+            expect![[r#"
+                Ok(
+                    (
+                        "uplatex",
+                        [
+                            "-interaction=nonstopmode",
+                            "-synctex=1",
+                            "/texlab/main.tex",
+                        ],
+                    ),
+                )
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_magic_comment_arbitrary_program() {
+        check(
+            r#"
+%! main.tex
+% !TEX program = rm
+\documentclass{article}
+"#,
+            None,
+            expect![[r#"
+                Ok(
+                    (
+                        "latexmk",
+                        [
+                            "-pdf",
+                            "-interaction=nonstopmode",
+                            "-synctex=1",
+                            "/texlab/main.tex",
+                        ],
+                    ),
+                )
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_magic_root() {
+        check(
+            r#"
+%! chapters/intro.tex
+% !TEX root = ../main.tex
+\section{Introduction}
+
+%! main.tex
+% !TEX program = xelatex
+\documentclass{article}
+"#,
+            None,
+            expect![[r#"
+                Ok(
+                    (
+                        "latexmk",
+                        [
+                            "-xelatex",
+                            "-interaction=nonstopmode",
+                            "-synctex=1",
+                            "/texlab/main.tex",
+                        ],
+                    ),
+                )
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_profile() {
+        check(
+            r#"
+%! main.tex
+% !TEX program = lualatex
+\documentclass{article}
+"#,
+            Some("draft"),
+            expect![[r#"
+                Ok(
+                    (
+                        "pdflatex",
+                        [
+                            "-interaction=nonstopmode",
+                            "-synctex=1",
+                            "/texlab/main.tex",
+                        ],
+                    ),
+                )
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_unknown_profile() {
+        check(
+            r#"
+%! main.tex
+\documentclass{article}
+"#,
+            Some("foo"),
+            expect![[r#"
+                Err(
+                    UnknownProfile(
+                        "foo",
+                    ),
+                )
+            "#]],
+        );
+    }
+
+    fn shadow_fixture(input: &str) -> (Fixture, tempfile::TempDir, ShadowDirectory) {
+        let fixture = Fixture::parse(input);
+        let dir = tempfile::tempdir().unwrap();
+        let shadow = ShadowDirectory {
+            base_dir: PathBuf::from(fixture.documents[0].uri.path())
+                .parent()
+                .unwrap()
+                .to_path_buf(),
+            dir: dir.path().join("shadow"),
+        };
+
+        (fixture, dir, shadow)
+    }
+
+    #[test]
+    fn test_shadow() {
+        let (fixture, _dir, shadow) = shadow_fixture(
+            r#"
+%! main.tex
+\documentclass{article}
+\input{chapter}
+
+%! chapter.tex
+Foo
+"#,
+        );
+
+        let uri = &fixture.documents[0].uri;
+        let (command, shadow) = BuildCommand::new(&fixture.workspace, uri, None)
+            .and_then(|command| command.with_shadow(&fixture.workspace, shadow))
+            .unwrap();
+
+        let main_path = shadow.dir.join("main.tex");
+        assert_eq!(command.working_dir, shadow.dir);
+        assert_eq!(command.args.last().map(String::as_str), main_path.to_str());
+
+        assert_eq!(
+            std::fs::read_to_string(&main_path).unwrap(),
+            fixture.documents[0].text
+        );
+        assert_eq!(
+            std::fs::read_to_string(shadow.dir.join("chapter.tex")).unwrap(),
+            fixture.documents[1].text
+        );
+
+        assert!(command.env.iter().any(|(name, value)| name == "TEXINPUTS"
+            && value.contains(&*shadow.base_dir.to_string_lossy())));
+    }
+
+    #[test]
+    fn test_shadow_root_not_open() {
+        let (mut fixture, _dir, shadow) = shadow_fixture(
+            r#"
+%! main.tex
+\documentclass{article}
+\begin{document}
+\input{chapter}
+\end{document}
+
+%! chapter.tex
+Foo
+"#,
+        );
+
+        let main = &fixture.documents[0];
+        fixture.workspace.open(
+            main.uri.clone(),
+            main.text.clone(),
+            Language::Tex,
+            Owner::Server,
+            LineCol { line: 0, col: 0 },
+        );
+
+        let uri = &fixture.documents[1].uri;
+        let (command, shadow) = BuildCommand::new(&fixture.workspace, uri, None)
+            .and_then(|command| command.with_shadow(&fixture.workspace, shadow))
+            .unwrap();
+
+        assert_eq!(command.working_dir, shadow.dir);
+        assert_eq!(
+            command.args.last().map(String::as_str),
+            Some(fixture.documents[0].uri.path())
+        );
+
+        assert!(!shadow.dir.join("main.tex").exists());
+        assert_eq!(
+            std::fs::read_to_string(shadow.dir.join("chapter.tex")).unwrap(),
+            fixture.documents[1].text
+        );
+    }
+}
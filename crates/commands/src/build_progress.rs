@@ -0,0 +1,132 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use syntax::BuildLog;
+
+static RULE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("Run number \\d+ of rule '(?P<rule>[^']+)'").unwrap());
+
+static ENGINE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^This is [a-zA-Z-]*TeX|^note: (Re)?[Rr]unning TeX").unwrap());
+
+static PAGE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new("(^|\\s)\\[(?P<page>\\d+)").unwrap());
+
+/// Tracks the output of a running build to report its progress.
+///
+/// Every run of a rule (`pdflatex`, `biber`, `makeindex`, ...) counts as a pass.
+/// The compiler output of the current TeX run is kept to extract the diagnostics.
+#[derive(Debug, Default)]
+pub struct BuildProgress {
+    pub pass: u32,
+    pub rule: Option<String>,
+    pub page: u32,
+    expected_pages: Option<u32>,
+    rule_started: bool,
+    output: String,
+}
+
+impl BuildProgress {
+    /// Processes a single line of compiler output.
+    pub fn push_line(&mut self, line: &str) {
+        if let Some(captures) = RULE_REGEX.captures(line) {
+            self.pass += 1;
+            self.rule = Some(captures["rule"].into());
+            self.rule_started = true;
+            return;
+        }
+
+        if ENGINE_REGEX.is_match(line) {
+            if !std::mem::take(&mut self.rule_started) {
+                self.pass += 1;
+            }
+
+            if self.page > 0 {
+                self.expected_pages = Some(self.page);
+            }
+
+            self.page = 0;
+            self.output.clear();
+        }
+
+        if let Some(page) = PAGE_REGEX
+            .captures_iter(line)
+            .filter_map(|captures| captures["page"].parse().ok())
+            .max()
+        {
+            self.page = self.page.max(page);
+        }
+
+        self.output.push_str(line);
+        self.output.push('\n');
+    }
+
+    /// Estimates the progress of the current TeX run based on the page count of the previous run.
+    pub fn percentage(&self) -> Option<u32> {
+        let expected_pages = self.expected_pages?;
+        Some((self.page * 100 / expected_pages).min(99))
+    }
+
+    /// Extracts the errors and warnings of the current TeX run.
+    pub fn log(&self) -> BuildLog {
+        parser::parse_build_log(&self.output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BuildProgress;
+
+    #[test]
+    fn test_latexmk_passes() {
+        let mut progress = BuildProgress::default();
+        for line in [
+            "Rc files read:",
+            "Latexmk: Run number 1 of rule 'pdflatex'",
+            "This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex)",
+            "[1{/usr/share/texmf/fonts/map/pdftex/updmap/pdftex.map}] [2] [3]",
+            "Latexmk: Run number 1 of rule 'biber main'",
+            "INFO - This is Biber 2.19",
+            "Latexmk: Run number 2 of rule 'pdflatex'",
+            "This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex)",
+            "[1] [2]",
+        ] {
+            progress.push_line(line);
+        }
+
+        assert_eq!(progress.pass, 3);
+        assert_eq!(progress.rule.as_deref(), Some("pdflatex"));
+        assert_eq!(progress.page, 2);
+        assert_eq!(progress.percentage(), Some(66));
+    }
+
+    #[test]
+    fn test_errors_of_current_run() {
+        let mut progress = BuildProgress::default();
+        for line in [
+            "This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex)",
+            "(./main.tex",
+            "! Undefined control sequence.",
+            "l.3 \\foo",
+            "This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex)",
+            "(./main.tex",
+            "! Missing $ inserted.",
+            "l.5 x^",
+            ")",
+        ] {
+            progress.push_line(line);
+        }
+
+        let messages: Vec<_> = progress
+            .log()
+            .errors
+            .into_iter()
+            .map(|error| (error.message, error.line))
+            .collect();
+
+        assert_eq!(progress.pass, 2);
+        assert_eq!(progress.percentage(), None);
+        assert_eq!(
+            messages,
+            vec![(String::from("Missing $ inserted."), Some(4))]
+        );
+    }
+}
@@ -1,7 +1,8 @@
-use std::process::Stdio;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use base_db::{Document, Workspace};
+use rustc_hash::FxHashSet;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum CleanTarget {
@@ -9,55 +10,294 @@ pub enum CleanTarget {
     Artifacts,
 }
 
+/// The extensions of the files which are produced by TeX engines and the common auxiliary tools.
+const AUXILIARY_EXTENSIONS: &[&str] = &[
+    "aux",
+    "log",
+    "toc",
+    "lof",
+    "lot",
+    "bbl",
+    "bcf",
+    "blg",
+    "run.xml",
+    "fls",
+    "fdb_latexmk",
+    "synctex.gz",
+    "synctex",
+    "out",
+    "nav",
+    "snm",
+    "vrb",
+    "idx",
+    "ind",
+    "ilg",
+    "glo",
+    "gls",
+    "glg",
+    "ist",
+    "acn",
+    "acr",
+    "alg",
+    "xdy",
+    "brf",
+    "thm",
+    "xdv",
+    "-blx.bib",
+];
+
+/// The extensions of the final outputs which are only removed when cleaning all artifacts.
+const ARTIFACT_EXTENSIONS: &[&str] = &["pdf", "dvi", "ps"];
+
+/// Removes the files produced by compiling the root documents of a project
+/// without relying on an external tool like `latexmk`.
 #[derive(Debug)]
 pub struct CleanCommand {
-    executable: String,
-    args: Vec<String>,
+    files: Vec<PathBuf>,
 }
 
 impl CleanCommand {
     pub fn new(workspace: &Workspace, document: &Document, target: CleanTarget) -> Result<Self> {
-        let Some(path) = document.path.as_deref() else {
+        if document.path.is_none() {
             anyhow::bail!("document '{}' is not a local file", document.uri)
         };
 
-        let base_dir = workspace.current_dir(&document.dir);
+        let mut roots: Vec<&Document> = workspace.parents(document).into_iter().collect();
+        if roots.is_empty() {
+            roots.push(document);
+        }
 
-        let flag = match target {
-            CleanTarget::Auxiliary => "-c",
-            CleanTarget::Artifacts => "-C",
-        };
+        let mut files = FxHashSet::default();
+        for root in roots {
+            let Some(stem) = root.path.as_deref().and_then(Path::file_stem) else {
+                continue;
+            };
 
-        let out_dir = match target {
-            CleanTarget::Auxiliary => workspace.aux_dir(&base_dir),
-            CleanTarget::Artifacts => workspace.pdf_dir(&base_dir),
-        };
+            let stem = stem.to_string_lossy();
+            let base_dir = workspace.current_dir(&root.dir);
+            let jobname = workspace
+                .current_latexmkrc(&base_dir)
+                .and_then(|data| data.jobname.as_deref())
+                .map_or_else(|| stem.to_string(), |name| name.replace("%A", &stem));
 
-        let out_dir_path = out_dir.to_file_path().unwrap();
+            let aux_dir = workspace.aux_dir(&base_dir);
+            let log_dir = workspace.log_dir(&base_dir);
+            let pdf_dir = workspace.pdf_dir(&base_dir);
+            let dirs = [aux_dir, log_dir, pdf_dir.clone()]
+                .into_iter()
+                .filter_map(|dir| dir.to_file_path().ok())
+                .collect::<FxHashSet<_>>();
 
-        let executable = String::from("latexmk");
-        let args = vec![
-            format!("-outdir={}", out_dir_path.display()),
-            String::from(flag),
-            path.display().to_string(),
-        ];
+            for dir in &dirs {
+                files.extend(Self::candidates(dir, &jobname, AUXILIARY_EXTENSIONS));
+            }
 
-        Ok(Self { executable, args })
+            if target == CleanTarget::Artifacts {
+                if let Ok(pdf_dir) = pdf_dir.to_file_path() {
+                    files.extend(Self::candidates(&pdf_dir, &jobname, ARTIFACT_EXTENSIONS));
+                }
+            }
+        }
+
+        let mut files: Vec<_> = files.into_iter().filter(|path| path.is_file()).collect();
+        files.sort();
+        Ok(Self { files })
     }
 
-    pub fn run(self) -> Result<()> {
-        log::debug!("Cleaning output files: {} {:?}", self.executable, self.args);
-        let result = std::process::Command::new(self.executable)
-            .args(self.args)
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .status();
+    fn candidates<'a>(
+        dir: &'a Path,
+        jobname: &'a str,
+        extensions: &'a [&str],
+    ) -> impl Iterator<Item = PathBuf> + 'a {
+        extensions.iter().map(move |extension| {
+            let separator = if extension.starts_with('-') { "" } else { "." };
+            dir.join(format!("{jobname}{separator}{extension}"))
+        })
+    }
 
-        if let Err(why) = result {
-            anyhow::bail!("failed to spawn process: {why}")
+    /// Returns the files which would be removed by running the command.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Removes all files of the command, even if some of them cannot be removed.
+    /// The failures are reported together afterwards.
+    pub fn run(self) -> Result<Vec<PathBuf>> {
+        log::debug!("Cleaning output files: {:?}", self.files);
+        let mut removed = Vec::new();
+        let mut failures = Vec::new();
+        for path in self.files {
+            match std::fs::remove_file(&path) {
+                Ok(()) => removed.push(path),
+                Err(why) => failures.push(format!("{}: {why}", path.display())),
+            }
         }
 
-        Ok(())
+        if !failures.is_empty() {
+            anyhow::bail!("failed to remove {}", failures.join(", "))
+        }
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use base_db::{Config, Owner, Workspace};
+    use distro::Language;
+    use expect_test::{expect, Expect};
+    use line_index::LineCol;
+    use url::Url;
+
+    use super::{CleanCommand, CleanTarget};
+
+    fn check(
+        files: &[&str],
+        latexmkrc: Option<&str>,
+        config: Config,
+        target: CleanTarget,
+        expect: Expect,
+    ) {
+        let dir = tempfile::tempdir().unwrap();
+        for file in files {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        let mut workspace = Workspace::default();
+        workspace.set_config(config);
+
+        let mut open = |name: &str, text: &str, language: Language| {
+            let uri = Url::from_file_path(dir.path().join(name)).unwrap();
+            let cursor = LineCol { line: 0, col: 0 };
+            workspace.open(uri.clone(), text.into(), language, Owner::Client, cursor);
+            uri
+        };
+
+        if let Some(text) = latexmkrc {
+            open(".latexmkrc", text, Language::Latexmkrc);
+        }
+
+        let uri = open("main.tex", "\\documentclass{article}", Language::Tex);
+        let document = workspace.lookup(&uri).unwrap();
+        let command = CleanCommand::new(&workspace, document, target).unwrap();
+        let files: Vec<_> = command
+            .files()
+            .iter()
+            .map(|path| relative_path(dir.path(), path))
+            .collect();
+
+        expect.assert_debug_eq(&files);
+    }
+
+    fn relative_path(dir: &Path, path: &Path) -> String {
+        let path = path.strip_prefix(dir).unwrap();
+        path.to_string_lossy().replace('\\', "/")
+    }
+
+    #[test]
+    fn test_auxiliary() {
+        check(
+            &[
+                "main.aux",
+                "main.log",
+                "main.pdf",
+                "main.synctex.gz",
+                "main-blx.bib",
+                "main.tex",
+                "other.aux",
+            ],
+            None,
+            Config::default(),
+            CleanTarget::Auxiliary,
+            expect![[r#"
+                [
+                    "main-blx.bib",
+                    "main.aux",
+                    "main.log",
+                    "main.synctex.gz",
+                ]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_artifacts() {
+        check(
+            &["main.aux", "main.dvi", "main.pdf", "main.tex", "other.pdf"],
+            None,
+            Config::default(),
+            CleanTarget::Artifacts,
+            expect![[r#"
+                [
+                    "main.aux",
+                    "main.dvi",
+                    "main.pdf",
+                ]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_output_dirs() {
+        let mut config = Config::default();
+        config.build.aux_dir = String::from("build");
+        config.build.log_dir = String::from("logs");
+        config.build.pdf_dir = String::from("out");
+        check(
+            &[
+                "build/main.aux",
+                "build/main.pdf",
+                "logs/main.log",
+                "main.aux",
+                "main.tex",
+                "out/main.pdf",
+            ],
+            None,
+            config,
+            CleanTarget::Artifacts,
+            expect![[r#"
+                [
+                    "build/main.aux",
+                    "logs/main.log",
+                    "out/main.pdf",
+                ]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_jobname() {
+        check(
+            &["main-draft.aux", "main-draft.pdf", "main.aux", "main.tex"],
+            Some("$jobname = \"%A-draft\";"),
+            Config::default(),
+            CleanTarget::Artifacts,
+            expect![[r#"
+                [
+                    "main-draft.aux",
+                    "main-draft.pdf",
+                ]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_run_continues_after_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.aux");
+        let existing = dir.path().join("main.aux");
+        std::fs::write(&existing, "").unwrap();
+
+        let command = CleanCommand {
+            files: vec![missing.clone(), existing.clone()],
+        };
+
+        let error = command.run().unwrap_err().to_string();
+        assert!(error.contains(&missing.display().to_string()));
+        assert!(!existing.exists());
     }
 }
@@ -1,7 +1,10 @@
 use std::io::Write;
 
 use anyhow::Result;
-use base_db::{graph, Document, Workspace};
+use base_db::{
+    graph::{self, EdgeKind},
+    Document, Workspace,
+};
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
 
@@ -44,10 +47,13 @@ pub fn show_dependency_graph(workspace: &Workspace) -> Result<String> {
     {
         let source = &documents[edge.source];
         let target = &documents[edge.target];
-        let label = edge
-            .weight
-            .as_ref()
-            .map_or("<artifact>", |weight| &weight.link.path.text);
+        let label = match (edge.kind, edge.weight.as_ref()) {
+            (_, Some(weight)) => weight.link.path.text.as_str(),
+            (EdgeKind::MagicRoot, None) => "<root>",
+            (EdgeKind::TectonicInput, None) => "<input>",
+            (EdgeKind::Recorded, None) => "<recorded>",
+            (EdgeKind::Link | EdgeKind::Artifact, None) => "<artifact>",
+        };
 
         writeln!(&mut writer, "\t{source} -> {target} [label=\"{label}\"];")?;
     }
@@ -0,0 +1,115 @@
+use base_db::{util::find_project_frames, Document, Workspace};
+use rowan::TextRange;
+use syntax::latex;
+use url::Url;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FrameMatch {
+    pub uri: Url,
+    pub title: Option<String>,
+    pub full_range: TextRange,
+}
+
+/// Finds the Beamer frame with the given (one-based) number in the project of the document.
+/// Like the frame numbers of the document symbols, the frames are counted from the root document.
+pub fn find_frame(workspace: &Workspace, document: &Document, number: usize) -> Option<FrameMatch> {
+    let parents = workspace.parents(document);
+    let root = parents.iter().next().copied().unwrap_or(document);
+
+    let (document, frame) = find_project_frames(workspace, root)
+        .into_iter()
+        .nth(number.checked_sub(1)?)?;
+
+    Some(FrameMatch {
+        uri: document.uri.clone(),
+        title: frame.title_text(),
+        full_range: latex::small_range(&frame.environment),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+    use test_utils::fixture::Fixture;
+
+    use crate::find_frame;
+
+    fn check(fixture: &str, number: usize, expect: Expect) {
+        let fixture = Fixture::parse(fixture);
+        let document = fixture.workspace.lookup(&fixture.documents[0].uri).unwrap();
+        let result = find_frame(&fixture.workspace, document, number)
+            .map(|result| (result.uri.to_string(), result.title, result.full_range));
+        expect.assert_debug_eq(&result);
+    }
+
+    #[test]
+    fn test_simple() {
+        check(
+            r#"
+%! main.tex
+\begin{frame}{Foo}
+\end{frame}
+\begin{frame}
+    \frametitle{Bar}
+\end{frame}"#,
+            2,
+            expect![[r#"
+                Some(
+                    (
+                        "file:///texlab/main.tex",
+                        Some(
+                            "Bar",
+                        ),
+                        31..77,
+                    ),
+                )
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_project() {
+        check(
+            r#"
+%! slides.tex
+\begin{frame}{Bar}
+\end{frame}
+
+%! main.tex
+\documentclass{beamer}
+\begin{document}
+\begin{frame}{Foo}
+\end{frame}
+\input{slides}
+\begin{frame}{Baz}
+\end{frame}
+\end{document}"#,
+            2,
+            expect![[r#"
+                Some(
+                    (
+                        "file:///texlab/slides.tex",
+                        Some(
+                            "Bar",
+                        ),
+                        0..30,
+                    ),
+                )
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        check(
+            r#"
+%! main.tex
+\begin{frame}{Foo}
+\end{frame}"#,
+            0,
+            expect![[r#"
+                None
+            "#]],
+        );
+    }
+}
@@ -1,11 +1,14 @@
-use std::{path::PathBuf, process::Stdio};
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+};
 
 use anyhow::Result;
-use base_db::{Document, Workspace};
+use base_db::{Document, Owner, Workspace};
 use thiserror::Error;
 use url::Url;
 
-use crate::placeholders::replace_placeholders;
+use crate::{placeholders::replace_placeholders, shadow::ShadowDirectory};
 
 #[derive(Debug, Error)]
 pub enum ForwardSearchError {
@@ -39,6 +42,24 @@ impl ForwardSearch {
         workspace: &Workspace,
         uri: &Url,
         line: Option<u32>,
+    ) -> Result<Self, ForwardSearchError> {
+        Self::create(workspace, uri, line, None)
+    }
+
+    /// Prepares a forward search into the PDF which has been compiled inside of the given shadow directory.
+    pub fn preview(
+        workspace: &Workspace,
+        uri: &Url,
+        shadow: &ShadowDirectory,
+    ) -> Result<Self, ForwardSearchError> {
+        Self::create(workspace, uri, None, Some(shadow))
+    }
+
+    fn create(
+        workspace: &Workspace,
+        uri: &Url,
+        line: Option<u32>,
+        shadow: Option<&ShadowDirectory>,
     ) -> Result<Self, ForwardSearchError> {
         log::debug!("[FwdSearch] Preparing forward search: document={uri}, line={line:#?}");
         let synctex_config = workspace
@@ -57,12 +78,17 @@ impl ForwardSearch {
 
         log::debug!("[FwdSearch] root_document={}", parent.uri,);
 
-        let pdf_path = Self::find_pdf(workspace, parent)?;
+        let pdf_path = Self::find_pdf(workspace, parent, shadow)?;
         let pdf_path = pdf_path.to_string_lossy().into_owned();
         let tex_path = child
             .path
             .as_deref()
             .ok_or_else(|| ForwardSearchError::InvalidPath(child.uri.clone()))?;
+
+        let tex_path = shadow
+            .filter(|_| child.owner == Owner::Client)
+            .and_then(|shadow| shadow.map(tex_path))
+            .unwrap_or_else(|| tex_path.to_path_buf());
         let tex_path = tex_path.to_string_lossy().into_owned();
 
         let line = line.unwrap_or(child.cursor.line);
@@ -77,7 +103,15 @@ impl ForwardSearch {
         Ok(Self { program, args })
     }
 
-    fn find_pdf(workspace: &Workspace, document: &Document) -> Result<PathBuf, ForwardSearchError> {
+    fn find_pdf(
+        workspace: &Workspace,
+        document: &Document,
+        shadow: Option<&ShadowDirectory>,
+    ) -> Result<PathBuf, ForwardSearchError> {
+        if let Some((manifest, output)) = workspace.tectonic_output(document) {
+            return Self::find_tectonic_pdf(manifest, &output.name);
+        }
+
         let base_dir = workspace.current_dir(&document.dir);
         let pdf_dir = workspace.pdf_dir(&base_dir);
 
@@ -90,14 +124,26 @@ impl ForwardSearch {
         let pdf_name_override = workspace.config().build.output_filename.clone();
         log::debug!("[FwdSearch] pdf_name_override={pdf_name_override:?}");
 
+        let jobname = workspace
+            .current_latexmkrc(&base_dir)
+            .and_then(|data| data.jobname.as_deref());
+
+        log::debug!("[FwdSearch] jobname={jobname:?}");
+
         let pdf_name = pdf_name_override
             .or_else(|| {
-                let stem = document.path.as_ref()?.file_stem()?;
-                Some(format!("{}.pdf", stem.to_string_lossy()))
+                let stem = document.path.as_ref()?.file_stem()?.to_string_lossy();
+                let name =
+                    jobname.map_or_else(|| stem.to_string(), |name| name.replace("%A", &stem));
+                Some(format!("{name}.pdf"))
             })
             .ok_or_else(|| ForwardSearchError::InvalidPath(document.uri.clone()))?;
 
         let pdf_path = pdf_dir.join(&pdf_name);
+        let pdf_path = shadow
+            .and_then(|shadow| shadow.map(&pdf_path))
+            .unwrap_or(pdf_path);
+
         let pdf_exists = pdf_path.exists();
 
         log::debug!("[FwdSearch] pdf_path={pdf_path:?}, pdf_exists={pdf_exists}");
@@ -107,6 +153,27 @@ impl ForwardSearch {
 
         Ok(pdf_path)
     }
+
+    fn find_tectonic_pdf(manifest: &Document, name: &str) -> Result<PathBuf, ForwardSearchError> {
+        let project_dir = manifest
+            .path
+            .as_deref()
+            .and_then(Path::parent)
+            .ok_or_else(|| ForwardSearchError::InvalidPath(manifest.uri.clone()))?;
+
+        let pdf_path = project_dir
+            .join("build")
+            .join(name)
+            .join(format!("{name}.pdf"));
+
+        let pdf_exists = pdf_path.exists();
+        log::debug!("[FwdSearch] tectonic_pdf_path={pdf_path:?}, pdf_exists={pdf_exists}");
+        if !pdf_exists {
+            return Err(ForwardSearchError::PdfNotFound(pdf_path));
+        }
+
+        Ok(pdf_path)
+    }
 }
 
 impl ForwardSearch {
@@ -1,16 +1,26 @@
 mod build;
+mod build_progress;
 mod change_env;
 mod clean;
 mod dep_graph;
 mod find_envs;
+mod find_frame;
 mod fwd_search;
+mod merge_entries;
 mod placeholders;
+mod shadow;
+mod sort_bib;
 
 pub use self::{
     build::{BuildCommand, BuildError},
+    build_progress::BuildProgress,
     change_env::{change_environment, ChangeEnvironmentResult},
     clean::{CleanCommand, CleanTarget},
     dep_graph::show_dependency_graph,
     find_envs::find_environments,
+    find_frame::{find_frame, FrameMatch},
     fwd_search::{ForwardSearch, ForwardSearchError},
+    merge_entries::{merge_duplicate_entries, MergeEntriesResult},
+    shadow::ShadowDirectory,
+    sort_bib::{sort_bibliography, BibDelimiter, BibSortKey, SortBibliographyOptions},
 };
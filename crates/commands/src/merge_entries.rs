@@ -0,0 +1,207 @@
+use base_db::{semantics::bib::Entry, util::queries::Object, Document, Workspace};
+use rowan::{ast::AstNode, TextRange, TextSize};
+use rustc_hash::FxHashSet;
+use syntax::bibtex::{self, HasComma, HasName, HasValue};
+
+#[derive(Debug)]
+pub struct MergeEntriesResult<'a> {
+    pub main: &'a str,
+    pub duplicate: &'a str,
+    pub edits: Vec<(&'a Document, TextRange, String)>,
+}
+
+/// Computes the edits required to merge every entry that describes the same work as the entry
+/// at the given position into that entry.
+/// Fields missing from the entry are copied from the duplicate, the duplicate gets removed
+/// and all citations of the duplicate are redirected to the entry.
+pub fn merge_duplicate_entries<'a>(
+    workspace: &'a Workspace,
+    document: &'a Document,
+    offset: TextSize,
+) -> Vec<MergeEntriesResult<'a>> {
+    let Some(data) = document.data.as_bib() else {
+        return Vec::new();
+    };
+
+    let Some(main) = data
+        .semantics
+        .entries
+        .iter()
+        .find(|entry| entry.full_range.contains_inclusive(offset))
+    else {
+        return Vec::new();
+    };
+
+    let project = workspace.project(document);
+    Entry::find_all(&project)
+        .filter(|(_, other)| other.name.text != main.name.text)
+        .filter(|(_, other)| main.identity.matches(&other.identity))
+        .filter_map(|(other_document, other)| {
+            merge(
+                &project.documents,
+                (document, main),
+                (other_document, other),
+            )
+        })
+        .collect()
+}
+
+fn merge<'a>(
+    documents: &FxHashSet<&'a Document>,
+    (main_document, main): (&'a Document, &'a Entry),
+    (other_document, other): (&'a Document, &'a Entry),
+) -> Option<MergeEntriesResult<'a>> {
+    let main_node = find_entry_node(main_document, main)?;
+    let other_node = find_entry_node(other_document, other)?;
+
+    let existing_fields: FxHashSet<String> = main_node
+        .fields()
+        .filter_map(|field| field.name_token())
+        .map(|name| name.text().to_lowercase())
+        .collect();
+
+    let mut edits = Vec::new();
+
+    let mut new_fields = String::new();
+    for field in other_node.fields() {
+        let (Some(name), Some(value)) = (field.name_token(), field.value()) else {
+            continue;
+        };
+
+        if !existing_fields.contains(&name.text().to_lowercase()) {
+            new_fields.push_str(&format!(
+                "\n    {} = {},",
+                name.text(),
+                value.syntax().text()
+            ));
+        }
+    }
+
+    if !new_fields.is_empty() {
+        let (offset, needs_comma) = match main_node.fields().last() {
+            Some(field) => (
+                field.syntax().text_range().end(),
+                field.comma_token().is_none(),
+            ),
+            None => (main_node.comma_token()?.text_range().end(), false),
+        };
+
+        if needs_comma {
+            new_fields.insert(0, ',');
+        }
+
+        edits.push((main_document, TextRange::empty(offset), new_fields));
+    }
+
+    let mut delete_range = other.full_range;
+    if other_document.text[usize::from(delete_range.end())..].starts_with('\n') {
+        delete_range = TextRange::new(
+            delete_range.start(),
+            delete_range.end() + TextSize::of('\n'),
+        );
+    }
+
+    edits.push((other_document, delete_range, String::new()));
+
+    for document in documents {
+        let Some(data) = document.data.as_tex() else {
+            continue;
+        };
+
+        for citation in &data.semantics.citations {
+            if citation.name.text == other.name.text {
+                edits.push((document, citation.name.range, main.name.text.clone()));
+            }
+        }
+    }
+
+    Some(MergeEntriesResult {
+        main: &main.name.text,
+        duplicate: &other.name.text,
+        edits,
+    })
+}
+
+fn find_entry_node(document: &Document, entry: &Entry) -> Option<bibtex::Entry> {
+    let root = document.data.as_bib()?.root_node();
+    root.children()
+        .filter_map(bibtex::Entry::cast)
+        .find(|node| node.syntax().text_range() == entry.full_range)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+    use test_utils::fixture::Fixture;
+
+    use super::merge_duplicate_entries;
+
+    fn check(fixture: &str, expect: Expect) {
+        let fixture = Fixture::parse(fixture);
+        let (params, offset) = fixture.make_params().unwrap();
+        let results = merge_duplicate_entries(&fixture.workspace, params.document, offset);
+        expect.assert_debug_eq(&results);
+    }
+
+    #[test]
+    fn test_merge() {
+        check(
+            r#"
+%! main.bib
+@article{foo, doi = {10.1000/xyz}, title = {Foo}}
+          |
+@article{bar, doi = {10.1000/xyz}, year = {2020}}
+
+%! main.tex
+\bibliography{main}
+\cite{bar}
+"#,
+            expect![[r#"
+                [
+                    MergeEntriesResult {
+                        main: "foo",
+                        duplicate: "bar",
+                        edits: [
+                            (
+                                Document(
+                                    "file:///texlab/main.bib",
+                                ),
+                                48..48,
+                                ",\n    year = {2020},",
+                            ),
+                            (
+                                Document(
+                                    "file:///texlab/main.bib",
+                                ),
+                                50..100,
+                                "",
+                            ),
+                            (
+                                Document(
+                                    "file:///texlab/main.tex",
+                                ),
+                                26..29,
+                                "foo",
+                            ),
+                        ],
+                    },
+                ]
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_no_duplicates() {
+        check(
+            r#"
+%! main.bib
+@article{foo, doi = {10.1000/xyz}}
+          |
+@article{bar, doi = {10.1000/abc}}
+"#,
+            expect![[r#"
+                []
+            "#]],
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use std::{
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use base_db::{Owner, Workspace};
+use rustc_hash::FxHasher;
+use url::Url;
+
+/// A copy of the unsaved documents of a project which is used to compile
+/// the in-memory state without modifying the files of the user.
+#[derive(Debug, Clone)]
+pub struct ShadowDirectory {
+    pub base_dir: PathBuf,
+    pub dir: PathBuf,
+}
+
+impl ShadowDirectory {
+    pub fn new(root: &Url, base_dir: PathBuf) -> Self {
+        let mut hasher = FxHasher::default();
+        root.as_str().hash(&mut hasher);
+        let dir = std::env::temp_dir()
+            .join("texlab-preview")
+            .join(format!("{:016x}", hasher.finish()));
+
+        Self { base_dir, dir }
+    }
+
+    /// Maps a path of the project to its location inside the shadow directory.
+    pub fn map(&self, path: &Path) -> Option<PathBuf> {
+        let relative_path = path.strip_prefix(&self.base_dir).ok()?;
+        Some(self.dir.join(relative_path))
+    }
+
+    /// Writes the documents of the project which are opened in the editor into the shadow directory
+    /// and returns the paths of the copies.
+    /// Copies of documents which have been closed in the meantime are removed,
+    /// so that the compiler picks up the file from the project instead.
+    pub fn write(&self, workspace: &Workspace, root: &Url) -> std::io::Result<Vec<PathBuf>> {
+        let mut written = Vec::new();
+        let Some(root_document) = workspace.lookup(root) else {
+            return Ok(written);
+        };
+
+        std::fs::create_dir_all(&self.dir)?;
+        for document in &workspace.project(root_document).documents {
+            let Some(path) = document.path.as_deref().and_then(|path| self.map(path)) else {
+                continue;
+            };
+
+            if document.owner == Owner::Client {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+
+                std::fs::write(&path, &document.text)?;
+                written.push(path);
+            } else if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+        }
+
+        Ok(written)
+    }
+
+    /// Computes the search path which makes the files of the project
+    /// available to the compiler if they have not been copied.
+    pub fn search_path(&self, variable: &str) -> String {
+        let separator = if cfg!(windows) { ';' } else { ':' };
+        let existing = std::env::var(variable).unwrap_or_default();
+        format!(
+            ".{separator}{}{separator}{existing}",
+            self.base_dir.display()
+        )
+    }
+}
@@ -0,0 +1,526 @@
+use std::cmp::Ordering;
+
+use base_db::Document;
+use bibtex_utils::field::{author::AuthorFieldData, date::DateFieldData};
+use rowan::{ast::AstNode, NodeOrToken};
+use syntax::bibtex::{self, HasName, HasType, HasValue};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub enum BibSortKey {
+    #[default]
+    Key,
+    Year,
+    Author,
+    Type,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum BibDelimiter {
+    Braces,
+    Quotes,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SortBibliographyOptions {
+    pub sort_by: BibSortKey,
+    pub sort_fields: bool,
+    pub lowercase_field_names: bool,
+    pub delimiter: Option<BibDelimiter>,
+    pub remove_empty_fields: bool,
+    /// The indentation of the fields of rewritten entries.
+    pub formatting: bibfmt::Options,
+}
+
+impl SortBibliographyOptions {
+    fn rewrites_fields(&self) -> bool {
+        self.sort_fields
+            || self.lowercase_field_names
+            || self.delimiter.is_some()
+            || self.remove_empty_fields
+    }
+}
+
+/// The order in which fields appear if `sort_fields` is set.
+/// Fields that are not part of this list are placed after the known fields
+/// and keep their relative order.
+static FIELD_ORDER: &[&str] = &[
+    "author",
+    "editor",
+    "translator",
+    "title",
+    "subtitle",
+    "booktitle",
+    "journal",
+    "journaltitle",
+    "series",
+    "volume",
+    "number",
+    "issue",
+    "edition",
+    "chapter",
+    "pages",
+    "eid",
+    "publisher",
+    "organization",
+    "institution",
+    "school",
+    "address",
+    "location",
+    "year",
+    "month",
+    "date",
+    "doi",
+    "isbn",
+    "issn",
+    "url",
+    "urldate",
+    "eprint",
+    "eprinttype",
+    "archiveprefix",
+    "eprintclass",
+    "primaryclass",
+    "language",
+    "note",
+    "abstract",
+    "keywords",
+    "file",
+];
+
+/// Sorts the entries of a BibTeX document and normalizes their fields according to `options`.
+/// String definitions and preambles are kept at the top of the document because entries may refer to them.
+/// Comments directly preceding an entry are moved together with the entry.
+pub fn sort_bibliography(document: &Document, options: &SortBibliographyOptions) -> Option<String> {
+    let root = document.data.as_bib()?.root_node();
+
+    let mut header = Vec::new();
+    let mut entries = Vec::new();
+    let mut leading = String::new();
+    for element in root.children_with_tokens() {
+        match element {
+            NodeOrToken::Token(token) => {
+                leading.push_str(token.text());
+            }
+            NodeOrToken::Node(node) => {
+                let comment = std::mem::take(&mut leading).trim().to_string();
+                match bibtex::Entry::cast(node.clone()) {
+                    Some(entry) => {
+                        let text = if options.rewrites_fields() {
+                            rewrite_entry(&entry, options)
+                        } else {
+                            node.text().to_string()
+                        };
+
+                        entries.push(SortItem {
+                            comment,
+                            text,
+                            key: SortKey::new(&entry, options.sort_by),
+                        });
+                    }
+                    None => {
+                        header.push(join_comment(comment, node.text().to_string()));
+                    }
+                }
+            }
+        }
+    }
+
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let mut output = header
+        .into_iter()
+        .chain(
+            entries
+                .into_iter()
+                .map(|item| join_comment(item.comment, item.text)),
+        )
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let trailing = leading.trim();
+    if !trailing.is_empty() {
+        output.push_str("\n\n");
+        output.push_str(trailing);
+    }
+
+    output.push('\n');
+    Some(output)
+}
+
+fn join_comment(comment: String, text: String) -> String {
+    if comment.is_empty() {
+        text
+    } else {
+        format!("{comment}\n{text}")
+    }
+}
+
+fn rewrite_entry(entry: &bibtex::Entry, options: &SortBibliographyOptions) -> String {
+    let entry_type = entry
+        .type_token()
+        .map_or(String::new(), |token| token.to_string());
+    let key = entry
+        .name_token()
+        .map_or(String::new(), |token| token.to_string());
+
+    let mut fields = entry
+        .fields()
+        .filter_map(|field| {
+            let name = field.name_token()?.to_string();
+            let value = field.value();
+            if options.remove_empty_fields && value.as_ref().map_or(true, is_empty_value) {
+                return None;
+            }
+
+            let value = value.map_or(String::new(), |value| match options.delimiter {
+                Some(delimiter) => change_delimiter(&value, delimiter),
+                None => value.syntax().text().to_string(),
+            });
+
+            Some((name, value))
+        })
+        .collect::<Vec<_>>();
+
+    if options.sort_fields {
+        fields.sort_by_key(|(name, _)| {
+            let name = name.to_lowercase();
+            FIELD_ORDER
+                .iter()
+                .position(|field| *field == name)
+                .unwrap_or(FIELD_ORDER.len())
+        });
+    }
+
+    let indent = options.formatting.indent();
+    let mut output = format!("{entry_type}{{{key},\n");
+    for (name, value) in fields {
+        let name = if options.lowercase_field_names {
+            name.to_lowercase()
+        } else {
+            name
+        };
+
+        output.push_str(&format!("{indent}{name} = {value},\n"));
+    }
+
+    output.push('}');
+    output
+}
+
+fn is_empty_value(value: &bibtex::Value) -> bool {
+    let text = value.syntax().text().to_string();
+    group_content(value, &text).is_some_and(|content| content.trim().is_empty())
+}
+
+/// Returns the text between the delimiters of a closed curly or quote group.
+fn group_content<'a>(value: &bibtex::Value, text: &'a str) -> Option<&'a str> {
+    match value {
+        bibtex::Value::CurlyGroup(_) => text.strip_prefix('{')?.strip_suffix('}'),
+        bibtex::Value::QuoteGroup(_) => text.strip_prefix('"')?.strip_suffix('"'),
+        _ => None,
+    }
+}
+
+fn change_delimiter(value: &bibtex::Value, delimiter: BibDelimiter) -> String {
+    let text = value.syntax().text().to_string();
+    let Some(content) = group_content(value, &text) else {
+        return text;
+    };
+
+    match (value, delimiter) {
+        (bibtex::Value::CurlyGroup(_), BibDelimiter::Quotes) if !content.contains('"') => {
+            format!("\"{content}\"")
+        }
+        (bibtex::Value::QuoteGroup(_), BibDelimiter::Braces) => format!("{{{content}}}"),
+        _ => text,
+    }
+}
+
+#[derive(Debug)]
+struct SortItem {
+    comment: String,
+    text: String,
+    key: SortKey,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct SortKey {
+    primary: Option<String>,
+    year: Option<i32>,
+    name: String,
+    by_year: bool,
+}
+
+impl SortKey {
+    fn new(entry: &bibtex::Entry, sort_by: BibSortKey) -> Self {
+        let name = entry
+            .name_token()
+            .map_or(String::new(), |token| token.text().to_lowercase());
+
+        let year = entry
+            .fields()
+            .filter(|field| {
+                field.name_token().is_some_and(|name| {
+                    let name = name.text();
+                    name.eq_ignore_ascii_case("year") || name.eq_ignore_ascii_case("date")
+                })
+            })
+            .filter_map(|field| DateFieldData::parse(&field.value()?))
+            .find_map(|date| match date {
+                DateFieldData::Year(year) => Some(year),
+                DateFieldData::Date(date) => Some(chrono::Datelike::year(&date)),
+                DateFieldData::Month(_) | DateFieldData::Other(_) => None,
+            });
+
+        let primary = match sort_by {
+            BibSortKey::Key | BibSortKey::Year => None,
+            BibSortKey::Author => entry
+                .fields()
+                .filter(|field| {
+                    field.name_token().is_some_and(|name| {
+                        let name = name.text();
+                        name.eq_ignore_ascii_case("author") || name.eq_ignore_ascii_case("editor")
+                    })
+                })
+                .filter_map(|field| AuthorFieldData::parse(&field.value()?))
+                .find_map(|data| Some(data.authors.first()?.surname().to_lowercase())),
+            BibSortKey::Type => entry.type_token().map(|token| token.text().to_lowercase()),
+        };
+
+        Self {
+            primary,
+            year,
+            name,
+            by_year: sort_by != BibSortKey::Key,
+        }
+    }
+}
+
+impl PartialOrd for SortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Entries without the requested information are placed at the end.
+        fn cmp_missing_last<T: Ord>(a: &Option<T>, b: &Option<T>) -> Ordering {
+            match (a, b) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+
+        cmp_missing_last(&self.primary, &other.primary)
+            .then_with(|| {
+                if self.by_year {
+                    cmp_missing_last(&self.year, &other.year)
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .then_with(|| self.name.cmp(&other.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::{expect, Expect};
+    use test_utils::fixture::Fixture;
+
+    use super::{sort_bibliography, BibDelimiter, BibSortKey, SortBibliographyOptions};
+
+    fn check(fixture: &str, options: SortBibliographyOptions, expect: Expect) {
+        let fixture = Fixture::parse(fixture);
+        let document = fixture.workspace.iter().next().unwrap();
+        let output = sort_bibliography(document, &options).unwrap();
+        expect.assert_eq(&output);
+    }
+
+    #[test]
+    fn test_sort_by_key() {
+        check(
+            r#"
+%! main.bib
+@string{foo = "Foo"}
+
+% A comment about b
+@article{b, title = {B}}
+@book{A, title = {A}}
+@misc{c, title = {C}}
+"#,
+            SortBibliographyOptions::default(),
+            expect![[r#"
+                @string{foo = "Foo"}
+
+                @book{A, title = {A}}
+
+                % A comment about b
+                @article{b, title = {B}}
+
+                @misc{c, title = {C}}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_sort_by_year() {
+        check(
+            r#"
+%! main.bib
+@article{a, year = {2020}}
+@article{b}
+@article{c, date = {2019-05-01}}
+"#,
+            SortBibliographyOptions {
+                sort_by: BibSortKey::Year,
+                ..SortBibliographyOptions::default()
+            },
+            expect![[r#"
+                @article{c, date = {2019-05-01}}
+
+                @article{a, year = {2020}}
+
+                @article{b}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_sort_by_author() {
+        check(
+            r#"
+%! main.bib
+@article{a, author = {Zeta, Anna}}
+@article{b, author = {Bob Alpha and Carl Beta}}
+"#,
+            SortBibliographyOptions {
+                sort_by: BibSortKey::Author,
+                ..SortBibliographyOptions::default()
+            },
+            expect![[r#"
+                @article{b, author = {Bob Alpha and Carl Beta}}
+
+                @article{a, author = {Zeta, Anna}}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_sort_by_type() {
+        check(
+            r#"
+%! main.bib
+@misc{a}
+@Book{b}
+@article{c}
+"#,
+            SortBibliographyOptions {
+                sort_by: BibSortKey::Type,
+                ..SortBibliographyOptions::default()
+            },
+            expect![[r#"
+                @article{c}
+
+                @Book{b}
+
+                @misc{a}
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_normalize_fields() {
+        check(
+            r#"
+%! main.bib
+@article{foo, Year = 2020, TITLE = "Bar", note = {}, Author = {Baz}, abstract = {"Quoted"}}
+"#,
+            SortBibliographyOptions {
+                sort_by: BibSortKey::Key,
+                sort_fields: true,
+                lowercase_field_names: true,
+                delimiter: Some(BibDelimiter::Braces),
+                remove_empty_fields: true,
+                ..SortBibliographyOptions::default()
+            },
+            expect![[r#"
+                @article{foo,
+                    author = {Baz},
+                    title = {Bar},
+                    year = 2020,
+                    abstract = {"Quoted"},
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_quotes() {
+        check(
+            r#"
+%! main.bib
+@article{foo, title = {Bar}, abstract = {"Quoted"}}
+"#,
+            SortBibliographyOptions {
+                delimiter: Some(BibDelimiter::Quotes),
+                ..SortBibliographyOptions::default()
+            },
+            expect![[r#"
+                @article{foo,
+                    title = "Bar",
+                    abstract = {"Quoted"},
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_indent() {
+        check(
+            r#"
+%! main.bib
+@article{foo, title = {Bar}, author = {Baz}}
+"#,
+            SortBibliographyOptions {
+                sort_fields: true,
+                formatting: bibfmt::Options {
+                    insert_spaces: false,
+                    ..bibfmt::Options::default()
+                },
+                ..SortBibliographyOptions::default()
+            },
+            expect![[r#"
+                @article{foo,
+                	author = {Baz},
+                	title = {Bar},
+                }
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_tab_size() {
+        check(
+            r#"
+%! main.bib
+@article{foo, title = {Bar}}
+"#,
+            SortBibliographyOptions {
+                sort_fields: true,
+                formatting: bibfmt::Options {
+                    tab_size: 2,
+                    ..bibfmt::Options::default()
+                },
+                ..SortBibliographyOptions::default()
+            },
+            expect![[r#"
+                @article{foo,
+                  title = {Bar},
+                }
+            "#]],
+        );
+    }
+}
@@ -6,6 +6,10 @@ use once_cell::sync::Lazy;
 use rustc_hash::FxHashMap;
 use serde::Deserialize;
 
+mod signatures;
+
+pub use self::signatures::parameter_names;
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Database<'a> {
@@ -23,6 +27,9 @@ pub struct Database<'a> {
 
     #[serde(skip)]
     lookup_kernel: usize,
+
+    #[serde(skip)]
+    command_options: FxHashMap<&'a str, Vec<PackageOption<'a>>>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
@@ -39,6 +46,14 @@ pub struct Package<'a> {
 
     #[serde(borrow)]
     pub environments: Vec<&'a str>,
+
+    /// The options are not part of the generated database and are added from `options.json`.
+    #[serde(skip)]
+    pub options: Vec<PackageOption<'a>>,
+
+    /// Whether `options` contains every option which the package accepts.
+    #[serde(skip)]
+    pub exhaustive_options: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
@@ -69,6 +84,16 @@ pub struct Argument<'a> {
     pub image: Option<&'a str>,
 }
 
+/// An option of a package or a document class or a key of a key-value argument.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageOption<'a> {
+    pub name: &'a str,
+
+    #[serde(borrow)]
+    pub description: Cow<'a, str>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Metadata<'a> {
@@ -101,10 +126,97 @@ impl<'a> Database<'a> {
     pub fn kernel(&self) -> &Package {
         &self.packages[self.lookup_kernel]
     }
+
+    /// Returns the options of the given package or class (including the file extension).
+    pub fn package_options(&self, file_name: &str) -> Option<&[PackageOption<'_>]> {
+        self.find(file_name)
+            .map(|package| package.options.as_slice())
+            .filter(|options| !options.is_empty())
+    }
+
+    /// Returns the keys of the key-value argument of the given command (without the backslash).
+    pub fn command_options(&self, name: &str) -> Option<&[PackageOption<'_>]> {
+        self.command_options.get(name).map(Vec::as_slice)
+    }
+
+    /// Adds the options of `options.json` to the packages and creates the packages
+    /// which are missing from the database.
+    fn add_options(&mut self, data: OptionsData<'a>) {
+        for package in data.packages {
+            let mut missing = Vec::new();
+            for name in &package.file_names {
+                match self.lookup_packages.get(name) {
+                    Some(&index) => {
+                        self.packages[index].options = package.options.clone();
+                        self.packages[index].exhaustive_options = package.exhaustive_options;
+                    }
+                    None => missing.push(*name),
+                }
+            }
+
+            if !missing.is_empty() {
+                for name in &missing {
+                    self.lookup_packages.insert(name, self.packages.len());
+                }
+
+                self.packages.push(Package {
+                    file_names: missing,
+                    references: Vec::new(),
+                    commands: Vec::new(),
+                    environments: Vec::new(),
+                    options: package.options,
+                    exhaustive_options: package.exhaustive_options,
+                });
+            }
+        }
+
+        for command in data.commands {
+            for name in command.names {
+                self.command_options.insert(name, command.options.clone());
+            }
+        }
+    }
+}
+
+/// The options of packages and classes and the keys of key-value arguments,
+/// which are maintained by hand.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OptionsData<'a> {
+    #[serde(borrow)]
+    packages: Vec<PackageOptions<'a>>,
+
+    #[serde(borrow)]
+    commands: Vec<CommandOptions<'a>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PackageOptions<'a> {
+    #[serde(borrow)]
+    file_names: Vec<&'a str>,
+
+    #[serde(default)]
+    exhaustive_options: bool,
+
+    #[serde(borrow)]
+    options: Vec<PackageOption<'a>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommandOptions<'a> {
+    #[serde(borrow)]
+    names: Vec<&'a str>,
+
+    #[serde(borrow)]
+    options: Vec<PackageOption<'a>>,
 }
 
 const JSON_GZ: &[u8] = include_bytes!("../data/completion.json.gz");
 
+const OPTIONS_JSON: &str = include_str!("options.json");
+
 pub static DATABASE: Lazy<Database<'static>> = Lazy::new(|| {
     let mut decoder = GzDecoder::new(JSON_GZ);
     let json = Box::leak(Box::default());
@@ -131,5 +243,6 @@ pub static DATABASE: Lazy<Database<'static>> = Lazy::new(|| {
         .position(|package| package.file_names.is_empty())
         .unwrap();
 
+    db.add_options(serde_json::from_str(OPTIONS_JSON).unwrap());
     db
 });
@@ -0,0 +1,37 @@
+/// The names of the mandatory arguments of common commands.
+/// The bundled database does not contain the names of the parameters,
+/// so they are maintained here for the commands which are used the most.
+const SIGNATURES: &[(&str, &[&str])] = &[
+    ("frac", &["numerator", "denominator"]),
+    ("dfrac", &["numerator", "denominator"]),
+    ("tfrac", &["numerator", "denominator"]),
+    ("binom", &["n", "k"]),
+    ("sqrt", &["radicand"]),
+    ("stackrel", &["top", "base"]),
+    ("overset", &["top", "base"]),
+    ("underset", &["bottom", "base"]),
+    ("setlength", &["length", "value"]),
+    ("addtolength", &["length", "value"]),
+    ("setcounter", &["counter", "value"]),
+    ("addtocounter", &["counter", "value"]),
+    ("newcounter", &["counter"]),
+    ("hspace", &["length"]),
+    ("vspace", &["length"]),
+    ("href", &["url", "text"]),
+    ("multicolumn", &["columns", "alignment", "text"]),
+    ("multirow", &["rows", "width", "text"]),
+    ("rule", &["width", "height"]),
+    ("raisebox", &["distance", "text"]),
+    ("parbox", &["width", "text"]),
+    ("rotatebox", &["angle", "text"]),
+    ("scalebox", &["factor", "text"]),
+    ("resizebox", &["width", "height", "text"]),
+];
+
+/// Returns the names of the mandatory arguments of the given command (without the backslash).
+pub fn parameter_names(command: &str) -> Option<&'static [&'static str]> {
+    SIGNATURES
+        .iter()
+        .find(|(name, _)| *name == command)
+        .map(|(_, parameters)| *parameters)
+}
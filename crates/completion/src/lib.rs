@@ -60,6 +60,7 @@ pub enum CompletionItemData<'a> {
     EntryType(EntryTypeData<'a>),
     Field(FieldTypeData<'a>),
     TikzLibrary(&'a str),
+    PackageOption(PackageOptionData),
 }
 
 impl<'a> CompletionItemData<'a> {
@@ -81,6 +82,7 @@ impl<'a> CompletionItemData<'a> {
             Self::EntryType(data) => data.0.name,
             Self::Field(data) => data.0.name,
             Self::TikzLibrary(name) => name,
+            Self::PackageOption(data) => data.0.name,
         }
     }
 }
@@ -126,10 +128,23 @@ impl<'a> std::fmt::Debug for ArgumentData<'a> {
     }
 }
 
+#[derive(PartialEq, Eq)]
+pub struct PackageOptionData(pub &'static completion_data::PackageOption<'static>);
+
+impl std::fmt::Debug for PackageOptionData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("PackageOptionData")
+            .field(&self.0.name)
+            .finish()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct CitationData<'a> {
     pub document: &'a Document,
     pub entry: &'a bib::Entry,
+    /// The text that clients should use to filter the item if the query contains field filters.
+    pub filter_text: Option<&'a str>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -180,6 +195,7 @@ pub fn complete<'a>(params: &'a CompletionParams<'a>) -> CompletionResult<'a> {
     providers::complete_entry_types(params, &mut builder);
     providers::complete_fields(params, &mut builder);
     providers::complete_tikz_libraries(params, &mut builder);
+    providers::complete_options(params, &mut builder);
     builder.finish()
 }
 
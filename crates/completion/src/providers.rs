@@ -10,6 +10,7 @@ mod glossary;
 mod import;
 mod include;
 mod label;
+mod option;
 mod tikz_library;
 
 pub use argument::complete_arguments;
@@ -24,4 +25,5 @@ pub use glossary::{complete_acronyms, complete_glossaries};
 pub use import::complete_imports;
 pub use include::complete_includes;
 pub use label::complete_labels;
+pub use option::complete_options;
 pub use tikz_library::complete_tikz_libraries;
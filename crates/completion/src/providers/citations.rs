@@ -1,6 +1,10 @@
-use base_db::semantics::Span;
+use base_db::{
+    semantics::{bib, Span},
+    CitationRanking,
+};
 use rayon::prelude::{IntoParallelRefIterator, ParallelExtend, ParallelIterator};
 use rowan::ast::AstNode;
+use rustc_hash::FxHashMap;
 use syntax::latex;
 
 use crate::{
@@ -13,12 +17,46 @@ pub fn complete_citations<'a>(
     builder: &mut CompletionBuilder<'a>,
 ) -> Option<()> {
     let cursor = find_citation(params)?;
+    let query_text = &params.feature.document.text[cursor.range];
+    let query = CitationQuery::parse(query_text);
+    let filter_text = (!query.filters.is_empty()).then_some(query_text);
+
+    let ranking = params
+        .feature
+        .workspace
+        .config()
+        .completion
+        .citation_ranking;
+
+    let usage = if ranking == CitationRanking::Usage {
+        count_citations(params)
+    } else {
+        FxHashMap::default()
+    };
 
     for document in &params.feature.project.documents {
         if let Some(data) = document.data.as_bib() {
             let items = data.semantics.entries.par_iter().filter_map(|entry| {
-                let score = builder.matcher.score(&entry.name.text, &cursor.text)?;
-                let data = CompletionItemData::Citation(CitationData { document, entry });
+                let relevance = if filter_text.is_none() {
+                    builder.matcher.score(&entry.name.text, &cursor.text)?
+                } else {
+                    query.matches(entry).then_some(0)?
+                };
+
+                let score = match ranking {
+                    CitationRanking::Relevance => relevance,
+                    CitationRanking::Recency => entry.year.unwrap_or(0),
+                    CitationRanking::Usage => {
+                        usage.get(entry.name.text.as_str()).copied().unwrap_or(0) as i32
+                    }
+                };
+
+                let data = CompletionItemData::Citation(CitationData {
+                    document,
+                    entry,
+                    filter_text,
+                });
+
                 Some(CompletionItem::new_simple(score, cursor.range, data))
             });
 
@@ -29,6 +67,91 @@ pub fn complete_citations<'a>(
     Some(())
 }
 
+/// A citation query which consists of plain search terms and
+/// field filters like `author:smith`, `year:2020` or `type:article`.
+#[derive(Debug, Default)]
+struct CitationQuery {
+    terms: Vec<String>,
+    filters: Vec<(String, String)>,
+}
+
+impl CitationQuery {
+    fn parse(text: &str) -> Self {
+        let mut query = Self::default();
+        for word in text.split_whitespace() {
+            match word.split_once(':') {
+                Some((name, value)) if !value.is_empty() && is_filter_name(name) => {
+                    query
+                        .filters
+                        .push((name.to_lowercase(), value.to_lowercase()));
+                }
+                _ => query.terms.push(word.to_lowercase()),
+            }
+        }
+
+        query
+    }
+
+    fn matches(&self, entry: &bib::Entry) -> bool {
+        let keywords = entry.keywords.to_lowercase();
+        self.terms
+            .iter()
+            .all(|term| keywords.contains(term.as_str()))
+            && self
+                .filters
+                .iter()
+                .all(|(name, value)| matches_filter(entry, name, value))
+    }
+}
+
+/// Only the listed names are filters, so keys like `DBLP:conf/foo` or `doi:10.1000/bar`
+/// are still matched against the entry names.
+const FILTER_NAMES: &[&str] = &["author", "year", "type", "key", "title", "journal"];
+
+fn is_filter_name(name: &str) -> bool {
+    FILTER_NAMES
+        .iter()
+        .any(|filter| filter.eq_ignore_ascii_case(name))
+}
+
+fn matches_filter(entry: &bib::Entry, name: &str, value: &str) -> bool {
+    match name {
+        "type" => entry.entry_type.starts_with(value),
+        "key" => entry.name.text.to_lowercase().contains(value),
+        "year" => entry.year.is_some_and(|year| matches_year(year, value)),
+        _ => entry
+            .field(name)
+            .is_some_and(|text| text.to_lowercase().contains(value)),
+    }
+}
+
+/// Matches a year against a prefix (`year:20`) or an inclusive range (`year:2018-2020`).
+fn matches_year(year: i32, value: &str) -> bool {
+    match value.split_once('-') {
+        Some((start, end)) => {
+            let start = start.parse().unwrap_or(i32::MIN);
+            let end = end.parse().unwrap_or(i32::MAX);
+            (start..=end).contains(&year)
+        }
+        None => year.to_string().starts_with(value),
+    }
+}
+
+fn count_citations<'a>(params: &'a CompletionParams) -> FxHashMap<&'a str, usize> {
+    let mut usage = FxHashMap::default();
+    for document in &params.feature.project.documents {
+        let Some(data) = document.data.as_tex() else {
+            continue;
+        };
+
+        for citation in &data.semantics.citations {
+            *usage.entry(citation.name.text.as_str()).or_default() += 1;
+        }
+    }
+
+    usage
+}
+
 fn find_citation(params: &CompletionParams) -> Option<Span> {
     find_citation_command(params).or_else(|| find_citation_acronym(params))
 }
@@ -0,0 +1,68 @@
+use base_db::{semantics::Span, util::find_options};
+use rowan::TokenAtOffset;
+use syntax::latex;
+
+use crate::{
+    util::CompletionBuilder, CompletionItem, CompletionItemData, CompletionParams,
+    PackageOptionData,
+};
+
+pub fn complete_options<'a>(
+    params: &'a CompletionParams<'a>,
+    builder: &mut CompletionBuilder<'a>,
+) -> Option<()> {
+    let (cursor, group) = find_key(params)?;
+
+    for option in find_options(&group)? {
+        if let Some(score) = builder.matcher.score(option.name, &cursor.text) {
+            let data = CompletionItemData::PackageOption(PackageOptionData(option));
+            builder
+                .items
+                .push(CompletionItem::new_simple(score, cursor.range, data));
+        }
+    }
+
+    Some(())
+}
+
+/// Finds the key of a key-value argument which is written at the cursor position.
+fn find_key(params: &CompletionParams) -> Option<(Span, latex::SyntaxNode)> {
+    let data = params.feature.document.data.as_tex()?;
+    let tokens = data.root_node().token_at_offset(params.offset);
+
+    let (span, token) = match tokens.clone().find(|token| token.kind() == latex::WORD) {
+        Some(token) => (Span::from(&token), token),
+        None if matches!(tokens, TokenAtOffset::Between(_, _)) => {
+            (Span::empty(params.offset), tokens.left_biased()?)
+        }
+        None => return None,
+    };
+
+    // Keys follow the opening bracket or a comma, values follow an equality sign.
+    let previous = if token.kind() == latex::WORD {
+        token.prev_token()
+    } else {
+        Some(token.clone())
+    };
+
+    std::iter::successors(previous, |token| token.prev_token())
+        .find(|token| !matches!(token.kind(), latex::WHITESPACE | latex::LINE_BREAK))
+        .filter(|token| matches!(token.kind(), latex::L_BRACK | latex::L_CURLY | latex::COMMA))?;
+
+    let group = token.parent_ancestors().find(|node| {
+        matches!(
+            node.kind(),
+            latex::BRACK_GROUP_KEY_VALUE | latex::CURLY_GROUP
+        )
+    })?;
+
+    let right_delim = group
+        .last_token()
+        .filter(|token| matches!(token.kind(), latex::R_BRACK | latex::R_CURLY));
+
+    if right_delim.is_some_and(|delim| delim.text_range().start() < params.offset) {
+        return None;
+    }
+
+    Some((span, group))
+}
@@ -368,7 +368,16 @@ fn citation() {
                             full_range: 88..107,
                             keywords: "bar:2005 @article",
                             category: Article,
+                            entry_type: "article",
+                            fields: [],
+                            year: None,
+                            identity: EntryIdentity {
+                                doi: None,
+                                eprint: None,
+                                title_author_year: None,
+                            },
                         },
+                        filter_text: None,
                     },
                 ),
                 Citation(
@@ -384,7 +393,33 @@ fn citation() {
                             full_range: 0..86,
                             keywords: "foo:2019 @article Foo Bar Baz Qux 2019",
                             category: Article,
+                            entry_type: "article",
+                            fields: [
+                                (
+                                    "author",
+                                    "Foo Bar",
+                                ),
+                                (
+                                    "title",
+                                    "Baz Qux",
+                                ),
+                                (
+                                    "year",
+                                    "2019",
+                                ),
+                            ],
+                            year: Some(
+                                2019,
+                            ),
+                            identity: EntryIdentity {
+                                doi: None,
+                                eprint: None,
+                                title_author_year: Some(
+                                    "baz qux|bar|2019",
+                                ),
+                            },
                         },
+                        filter_text: None,
                     },
                 ),
             ]
@@ -418,7 +453,16 @@ fn citation_open_brace() {
                             full_range: 0..14,
                             keywords: "foo @article",
                             category: Article,
+                            entry_type: "article",
+                            fields: [],
+                            year: None,
+                            identity: EntryIdentity {
+                                doi: None,
+                                eprint: None,
+                                title_author_year: None,
+                            },
                         },
+                        filter_text: None,
                     },
                 ),
             ]
@@ -453,7 +497,16 @@ fn citation_open_brace_multiple() {
                             full_range: 0..14,
                             keywords: "foo @article",
                             category: Article,
+                            entry_type: "article",
+                            fields: [],
+                            year: None,
+                            identity: EntryIdentity {
+                                doi: None,
+                                eprint: None,
+                                title_author_year: None,
+                            },
                         },
+                        filter_text: None,
                     },
                 ),
             ]
@@ -487,7 +540,16 @@ fn citation_acronym() {
                             full_range: 0..14,
                             keywords: "foo @article",
                             category: Article,
+                            entry_type: "article",
+                            fields: [],
+                            year: None,
+                            identity: EntryIdentity {
+                                doi: None,
+                                eprint: None,
+                                title_author_year: None,
+                            },
                         },
+                        filter_text: None,
                     },
                 ),
             ]
@@ -515,6 +577,187 @@ fn citation_after_brace() {
     );
 }
 
+#[test]
+fn citation_field_filter() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\bibliography{main}
+\begin{document}
+\cite{author:bar year:2019}
+                          |
+      ^^^^^^^^^^^^^^^^^^^^
+\end{document}
+
+%! main.bib
+@article{foo, author = {Foo Bar}, year = {2019}}
+@book{bar, author = {Foo Bar}, year = {2005}}
+@article{baz, author = {Baz Qux}, year = {2019}}"#,
+        expect![[r#"
+            [
+                Citation(
+                    CitationData {
+                        document: Document(
+                            "file:///texlab/main.bib",
+                        ),
+                        entry: Entry {
+                            name: Span(
+                                "foo",
+                                9..12,
+                            ),
+                            full_range: 0..48,
+                            keywords: "foo @article Foo Bar 2019",
+                            category: Article,
+                            entry_type: "article",
+                            fields: [
+                                (
+                                    "author",
+                                    "Foo Bar",
+                                ),
+                                (
+                                    "year",
+                                    "2019",
+                                ),
+                            ],
+                            year: Some(
+                                2019,
+                            ),
+                            identity: EntryIdentity {
+                                doi: None,
+                                eprint: None,
+                                title_author_year: None,
+                            },
+                        },
+                        filter_text: Some(
+                            "author:bar year:2019",
+                        ),
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn citation_key_with_colon() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\bibliography{main}
+\begin{document}
+\cite{DBLP:co}
+             |
+      ^^^^^^^
+\end{document}
+
+%! main.bib
+@inproceedings{DBLP:conf/foo, author = {Foo Bar}, year = {2019}}"#,
+        expect![[r#"
+            [
+                Citation(
+                    CitationData {
+                        document: Document(
+                            "file:///texlab/main.bib",
+                        ),
+                        entry: Entry {
+                            name: Span(
+                                "DBLP:conf/foo",
+                                15..28,
+                            ),
+                            full_range: 0..64,
+                            keywords: "DBLP:conf/foo @inproceedings Foo Bar 2019",
+                            category: Part,
+                            entry_type: "inproceedings",
+                            fields: [
+                                (
+                                    "author",
+                                    "Foo Bar",
+                                ),
+                                (
+                                    "year",
+                                    "2019",
+                                ),
+                            ],
+                            year: Some(
+                                2019,
+                            ),
+                            identity: EntryIdentity {
+                                doi: None,
+                                eprint: None,
+                                title_author_year: None,
+                            },
+                        },
+                        filter_text: None,
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn citation_type_filter() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\bibliography{main}
+\begin{document}
+\cite{type:book}
+               |
+      ^^^^^^^^^
+\end{document}
+
+%! main.bib
+@article{foo, author = {Foo Bar}, year = {2019}}
+@book{bar, author = {Foo Bar}, year = {2005}}"#,
+        expect![[r#"
+            [
+                Citation(
+                    CitationData {
+                        document: Document(
+                            "file:///texlab/main.bib",
+                        ),
+                        entry: Entry {
+                            name: Span(
+                                "bar",
+                                55..58,
+                            ),
+                            full_range: 49..94,
+                            keywords: "bar @book Foo Bar 2005",
+                            category: Book,
+                            entry_type: "book",
+                            fields: [
+                                (
+                                    "author",
+                                    "Foo Bar",
+                                ),
+                                (
+                                    "year",
+                                    "2005",
+                                ),
+                            ],
+                            year: Some(
+                                2005,
+                            ),
+                            identity: EntryIdentity {
+                                doi: None,
+                                eprint: None,
+                                title_author_year: None,
+                            },
+                        },
+                        filter_text: Some(
+                            "type:book",
+                        ),
+                    },
+                ),
+            ]
+        "#]],
+    );
+}
+
 #[test]
 fn color_model_definition_simple() {
     check(
@@ -1715,6 +1958,168 @@ fn tikz_library_closed_brace() {
     );
 }
 
+#[test]
+fn package_option_empty() {
+    check(
+        r#"
+%! main.tex
+\usepackage[]{geometry}
+            |"#,
+        expect![[r#"
+            [
+                PackageOption(
+                    PackageOptionData(
+                        "a3paper",
+                    ),
+                ),
+                PackageOption(
+                    PackageOptionData(
+                        "a4paper",
+                    ),
+                ),
+                PackageOption(
+                    PackageOptionData(
+                        "a5paper",
+                    ),
+                ),
+                PackageOption(
+                    PackageOptionData(
+                        "asymmetric",
+                    ),
+                ),
+                PackageOption(
+                    PackageOptionData(
+                        "b5paper",
+                    ),
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn package_option_word() {
+    check(
+        r#"
+%! main.tex
+\usepackage[a4paper, marg]{geometry}
+                        |
+                     ^^^^"#,
+        expect![[r#"
+            [
+                PackageOption(
+                    PackageOptionData(
+                        "margin",
+                    ),
+                ),
+                PackageOption(
+                    PackageOptionData(
+                        "marginparsep",
+                    ),
+                ),
+                PackageOption(
+                    PackageOptionData(
+                        "marginparwidth",
+                    ),
+                ),
+                PackageOption(
+                    PackageOptionData(
+                        "bmargin",
+                    ),
+                ),
+                PackageOption(
+                    PackageOptionData(
+                        "hmargin",
+                    ),
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn package_option_value() {
+    check(
+        r#"
+%! main.tex
+\usepackage[margin=1in]{geometry}
+                    |"#,
+        expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn class_option_after_comma() {
+    check(
+        r#"
+%! main.tex
+\documentclass[12pt,a4]{article}
+                     |
+                    ^^"#,
+        expect![[r#"
+            [
+                PackageOption(
+                    PackageOptionData(
+                        "a4paper",
+                    ),
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn command_option_hypersetup() {
+    check(
+        r#"
+%! main.tex
+\hypersetup{colorlinks, urlc}
+                           |
+                        ^^^^"#,
+        expect![[r#"
+            [
+                PackageOption(
+                    PackageOptionData(
+                        "urlcolor",
+                    ),
+                ),
+                PackageOption(
+                    PackageOptionData(
+                        "urlbordercolor",
+                    ),
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn command_option_includegraphics() {
+    check(
+        r#"
+%! main.tex
+\includegraphics[wid]{foo}
+                   |
+                 ^^^"#,
+        expect![[r#"
+            [
+                PackageOption(
+                    PackageOptionData(
+                        "width",
+                    ),
+                ),
+                PackageOption(
+                    PackageOptionData(
+                        "natwidth",
+                    ),
+                ),
+            ]
+        "#]],
+    );
+}
+
 #[test]
 fn test_user_command() {
     check(
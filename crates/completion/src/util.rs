@@ -1,5 +1,4 @@
 mod builder;
-pub mod matchers;
 mod patterns;
 
 pub use builder::*;
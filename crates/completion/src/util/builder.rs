@@ -1,10 +1,10 @@
-use base_db::{MatchingAlgo, Workspace};
-use fuzzy_matcher::skim::SkimMatcherV2;
+use base_db::{
+    util::matchers::{self, Matcher},
+    Workspace,
+};
 
 use crate::{CompletionItem, CompletionResult};
 
-use super::matchers::{self, Matcher};
-
 pub struct CompletionBuilder<'a> {
     pub matcher: Box<dyn Matcher>,
     pub items: Vec<CompletionItem<'a>>,
@@ -12,12 +12,7 @@ pub struct CompletionBuilder<'a> {
 
 impl<'a> From<&Workspace> for CompletionBuilder<'a> {
     fn from(workspace: &Workspace) -> Self {
-        let matcher: Box<dyn Matcher> = match workspace.config().completion.matcher {
-            MatchingAlgo::Skim => Box::<SkimMatcherV2>::default(),
-            MatchingAlgo::SkimIgnoreCase => Box::new(SkimMatcherV2::default().ignore_case()),
-            MatchingAlgo::Prefix => Box::new(matchers::Prefix),
-            MatchingAlgo::PrefixIgnoreCase => Box::new(matchers::PrefixIgnoreCase),
-        };
+        let matcher = matchers::create(&workspace.config().completion.matcher);
 
         Self {
             matcher,
@@ -1,4 +1,6 @@
-use base_db::{Document, Workspace};
+use std::path::Path;
+
+use base_db::{graph::Graph, Document, Workspace};
 use line_index::LineCol;
 use multimap::MultiMap;
 use rowan::{TextLen, TextRange, TextSize};
@@ -13,25 +15,43 @@ pub fn update(
     log_document: &Document,
     results: &mut FxHashMap<Url, MultiMap<Url, Diagnostic>>,
 ) -> Option<()> {
-    let mut errors = MultiMap::default();
-
     let data = log_document.data.as_log()?;
 
     let parents = workspace.parents(log_document);
     let root_document = parents.iter().next()?;
 
+    let errors = map_errors(workspace, root_document, &data.errors)?;
+    results.insert(log_document.uri.clone(), errors);
+    Some(())
+}
+
+/// Resolves the locations of build errors relative to the root document of the build.
+pub fn map_errors(
+    workspace: &Workspace,
+    root_document: &Document,
+    build_errors: &[BuildError],
+) -> Option<MultiMap<Url, Diagnostic>> {
+    let mut errors = MultiMap::default();
+
     let base_path = root_document
         .path
         .as_deref()
         .and_then(|path| path.parent())?;
 
-    for error in &data.errors {
+    let project: Vec<_> = Graph::new(workspace, root_document).preorder().collect();
+
+    for error in build_errors {
         let full_path = base_path.join(&error.relative_path);
         let Ok(full_path_uri) = Url::from_file_path(&full_path) else {
             continue;
         };
 
-        let tex_document = workspace.lookup(&full_path_uri).unwrap_or(root_document);
+        // Some tools (e.g. biber) only report the file name of a document,
+        // which is not necessarily located next to the root document.
+        let tex_document = workspace
+            .lookup(&full_path_uri)
+            .or_else(|| find_by_relative_path(&project, &error.relative_path))
+            .unwrap_or(root_document);
 
         let range = find_range_of_hint(tex_document, error).unwrap_or_else(|| {
             let line = error.line.unwrap_or(0);
@@ -47,8 +67,23 @@ pub fn update(
         errors.insert(tex_document.uri.clone(), diagnostic);
     }
 
-    results.insert(log_document.uri.clone(), errors);
-    Some(())
+    Some(errors)
+}
+
+fn find_by_relative_path<'a>(
+    project: &[&'a Document],
+    relative_path: &Path,
+) -> Option<&'a Document> {
+    if relative_path.as_os_str().is_empty() {
+        return None;
+    }
+
+    project.iter().copied().find(|document| {
+        document
+            .path
+            .as_deref()
+            .is_some_and(|path| path.ends_with(relative_path))
+    })
 }
 
 fn find_range_of_hint(document: &Document, error: &BuildError) -> Option<TextRange> {
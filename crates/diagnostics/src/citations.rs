@@ -3,8 +3,9 @@ use base_db::{
     util::queries::{self, Object},
     Document, Project, Workspace,
 };
+use itertools::Itertools;
 use multimap::MultiMap;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use url::Url;
 
 use crate::types::{BibError, Diagnostic, TexError};
@@ -74,3 +75,67 @@ pub fn detect_duplicate_entries<'a>(
         results.insert(conflict.main.document.uri.clone(), diagnostic);
     }
 }
+
+/// Detects entries which describe the same work under different keys.
+/// Entries are considered equal if they share a DOI, an arXiv identifier
+/// or the normalized title together with the first author and the year.
+pub fn detect_duplicate_entry_contents(
+    workspace: &Workspace,
+    results: &mut MultiMap<Url, Diagnostic>,
+) {
+    let entries: Vec<(&Document, &Entry)> = workspace
+        .iter()
+        .flat_map(|document| Entry::find(document).map(move |entry| (document, entry)))
+        .collect();
+
+    let mut groups: FxHashMap<(u8, &str), Vec<usize>> = FxHashMap::default();
+    for (i, (_, entry)) in entries.iter().enumerate() {
+        let identity = &entry.identity;
+        let keys = [
+            (0, identity.doi.as_deref()),
+            (1, identity.eprint.as_deref()),
+            (2, identity.title_author_year.as_deref()),
+        ];
+
+        for (kind, key) in keys {
+            if let Some(key) = key {
+                groups.entry((kind, key)).or_default().push(i);
+            }
+        }
+    }
+
+    let mut duplicates: FxHashMap<usize, FxHashSet<usize>> = FxHashMap::default();
+    for group in groups.into_values().filter(|group| group.len() > 1) {
+        for &i in &group {
+            duplicates
+                .entry(i)
+                .or_default()
+                .extend(group.iter().copied().filter(|j| *j != i));
+        }
+    }
+
+    let mut projects: FxHashMap<&Url, Project> = FxHashMap::default();
+    for (i, others) in duplicates.into_iter().sorted_by_key(|(i, _)| *i) {
+        let (document, entry) = entries[i];
+        let project = projects
+            .entry(&document.uri)
+            .or_insert_with(|| workspace.project(document));
+
+        let others: Vec<_> = others
+            .into_iter()
+            .map(|j| entries[j])
+            .filter(|(other_document, _)| project.documents.contains(other_document))
+            .filter(|(_, other)| other.name.text != entry.name.text)
+            .sorted_by_key(|(other_document, other)| {
+                (&other_document.uri, other.name.range.start())
+            })
+            .map(|(other_document, other)| (other_document.uri.clone(), other.name.range))
+            .collect();
+
+        if !others.is_empty() {
+            let diagnostic =
+                Diagnostic::Bib(entry.name.range, BibError::DuplicateEntryContent(others));
+            results.insert(document.uri.clone(), diagnostic);
+        }
+    }
+}
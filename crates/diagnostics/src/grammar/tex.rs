@@ -1,7 +1,8 @@
 use base_db::{Config, Document, TexDocumentData};
+use completion_data::DATABASE;
 use multimap::MultiMap;
 use rowan::{ast::AstNode, NodeOrToken, TextRange};
-use syntax::latex;
+use syntax::latex::{self, HasKeyValueBody};
 use url::Url;
 
 use crate::types::{Diagnostic, TexError};
@@ -59,7 +60,8 @@ impl<'a> Analyzer<'a> {
 
                     self.analyze_environment(node.clone())
                         .or_else(|| self.analyze_curly_group(node.clone()))
-                        .or_else(|| self.analyze_curly_braces(node));
+                        .or_else(|| self.analyze_curly_braces(node.clone()))
+                        .or_else(|| self.analyze_package_options(node));
                 }
                 rowan::WalkEvent::Leave(_) => {
                     continue;
@@ -120,4 +122,44 @@ impl<'a> Analyzer<'a> {
             None
         }
     }
+
+    /// Class options are not checked because they are passed to the packages as global options.
+    /// Packages are only checked if the database knows all of their options.
+    fn analyze_package_options(&mut self, node: latex::SyntaxNode) -> Option<()> {
+        if node.kind() != latex::PACKAGE_INCLUDE {
+            return None;
+        }
+
+        let include = latex::Include::cast(node)?;
+        let packages = include
+            .path_list()?
+            .keys()
+            .map(|key| DATABASE.find(&format!("{}.sty", key.to_string())))
+            .collect::<Option<Vec<_>>>()?;
+
+        if packages.is_empty() || !packages.iter().all(|package| package.exhaustive_options) {
+            return None;
+        }
+
+        let options = include
+            .syntax()
+            .children()
+            .find_map(latex::BrackGroupKeyValue::cast)
+            .and_then(|group| group.body())?;
+
+        for key in options.pairs().filter_map(|pair| pair.key()) {
+            let name = key.to_string();
+            if packages
+                .iter()
+                .all(|package| package.options.iter().all(|option| option.name != name))
+            {
+                self.diagnostics.push(Diagnostic::Tex(
+                    latex::small_range(&key),
+                    TexError::UnknownOption,
+                ));
+            }
+        }
+
+        Some(())
+    }
 }
@@ -1,6 +1,8 @@
-use base_db::{util::filter_regex_patterns, Document, Owner, Workspace};
+use base_db::{graph::Graph, util::filter_regex_patterns, Document, Owner, Workspace};
+use distro::Language;
 use multimap::MultiMap;
 use rustc_hash::FxHashMap;
+use syntax::BuildLog;
 use url::Url;
 
 use crate::types::Diagnostic;
@@ -11,6 +13,7 @@ pub struct Manager {
     grammar: MultiMap<Url, Diagnostic>,
     chktex: FxHashMap<Url, Vec<Diagnostic>>,
     build_log: FxHashMap<Url, MultiMap<Url, Diagnostic>>,
+    build_output: FxHashMap<Url, MultiMap<Url, Diagnostic>>,
 }
 
 impl Manager {
@@ -26,6 +29,31 @@ impl Manager {
 
         self.build_log.remove(&document.uri);
         super::build_log::update(workspace, document, &mut self.build_log);
+
+        // The log file supersedes the diagnostics extracted from the compiler output.
+        if document.language == Language::Log {
+            for parent in workspace.parents(document) {
+                self.build_output.remove(&parent.uri);
+            }
+        }
+    }
+
+    /// Updates the diagnostics extracted from the output of a running build.
+    pub fn update_build_output(&mut self, workspace: &Workspace, root: &Url, log: &BuildLog) {
+        let Some(root_document) = workspace.lookup(root) else {
+            return;
+        };
+
+        // Until the build finishes, the previous log file is outdated.
+        for document in Graph::new(workspace, root_document).preorder() {
+            if document.language == Language::Log {
+                self.build_log.remove(&document.uri);
+            }
+        }
+
+        let errors = super::build_log::map_errors(workspace, root_document, &log.errors);
+        self.build_output
+            .insert(root.clone(), errors.unwrap_or_default());
     }
 
     /// Updates the ChkTeX diagnostics for the given document.
@@ -40,7 +68,12 @@ impl Manager {
             results.insert_many_from_slice(uri.clone(), diagnostics);
         }
 
-        for (uri, diagnostics) in self.build_log.values().flatten() {
+        for (uri, diagnostics) in self
+            .build_log
+            .values()
+            .chain(self.build_output.values())
+            .flatten()
+        {
             results.insert_many_from_slice(uri.clone(), diagnostics);
         }
 
@@ -63,6 +96,7 @@ impl Manager {
         }
 
         super::citations::detect_duplicate_entries(workspace, &mut results);
+        super::citations::detect_duplicate_entry_contents(workspace, &mut results);
         super::labels::detect_duplicate_labels(workspace, &mut results);
         super::labels::detect_undefined_and_unused_labels(workspace, &mut results);
 
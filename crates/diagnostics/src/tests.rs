@@ -210,6 +210,34 @@ fn test_tex_environment_mismatched() {
     )
 }
 
+#[test]
+fn test_tex_unknown_package_option() {
+    check(
+        r#"
+%! main.tex
+\usepackage[fleqn, foo, alignedleftspaceyes]{amsmath}
+                   ^^^
+\usepackage[foo]{geometry}
+\usepackage[pdfpagelabels]{hyperref}
+\usepackage[foo]{amsmath, bar}
+\documentclass[bar]{article}
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.tex",
+                    [
+                        Tex(
+                            19..22,
+                            UnknownOption,
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    )
+}
+
 #[test]
 fn test_label_unused() {
     check(
@@ -306,3 +334,302 @@ fn test_citation_unused() {
         "#]],
     )
 }
+
+#[test]
+fn test_duplicate_entry_content_doi() {
+    check(
+        r#"
+%! main.bib
+@article{foo, doi = {10.1000/XYZ}}
+@article{bar, doi = {https://doi.org/10.1000/xyz}}
+
+%! main.tex
+\bibliography{main}
+\cite{foo, bar}
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.bib",
+                    [
+                        Bib(
+                            9..12,
+                            DuplicateEntryContent(
+                                [
+                                    (
+                                        Url {
+                                            scheme: "file",
+                                            cannot_be_a_base: false,
+                                            username: "",
+                                            password: None,
+                                            host: None,
+                                            port: None,
+                                            path: "/texlab/main.bib",
+                                            query: None,
+                                            fragment: None,
+                                        },
+                                        44..47,
+                                    ),
+                                ],
+                            ),
+                        ),
+                        Bib(
+                            44..47,
+                            DuplicateEntryContent(
+                                [
+                                    (
+                                        Url {
+                                            scheme: "file",
+                                            cannot_be_a_base: false,
+                                            username: "",
+                                            password: None,
+                                            host: None,
+                                            port: None,
+                                            path: "/texlab/main.bib",
+                                            query: None,
+                                            fragment: None,
+                                        },
+                                        9..12,
+                                    ),
+                                ],
+                            ),
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    )
+}
+
+#[test]
+fn test_duplicate_entry_content_title() {
+    check(
+        r#"
+%! main.bib
+@article{foo, author = {Smith, John}, title = {A {Great} Paper}, year = {2020}}
+@article{bar, author = {John Smith and Jane Doe}, title = {A great paper.}, year = 2020}
+@article{baz, author = {John Smith}, title = {A great paper}, year = 2021}
+
+%! main.tex
+\bibliography{main}
+\cite{foo, bar, baz}
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.bib",
+                    [
+                        Bib(
+                            9..12,
+                            DuplicateEntryContent(
+                                [
+                                    (
+                                        Url {
+                                            scheme: "file",
+                                            cannot_be_a_base: false,
+                                            username: "",
+                                            password: None,
+                                            host: None,
+                                            port: None,
+                                            path: "/texlab/main.bib",
+                                            query: None,
+                                            fragment: None,
+                                        },
+                                        89..92,
+                                    ),
+                                ],
+                            ),
+                        ),
+                        Bib(
+                            89..92,
+                            DuplicateEntryContent(
+                                [
+                                    (
+                                        Url {
+                                            scheme: "file",
+                                            cannot_be_a_base: false,
+                                            username: "",
+                                            password: None,
+                                            host: None,
+                                            port: None,
+                                            path: "/texlab/main.bib",
+                                            query: None,
+                                            fragment: None,
+                                        },
+                                        9..12,
+                                    ),
+                                ],
+                            ),
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    )
+}
+
+#[test]
+fn test_duplicate_entry_content_arxiv() {
+    check(
+        r#"
+%! main.bib
+@misc{foo, eprint = {2101.00001v2}, archivePrefix = {arXiv}}
+
+%! other.bib
+@misc{bar, eprint = {arXiv:2101.00001}}
+
+%! main.tex
+\bibliography{main}
+\bibliography{other}
+\cite{foo, bar}
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.bib",
+                    [
+                        Bib(
+                            6..9,
+                            DuplicateEntryContent(
+                                [
+                                    (
+                                        Url {
+                                            scheme: "file",
+                                            cannot_be_a_base: false,
+                                            username: "",
+                                            password: None,
+                                            host: None,
+                                            port: None,
+                                            path: "/texlab/other.bib",
+                                            query: None,
+                                            fragment: None,
+                                        },
+                                        6..9,
+                                    ),
+                                ],
+                            ),
+                        ),
+                    ],
+                ),
+                (
+                    "file:///texlab/other.bib",
+                    [
+                        Bib(
+                            6..9,
+                            DuplicateEntryContent(
+                                [
+                                    (
+                                        Url {
+                                            scheme: "file",
+                                            cannot_be_a_base: false,
+                                            username: "",
+                                            password: None,
+                                            host: None,
+                                            port: None,
+                                            path: "/texlab/main.bib",
+                                            query: None,
+                                            fragment: None,
+                                        },
+                                        6..9,
+                                    ),
+                                ],
+                            ),
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    )
+}
+
+#[test]
+fn test_bibtex_log() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\begin{document}
+\cite{foo}
+\bibliography{refs}
+\end{document}
+
+%! refs.bib
+@article{foo,
+    title = {Foo}
+    author = {Bar},
+}
+
+%! main.blg
+This is BibTeX, Version 0.99d (TeX Live 2023)
+I was expecting a `,' or a `}'---line 3 of file refs.bib
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/refs.bib",
+                    [
+                        Build(
+                            32..32,
+                            BuildError {
+                                relative_path: "refs.bib",
+                                level: Error,
+                                message: "I was expecting a `,' or a `}'",
+                                hint: None,
+                                line: Some(
+                                    2,
+                                ),
+                            },
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    )
+}
+
+#[test]
+fn test_biber_log() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\usepackage{biblatex}
+\addbibresource{bib/refs.bib}
+\begin{document}
+\cite{foo}
+\printbibliography
+\end{document}
+
+%! bib/refs.bib
+@article{foo,
+    title = {Foo}
+    author = {Bar},
+}
+
+%! main.blg
+[0] Config.pm:307> INFO - This is Biber 2.19
+[50] Utils.pm:411> ERROR - BibTeX subsystem: /tmp/biber_tmp_Xyz1/refs.bib_12345.utf8, line 3, syntax error: found "author", expected end of entry ("}" or ")") (skipping to next "@")
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/bib/refs.bib",
+                    [
+                        Build(
+                            32..32,
+                            BuildError {
+                                relative_path: "refs.bib",
+                                level: Error,
+                                message: "syntax error: found \"author\", expected end of entry (\"}\" or \")\") (skipping to next \"@\")",
+                                hint: None,
+                                line: Some(
+                                    2,
+                                ),
+                            },
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    )
+}
@@ -12,6 +12,7 @@ pub enum TexError {
     UndefinedLabel,
     UndefinedCitation,
     DuplicateLabel(Vec<(Url, TextRange)>),
+    UnknownOption,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -23,6 +24,7 @@ pub enum BibError {
     ExpectingFieldValue,
     UnusedEntry,
     DuplicateEntry(Vec<(Url, TextRange)>),
+    DuplicateEntryContent(Vec<(Url, TextRange)>),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -60,6 +62,7 @@ impl Diagnostic {
                 TexError::UndefinedLabel => "Undefined reference",
                 TexError::UndefinedCitation => "Undefined reference",
                 TexError::DuplicateLabel(_) => "Duplicate label",
+                TexError::UnknownOption => "Unknown option",
             },
             Diagnostic::Bib(_, error) => match error {
                 BibError::ExpectingLCurly => "Expecting a curly bracket: \"{\"",
@@ -69,6 +72,7 @@ impl Diagnostic {
                 BibError::ExpectingFieldValue => "Expecting a field value",
                 BibError::UnusedEntry => "Unused entry",
                 BibError::DuplicateEntry(_) => "Duplicate entry key",
+                BibError::DuplicateEntryContent(_) => "Duplicate entry",
             },
             Diagnostic::Build(_, error) => &error.message,
             Diagnostic::Chktex(error) => &error.message,
@@ -0,0 +1,132 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use crate::{file_name_db::FileNameDB, miktex, texlive, DistroKind};
+
+const CACHE_FORMAT: &str = "texlab-distro-cache-1";
+
+/// Describes the installed distribution. The cached database is only reused
+/// if the distribution and the modification times of its databases did not change.
+pub(super) fn fingerprint(kind: DistroKind, version: &str, root_dirs: &[PathBuf]) -> String {
+    let mut fingerprint = format!("{CACHE_FORMAT};{kind:?};{}", version.trim());
+    for dir in root_dirs {
+        let files = match kind {
+            DistroKind::Texlive => texlive::database_files(dir),
+            DistroKind::Miktex => miktex::database_files(dir),
+            DistroKind::Tectonic | DistroKind::Unknown => Vec::new(),
+        };
+
+        let _ = write!(fingerprint, ";{}", dir.display());
+        for file in files {
+            let modified = fs::metadata(&file)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |duration| duration.as_nanos());
+
+            let _ = write!(fingerprint, ";{}@{modified}", file.display());
+        }
+    }
+
+    fingerprint.replace(['\r', '\n'], " ")
+}
+
+/// Reads the database stored by [`save`] if it has been created for the same distribution.
+pub(super) fn load(file: &Path, fingerprint: &str) -> Option<FileNameDB> {
+    let reader = BufReader::new(fs::File::open(file).ok()?);
+    let mut lines = reader.lines();
+    if lines.next()?.ok()? != fingerprint {
+        return None;
+    }
+
+    let mut file_name_db = FileNameDB::default();
+    for line in lines {
+        file_name_db.insert(PathBuf::from(line.ok()?));
+    }
+
+    Some(file_name_db)
+}
+
+pub(super) fn save(file: &Path, fingerprint: &str, file_name_db: &FileNameDB) -> io::Result<()> {
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut writer = BufWriter::new(fs::File::create(file)?);
+    writeln!(writer, "{fingerprint}")?;
+    for (_, path) in file_name_db.iter() {
+        if let Some(path) = path.to_str().filter(|path| !path.contains('\n')) {
+            writeln!(writer, "{path}")?;
+        }
+    }
+
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::{file_name_db::FileNameDB, DistroKind};
+
+    use super::{fingerprint, load, save, CACHE_FORMAT};
+
+    fn file_name_db(paths: &[&str]) -> FileNameDB {
+        let mut file_name_db = FileNameDB::default();
+        for path in paths {
+            file_name_db.insert(PathBuf::from(path));
+        }
+
+        file_name_db
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let root_dirs = [PathBuf::from("/foo"), PathBuf::from("/bar")];
+        let fingerprint = fingerprint(DistroKind::Tectonic, "1.0\n", &root_dirs);
+        assert_eq!(
+            fingerprint,
+            format!("{CACHE_FORMAT};Tectonic;1.0;/foo;/bar"),
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_version() {
+        let root_dirs = [PathBuf::from("/foo")];
+        assert_ne!(
+            fingerprint(DistroKind::Tectonic, "1.0", &root_dirs),
+            fingerprint(DistroKind::Tectonic, "1.1", &root_dirs),
+        );
+    }
+
+    #[test]
+    fn test_save_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("cache").join("distro.txt");
+        save(&file, "foo", &file_name_db(&["/a/foo.sty", "/b/bar.cls"])).unwrap();
+
+        let file_name_db = load(&file, "foo").unwrap();
+        assert_eq!(file_name_db.get("foo.sty"), Some(Path::new("/a/foo.sty")));
+        assert_eq!(file_name_db.get("bar.cls"), Some(Path::new("/b/bar.cls")));
+        assert_eq!(file_name_db.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_load_fingerprint_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("distro.txt");
+        save(&file, "foo", &file_name_db(&["/a/foo.sty"])).unwrap();
+        assert!(load(&file, "bar").is_none());
+    }
+
+    #[test]
+    fn test_load_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load(&dir.path().join("distro.txt"), "foo").is_none());
+    }
+}
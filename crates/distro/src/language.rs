@@ -6,6 +6,9 @@ pub enum Language {
     Bib,
     Aux,
     Log,
+    Blg,
+    Ilg,
+    Fls,
     Root,
     Latexmkrc,
     Tectonic,
@@ -32,6 +35,9 @@ impl Language {
             "bib" | "bibtex" => Some(Self::Bib),
             "aux" => Some(Self::Aux),
             "log" => Some(Self::Log),
+            "blg" => Some(Self::Blg),
+            "ilg" => Some(Self::Ilg),
+            "fls" => Some(Self::Fls),
             _ => None,
         }
     }
@@ -1,10 +1,14 @@
+mod cache;
 mod file_name_db;
 mod kpsewhich;
 mod language;
 mod miktex;
 mod texlive;
 
-use std::process::{Command, Stdio};
+use std::{
+    path::Path,
+    process::{Command, Stdio},
+};
 
 use anyhow::Result;
 
@@ -32,9 +36,17 @@ pub struct Distro {
 
 impl Distro {
     pub fn detect() -> Result<Self> {
+        Self::detect_with_cache(None)
+    }
+
+    /// Detects the distribution like [`Distro::detect`] but reuses the file name database
+    /// stored in `cache_file` as long as the distribution has not been changed or updated.
+    pub fn detect_with_cache(cache_file: Option<&Path>) -> Result<Self> {
+        let mut version = String::new();
         let kind = match Command::new("latex").arg("--version").output() {
             Ok(output) => {
                 let stdout = String::from_utf8_lossy(&output.stdout);
+                version = stdout.lines().next().unwrap_or_default().to_string();
                 if stdout.contains("TeX Live") {
                     DistroKind::Texlive
                 } else if stdout.contains("MiKTeX") {
@@ -58,16 +70,34 @@ impl Distro {
             }
         };
 
-        let mut file_name_db = match kind {
-            DistroKind::Texlive => {
-                let root_dirs = kpsewhich::root_directories()?;
-                FileNameDB::parse(&root_dirs, &mut texlive::read_database)?
-            }
-            DistroKind::Miktex => {
-                let root_dirs = kpsewhich::root_directories()?;
-                FileNameDB::parse(&root_dirs, &mut miktex::read_database)?
+        let root_dirs = match kind {
+            DistroKind::Texlive | DistroKind::Miktex => kpsewhich::root_directories()?,
+            DistroKind::Tectonic | DistroKind::Unknown => Vec::new(),
+        };
+
+        let fingerprint = cache::fingerprint(kind, &version, &root_dirs);
+        let cached = cache_file.and_then(|file| cache::load(file, &fingerprint));
+        let mut file_name_db = match cached {
+            Some(file_name_db) => file_name_db,
+            None => {
+                let file_name_db = match kind {
+                    DistroKind::Texlive => {
+                        FileNameDB::parse(&root_dirs, &mut texlive::read_database)?
+                    }
+                    DistroKind::Miktex => {
+                        FileNameDB::parse(&root_dirs, &mut miktex::read_database)?
+                    }
+                    DistroKind::Tectonic | DistroKind::Unknown => FileNameDB::default(),
+                };
+
+                if let Some(file) = cache_file {
+                    if let Err(why) = cache::save(file, &fingerprint, &file_name_db) {
+                        log::warn!("Unable to write the distro cache: {}", why);
+                    }
+                }
+
+                file_name_db
             }
-            DistroKind::Tectonic | DistroKind::Unknown => FileNameDB::default(),
         };
 
         if let Some(bibinputs) = std::env::var_os("BIBINPUTS") {
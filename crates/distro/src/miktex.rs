@@ -31,6 +31,21 @@ pub(super) fn read_database(directory: &Path) -> Result<Vec<PathBuf>> {
     Ok(database)
 }
 
+pub(super) fn database_files(directory: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(directory.join(DATABASE_PATH)) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(OsStr::to_str) == Some("fndb-5"))
+        .collect();
+
+    files.sort();
+    files
+}
+
 fn parse_database(bytes: &[u8]) -> io::Result<Vec<PathBuf>> {
     let mut reader = Cursor::new(bytes);
     if read_u32(&mut reader)? != FNDB_SIGNATURE {
@@ -19,6 +19,15 @@ pub(super) fn read_database(directory: &Path) -> Result<Vec<PathBuf>> {
     Ok(files)
 }
 
+pub(super) fn database_files(directory: &Path) -> Vec<PathBuf> {
+    let file = directory.join(DATABASE_PATH);
+    if file.is_file() {
+        vec![file]
+    } else {
+        Vec::new()
+    }
+}
+
 fn parse_database(lines: Lines) -> Vec<PathBuf> {
     let mut paths = Vec::new();
     let mut directory = "";
@@ -1,5 +1,5 @@
-use base_db::{Document, DocumentData};
-use rowan::{ast::AstNode, TextRange};
+use base_db::{util::Frame, Document, DocumentData};
+use rowan::{ast::AstNode, TextLen, TextRange, TextSize};
 use syntax::{
     bibtex::{self, HasDelims, HasName},
     latex,
@@ -16,10 +16,17 @@ pub enum FoldingRangeKind {
     Section,
     Environment,
     Entry,
+    Comment,
+    Preamble,
+    Region,
+    Group,
 }
 
 pub fn find_all(document: &Document) -> Vec<FoldingRange> {
-    let mut builder = FoldingBuilder::default();
+    let mut builder = FoldingBuilder {
+        document,
+        ranges: Vec::new(),
+    };
 
     if let DocumentData::Tex(data) = &document.data {
         for node in data.root_node().descendants() {
@@ -27,10 +34,24 @@ pub fn find_all(document: &Document) -> Vec<FoldingRange> {
                 builder.fold_section(&section);
             } else if let Some(item) = latex::EnumItem::cast(node.clone()) {
                 builder.fold_enum_item(&item);
-            } else if let Some(env) = latex::Environment::cast(node) {
+            } else if let Some(env) = latex::Environment::cast(node.clone()) {
+                builder.fold_preamble(&env);
                 builder.fold_environment(env);
+            } else if node.kind() == latex::BLOCK_COMMENT {
+                builder.fold_block_comment(&node);
+            } else if matches!(
+                node.kind(),
+                latex::CURLY_GROUP
+                    | latex::CURLY_GROUP_KEY_VALUE
+                    | latex::BRACK_GROUP
+                    | latex::BRACK_GROUP_KEY_VALUE
+                    | latex::MIXED_GROUP
+            ) {
+                builder.fold_group(&node);
             }
         }
+
+        builder.fold_comments();
     } else if let DocumentData::Bib(data) = &document.data {
         for node in data.root_node().descendants() {
             if let Some(entry) = bibtex::Entry::cast(node.clone()) {
@@ -44,12 +65,12 @@ pub fn find_all(document: &Document) -> Vec<FoldingRange> {
     builder.ranges
 }
 
-#[derive(Debug, Default)]
-struct FoldingBuilder {
+struct FoldingBuilder<'a> {
+    document: &'a Document,
     ranges: Vec<FoldingRange>,
 }
 
-impl FoldingBuilder {
+impl<'a> FoldingBuilder<'a> {
     fn fold_section(&mut self, section: &latex::Section) -> Option<()> {
         let start = section
             .name()
@@ -81,8 +102,14 @@ impl FoldingBuilder {
     }
 
     fn fold_environment(&mut self, env: latex::Environment) -> Option<()> {
-        let start = latex::small_range(&env.begin()?).end();
+        let mut start = latex::small_range(&env.begin()?).end();
         let end = latex::small_range(&env.end()?).start();
+
+        // Keep the title of a frame visible when it is passed to `\begin{frame}`.
+        if let Some(title) = Frame::cast(env.clone()).and_then(|frame| frame.begin_title()) {
+            start = latex::small_range(&title).end().min(end);
+        }
+
         self.ranges.push(FoldingRange {
             range: TextRange::new(start, end),
             kind: FoldingRangeKind::Environment,
@@ -91,6 +118,109 @@ impl FoldingBuilder {
         Some(())
     }
 
+    /// Folds everything between `\\documentclass` and `\\begin{document}`.
+    fn fold_preamble(&mut self, env: &latex::Environment) -> Option<()> {
+        let name = env.begin()?.name()?.key()?;
+        if name.to_string() != "document" {
+            return None;
+        }
+
+        let root = env.syntax().ancestors().last()?;
+        let start = root
+            .descendants()
+            .filter(|node| node.kind() == latex::CLASS_INCLUDE)
+            .find_map(latex::Include::cast)
+            .map_or_else(
+                || root.text_range().start(),
+                |include| latex::small_range(&include).end(),
+            );
+
+        let end = latex::small_range(env).start();
+        self.push_multiline(start, end, FoldingRangeKind::Preamble)
+    }
+
+    fn fold_block_comment(&mut self, node: &latex::SyntaxNode) -> Option<()> {
+        let start = node.first_token()?.text_range().end();
+        let end = node.last_token()?.text_range().start();
+        self.push_multiline(start, end, FoldingRangeKind::Comment)
+    }
+
+    fn fold_group(&mut self, node: &latex::SyntaxNode) -> Option<()> {
+        let left = node.first_token()?;
+        let right = node
+            .children_with_tokens()
+            .filter_map(|element| element.into_token())
+            .filter(|token| matches!(token.kind(), latex::R_CURLY | latex::R_BRACK))
+            .last()?;
+
+        if !matches!(left.kind(), latex::L_CURLY | latex::L_BRACK) {
+            return None;
+        }
+
+        let start = left.text_range().end();
+        let end = right.text_range().start();
+        self.push_multiline(start, end, FoldingRangeKind::Group)
+    }
+
+    /// Folds blocks of consecutive comment lines and the regions between `% region` and `% endregion`.
+    fn fold_comments(&mut self) {
+        let mut block: Option<(TextSize, TextSize)> = None;
+        let mut regions = Vec::new();
+        let mut offset = TextSize::from(0);
+        for line in self.document.text.split_inclusive('\n') {
+            let start = offset;
+            offset += line.text_len();
+
+            let content = line.trim_end();
+            let end = start + content.text_len();
+            let Some(comment) = content.trim_start().strip_prefix('%') else {
+                self.finish_comment_block(block.take());
+                continue;
+            };
+
+            let comment = comment.trim_start();
+            if comment.starts_with("endregion") {
+                self.finish_comment_block(block.take());
+                if let Some(region_end) = regions.pop() {
+                    let marker = start + (line.text_len() - line.trim_start().text_len());
+                    self.push_multiline(region_end, marker, FoldingRangeKind::Region);
+                }
+            } else if comment.starts_with("region") {
+                self.finish_comment_block(block.take());
+                regions.push(end);
+            } else {
+                block = Some(block.map_or((end, end), |(first, _)| (first, end)));
+            }
+        }
+
+        self.finish_comment_block(block);
+    }
+
+    fn finish_comment_block(&mut self, block: Option<(TextSize, TextSize)>) {
+        if let Some((start, end)) = block {
+            self.push_multiline(start, end, FoldingRangeKind::Comment);
+        }
+    }
+
+    fn push_multiline(
+        &mut self,
+        start: TextSize,
+        end: TextSize,
+        kind: FoldingRangeKind,
+    ) -> Option<()> {
+        let line_index = &self.document.line_index;
+        if start >= end || line_index.line_col(start).line == line_index.line_col(end).line {
+            return None;
+        }
+
+        self.ranges.push(FoldingRange {
+            range: TextRange::new(start, end),
+            kind,
+        });
+
+        Some(())
+    }
+
     fn fold_entry(&mut self, entry: &(impl HasName + HasDelims)) -> Option<()> {
         let start = entry.name_token()?.text_range().end();
         let end = entry.right_delim_token()?.text_range().start();
@@ -85,3 +85,82 @@ here
         "#]],
     );
 }
+
+#[test]
+fn test_beamer_frame() {
+    check(
+        r#"
+%! main.tex
+\begin{frame}<2->{Foo}
+    Bar
+\end{frame}
+\begin{frame}
+    \frametitle{Baz}
+\end{frame}
+|"#,
+        expect![[r#"
+            [
+                FoldingRange {
+                    range: 22..31,
+                    kind: Environment,
+                },
+                FoldingRange {
+                    range: 56..78,
+                    kind: Environment,
+                },
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_preamble_and_comments() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\usepackage{amsmath}
+\newcommand{\foo}{
+    Foo
+}
+% region Commands
+% Foo
+% Bar
+\newcommand{\bar}{Bar}
+% endregion
+\begin{document}
+\iffalse
+    Baz
+\fi
+\end{document}
+|"#,
+        expect![[r#"
+            [
+                FoldingRange {
+                    range: 63..72,
+                    kind: Group,
+                },
+                FoldingRange {
+                    range: 23..139,
+                    kind: Preamble,
+                },
+                FoldingRange {
+                    range: 155..177,
+                    kind: Environment,
+                },
+                FoldingRange {
+                    range: 164..173,
+                    kind: Comment,
+                },
+                FoldingRange {
+                    range: 97..103,
+                    kind: Comment,
+                },
+                FoldingRange {
+                    range: 91..127,
+                    kind: Region,
+                },
+            ]
+        "#]],
+    );
+}
@@ -0,0 +1,33 @@
+use rowan::ast::AstNode;
+use syntax::bibtex::{self, HasName};
+
+use crate::{highlight_matching, Highlight, HighlightKind, HighlightParams, Occurrence};
+
+pub fn find_highlights(params: &HighlightParams, results: &mut Vec<Highlight>) -> Option<()> {
+    let document = params.feature.document;
+    let occurrences: Vec<_> = if let Some(data) = document.data.as_tex() {
+        data.semantics
+            .citations
+            .iter()
+            .map(|citation| Occurrence {
+                name: citation.name.text.clone(),
+                range: citation.name.range,
+                kind: HighlightKind::Read,
+            })
+            .collect()
+    } else {
+        let data = document.data.as_bib()?;
+        data.root_node()
+            .children()
+            .filter_map(bibtex::Entry::cast)
+            .filter_map(|entry| entry.name_token())
+            .map(|name| Occurrence {
+                name: name.text().into(),
+                range: name.text_range(),
+                kind: HighlightKind::Write,
+            })
+            .collect()
+    };
+
+    highlight_matching(params, &occurrences, results)
+}
@@ -0,0 +1,29 @@
+use rowan::ast::AstNode;
+use syntax::latex;
+
+use crate::{highlight_matching, Highlight, HighlightKind, HighlightParams, Occurrence};
+
+pub fn find_highlights(params: &HighlightParams, results: &mut Vec<Highlight>) -> Option<()> {
+    let data = params.feature.document.data.as_tex()?;
+    let occurrences: Vec<_> = data
+        .root_node()
+        .descendants()
+        .filter_map(|node| {
+            let (name, kind) = if let Some(definition) = latex::ColorDefinition::cast(node.clone())
+            {
+                (definition.name()?.key()?, HighlightKind::Write)
+            } else {
+                let reference = latex::ColorReference::cast(node)?;
+                (reference.name()?.key()?, HighlightKind::Read)
+            };
+
+            Some(Occurrence {
+                name: name.to_string(),
+                range: latex::small_range(&name),
+                kind,
+            })
+        })
+        .collect();
+
+    highlight_matching(params, &occurrences, results)
+}
@@ -0,0 +1,58 @@
+use rowan::ast::AstNode;
+use syntax::latex;
+
+use crate::{highlight_matching, Highlight, HighlightKind, HighlightParams, Occurrence};
+
+/// Only commands which are defined in the project are highlighted,
+/// because highlighting every occurrence of builtin commands like `\item` is not helpful.
+pub fn find_highlights(params: &HighlightParams, results: &mut Vec<Highlight>) -> Option<()> {
+    let data = params.feature.document.data.as_tex()?;
+    let cursor = data
+        .root_node()
+        .token_at_offset(params.offset)
+        .find(|token| token.kind() == latex::COMMAND_NAME)?;
+
+    if !is_defined(params, cursor.text()) {
+        return None;
+    }
+
+    let occurrences: Vec<_> = data
+        .root_node()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == latex::COMMAND_NAME)
+        .map(|token| {
+            let is_definition = token.parent().is_some_and(|parent| {
+                parent.kind() == latex::CURLY_GROUP_COMMAND
+                    && parent
+                        .parent()
+                        .is_some_and(|node| node.kind() == latex::COMMAND_DEFINITION)
+            });
+
+            Occurrence {
+                name: token.text().into(),
+                range: token.text_range(),
+                kind: if is_definition {
+                    HighlightKind::Write
+                } else {
+                    HighlightKind::Read
+                },
+            }
+        })
+        .collect();
+
+    highlight_matching(params, &occurrences, results)
+}
+
+fn is_defined(params: &HighlightParams, name: &str) -> bool {
+    params
+        .feature
+        .project
+        .documents
+        .iter()
+        .filter_map(|document| document.data.as_tex())
+        .flat_map(|data| data.root_node().descendants())
+        .filter_map(latex::CommandDefinition::cast)
+        .filter_map(|def| def.name()?.command())
+        .any(|command| command.text() == name)
+}
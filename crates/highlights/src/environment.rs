@@ -0,0 +1,33 @@
+use rowan::ast::AstNode;
+use syntax::latex;
+
+use crate::{Highlight, HighlightKind, HighlightParams};
+
+pub fn find_highlights(params: &HighlightParams, results: &mut Vec<Highlight>) -> Option<()> {
+    let data = params.feature.document.data.as_tex()?;
+    let root = data.root_node();
+    let token = root.token_at_offset(params.offset).right_biased()?;
+
+    let environment = token
+        .parent_ancestors()
+        .filter_map(latex::Environment::cast)
+        .find(|environment| {
+            let begin = environment.begin().and_then(|begin| begin.name());
+            let end = environment.end().and_then(|end| end.name());
+            begin
+                .into_iter()
+                .chain(end)
+                .any(|name| latex::small_range(&name).contains_inclusive(params.offset))
+        })?;
+
+    let begin = environment.begin()?.name()?.key()?;
+    let end = environment.end()?.name()?.key()?;
+    for name in [begin, end] {
+        results.push(Highlight {
+            range: latex::small_range(&name),
+            kind: HighlightKind::Text,
+        });
+    }
+
+    Some(())
+}
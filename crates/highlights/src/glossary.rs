@@ -0,0 +1,36 @@
+use rowan::ast::AstNode;
+use syntax::latex;
+
+use crate::{highlight_matching, Highlight, HighlightKind, HighlightParams, Occurrence};
+
+pub fn find_highlights(params: &HighlightParams, results: &mut Vec<Highlight>) -> Option<()> {
+    let data = params.feature.document.data.as_tex()?;
+    let occurrences: Vec<_> = data
+        .root_node()
+        .descendants()
+        .filter_map(|node| {
+            let (name, kind) =
+                if let Some(definition) = latex::GlossaryEntryDefinition::cast(node.clone()) {
+                    (definition.name()?, HighlightKind::Write)
+                } else if let Some(definition) = latex::AcronymDefinition::cast(node.clone()) {
+                    (definition.name()?, HighlightKind::Write)
+                } else if let Some(reference) = latex::GlossaryEntryReference::cast(node.clone()) {
+                    (reference.name()?, HighlightKind::Read)
+                } else {
+                    (
+                        latex::AcronymReference::cast(node)?.name()?,
+                        HighlightKind::Read,
+                    )
+                };
+
+            let name = name.key()?;
+            Some(Occurrence {
+                name: name.to_string(),
+                range: latex::small_range(&name),
+                kind,
+            })
+        })
+        .collect();
+
+    highlight_matching(params, &occurrences, results)
+}
@@ -1,6 +1,11 @@
 use base_db::FeatureParams;
 use rowan::{TextRange, TextSize};
 
+mod citation;
+mod color;
+mod command;
+mod environment;
+mod glossary;
 mod label;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -13,6 +18,7 @@ pub struct Highlight {
 pub enum HighlightKind {
     Write,
     Read,
+    Text,
 }
 
 #[derive(Debug)]
@@ -23,9 +29,45 @@ pub struct HighlightParams<'a> {
 
 pub fn find_all(params: &HighlightParams) -> Vec<Highlight> {
     let mut results = Vec::new();
-    label::find_highlights(&params, &mut results);
+    label::find_highlights(params, &mut results);
+    citation::find_highlights(params, &mut results);
+    command::find_highlights(params, &mut results);
+    environment::find_highlights(params, &mut results);
+    color::find_highlights(params, &mut results);
+    glossary::find_highlights(params, &mut results);
     results
 }
 
+/// An occurrence of a name in the current document.
+#[derive(Debug)]
+struct Occurrence {
+    name: String,
+    range: TextRange,
+    kind: HighlightKind,
+}
+
+/// Highlights all occurrences which share the name of the occurrence under the cursor.
+fn highlight_matching(
+    params: &HighlightParams,
+    occurrences: &[Occurrence],
+    results: &mut Vec<Highlight>,
+) -> Option<()> {
+    let cursor = occurrences
+        .iter()
+        .find(|occurrence| occurrence.range.contains_inclusive(params.offset))?;
+
+    for occurrence in occurrences
+        .iter()
+        .filter(|occurrence| occurrence.name == cursor.name)
+    {
+        results.push(Highlight {
+            range: occurrence.range,
+            kind: occurrence.kind,
+        });
+    }
+
+    Some(())
+}
+
 #[cfg(test)]
 mod tests;
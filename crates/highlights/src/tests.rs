@@ -39,3 +39,131 @@ fn test_label() {
         &[HighlightKind::Write, HighlightKind::Read],
     )
 }
+
+#[test]
+fn test_citation() {
+    check(
+        r#"
+%! main.tex
+\cite{foo,bar}
+       |
+      ^^^
+\cite{foo}
+      ^^^
+"#,
+        &[HighlightKind::Read, HighlightKind::Read],
+    )
+}
+
+#[test]
+fn test_entry() {
+    check(
+        r#"
+%! main.bib
+@article{foo,}
+          |
+         ^^^
+@article{bar,}
+"#,
+        &[HighlightKind::Write],
+    )
+}
+
+#[test]
+fn test_command() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\foo}{Foo}
+            ^^^^
+\foo \bar
+  |
+^^^^
+\foo
+^^^^
+"#,
+        &[
+            HighlightKind::Write,
+            HighlightKind::Read,
+            HighlightKind::Read,
+        ],
+    )
+}
+
+#[test]
+fn test_command_undefined() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\foo}{Foo}
+\bar \foo
+  |
+\bar
+"#,
+        &[],
+    )
+}
+
+#[test]
+fn test_command_project() {
+    check(
+        r#"
+%! main.tex
+\input{defs}
+\foo
+  |
+^^^^
+
+%! defs.tex
+\newcommand{\foo}{Foo}
+"#,
+        &[HighlightKind::Read],
+    )
+}
+
+#[test]
+fn test_environment() {
+    check(
+        r#"
+%! main.tex
+\begin{foo}
+         |
+       ^^^
+\begin{foo}
+\end{foo}
+\end{foo}
+     ^^^
+"#,
+        &[HighlightKind::Text, HighlightKind::Text],
+    )
+}
+
+#[test]
+fn test_color() {
+    check(
+        r#"
+%! main.tex
+\definecolor{foo}{rgb}{1,0,0}
+             ^^^
+\color{foo}
+        |
+       ^^^
+"#,
+        &[HighlightKind::Write, HighlightKind::Read],
+    )
+}
+
+#[test]
+fn test_glossary_entry() {
+    check(
+        r#"
+%! main.tex
+\newacronym{foo}{FOO}{Foo}
+            ^^^
+\gls{foo}
+      |
+     ^^^
+"#,
+        &[HighlightKind::Write, HighlightKind::Read],
+    )
+}
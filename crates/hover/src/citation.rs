@@ -27,11 +27,12 @@ pub(super) fn find_hover<'a>(params: &HoverParams<'a>) -> Option<Hover<'a>> {
         _ => return None,
     };
 
+    let style = feature.workspace.config().citation.style;
     let text = feature.project.documents.iter().find_map(|document| {
         let data = document.data.as_bib()?;
         let root = bibtex::Root::cast(data.root_node())?;
         let entry = root.find_entry(name)?;
-        citeproc::render(&entry)
+        citeproc::render(&entry, style)
     })?;
 
     let data = HoverData::Citation(text);
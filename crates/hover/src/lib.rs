@@ -2,6 +2,7 @@ mod citation;
 mod entry_type;
 mod field_type;
 mod label;
+mod option;
 mod package;
 mod string_ref;
 
@@ -32,11 +33,13 @@ pub enum HoverData<'db> {
     FieldType(BibtexFieldType<'db>),
     Label(RenderedLabel<'db>),
     StringRef(String),
+    PackageOption(&'db completion_data::PackageOption<'db>),
 }
 
 pub fn find<'a>(params: &HoverParams<'a>) -> Option<Hover<'a>> {
     citation::find_hover(&params)
         .or_else(|| package::find_hover(&params))
+        .or_else(|| option::find_hover(params))
         .or_else(|| entry_type::find_hover(&params))
         .or_else(|| field_type::find_hover(&params))
         .or_else(|| label::find_hover(&params))
@@ -0,0 +1,33 @@
+use base_db::util::find_options;
+use syntax::latex;
+
+use crate::{Hover, HoverData, HoverParams};
+
+pub(super) fn find_hover<'a>(params: &HoverParams<'a>) -> Option<Hover<'a>> {
+    let data = params.feature.document.data.as_tex()?;
+    let token = data
+        .root_node()
+        .token_at_offset(params.offset)
+        .find(|token| token.kind() == latex::WORD)?;
+
+    // Keys follow the opening bracket or a comma, values follow an equality sign.
+    std::iter::successors(token.prev_token(), |token| token.prev_token())
+        .find(|token| !matches!(token.kind(), latex::WHITESPACE | latex::LINE_BREAK))
+        .filter(|token| matches!(token.kind(), latex::L_BRACK | latex::L_CURLY | latex::COMMA))?;
+
+    let group = token.parent_ancestors().find(|node| {
+        matches!(
+            node.kind(),
+            latex::BRACK_GROUP_KEY_VALUE | latex::CURLY_GROUP
+        )
+    })?;
+
+    let option = find_options(&group)?
+        .into_iter()
+        .find(|option| option.name == token.text())?;
+
+    Some(Hover {
+        range: token.text_range(),
+        data: HoverData::PackageOption(option),
+    })
+}
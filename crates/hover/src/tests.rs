@@ -220,6 +220,48 @@ fn test_latex_class_unknown() {
     );
 }
 
+#[test]
+fn test_latex_package_option() {
+    check(
+        r#"
+%! main.tex
+\usepackage[a4paper, margin=1in]{geometry}
+                       |
+                     ^^^^^^"#,
+        expect![[r#"
+            Some(
+                PackageOption(
+                    PackageOption {
+                        name: "margin",
+                        description: "Sets all margins, e.g. `margin=1in`.",
+                    },
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_latex_class_option() {
+    check(
+        r#"
+%! main.tex
+\documentclass[twocolumn]{article}
+                  |
+               ^^^^^^^^^"#,
+        expect![[r#"
+            Some(
+                PackageOption(
+                    PackageOption {
+                        name: "twocolumn",
+                        description: "Typesets the text in two columns.",
+                    },
+                ),
+            )
+        "#]],
+    );
+}
+
 #[test]
 fn test_latex_label_section() {
     check(
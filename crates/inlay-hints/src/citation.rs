@@ -0,0 +1,98 @@
+use base_db::DocumentData;
+use bibtex_utils::field::author::AuthorFieldData;
+use rowan::ast::AstNode;
+use syntax::bibtex::{self, HasName, HasValue};
+
+use crate::{InlayHint, InlayHintBuilder, InlayHintData};
+
+pub(super) fn find_hints(builder: &mut InlayHintBuilder) -> Option<()> {
+    let params = &builder.params.feature;
+    if !params.workspace.config().inlay_hints.citations {
+        return None;
+    }
+
+    let data = params.document.data.as_tex()?;
+    let range = builder.params.range;
+    for citation in data
+        .semantics
+        .citations
+        .iter()
+        .filter(|citation| citation.name.range.intersect(range).is_some())
+    {
+        let Some(text) = render_citation(builder, &citation.name.text) else {
+            continue;
+        };
+
+        // The keys of `\cite{foo,bar}` share a single hint after the command.
+        let offset = citation.full_range.end();
+        match builder.hints.last_mut() {
+            Some(InlayHint {
+                offset: last_offset,
+                data: InlayHintData::Citation(last_text),
+            }) if *last_offset == offset => {
+                last_text.push_str("; ");
+                last_text.push_str(&text);
+            }
+            _ => {
+                builder.hints.push(InlayHint {
+                    offset,
+                    data: InlayHintData::Citation(text),
+                });
+            }
+        }
+    }
+
+    Some(())
+}
+
+/// Renders the entry as `Smith 2019`, `Smith and Doe 2019` or `Smith et al. 2019`.
+fn render_citation(builder: &InlayHintBuilder, key: &str) -> Option<String> {
+    let (entry, node) = builder
+        .params
+        .feature
+        .project
+        .documents
+        .iter()
+        .find_map(|document| {
+            let DocumentData::Bib(data) = &document.data else {
+                return None;
+            };
+
+            let entry = data
+                .semantics
+                .entries
+                .iter()
+                .find(|entry| entry.name.text == key)?;
+
+            let node = bibtex::Root::cast(data.root_node())?.find_entry(key)?;
+            Some((entry, node))
+        })?;
+
+    let authors = node
+        .fields()
+        .filter(|field| {
+            field.name_token().is_some_and(|name| {
+                let name = name.text();
+                name.eq_ignore_ascii_case("author") || name.eq_ignore_ascii_case("editor")
+            })
+        })
+        .find_map(|field| AuthorFieldData::parse(&field.value()?))?;
+
+    let surnames = authors
+        .authors
+        .iter()
+        .map(|author| author.surname())
+        .collect::<Vec<_>>();
+
+    let authors = match surnames.as_slice() {
+        [] => return None,
+        [first] => first.to_string(),
+        [first, second] => format!("{first} and {second}"),
+        [first, ..] => format!("{first} et al."),
+    };
+
+    Some(match entry.year {
+        Some(year) => format!("{authors} {year}"),
+        None => authors,
+    })
+}
@@ -0,0 +1,59 @@
+use rowan::ast::AstNode;
+use syntax::latex::{self, HasCurly};
+
+use crate::{InlayHint, InlayHintBuilder, InlayHintData};
+
+/// Environments with fewer lines are small enough to see their beginning.
+const MIN_LINES: u32 = 10;
+
+pub(super) fn find_hints(builder: &mut InlayHintBuilder) -> Option<()> {
+    let params = &builder.params.feature;
+    if !params.workspace.config().inlay_hints.environment_ends {
+        return None;
+    }
+
+    let data = params.document.data.as_tex()?;
+    let line_index = &params.document.line_index;
+    let range = builder.params.range;
+    for environment in data
+        .root_node()
+        .descendants()
+        .filter_map(latex::Environment::cast)
+    {
+        let Some(end) = environment.end() else {
+            continue;
+        };
+
+        let end_range = latex::small_range(&end);
+        if end_range.intersect(range).is_none() {
+            continue;
+        }
+
+        let start = line_index.line_col(latex::small_range(&environment).start());
+        let stop = line_index.line_col(end_range.end());
+        if stop.line - start.line + 1 < MIN_LINES {
+            continue;
+        }
+
+        if let Some(text) = find_label_or_caption(&environment) {
+            builder.hints.push(InlayHint {
+                offset: end_range.end(),
+                data: InlayHintData::EnvironmentEnd(text),
+            });
+        }
+    }
+
+    Some(())
+}
+
+fn find_label_or_caption(environment: &latex::Environment) -> Option<String> {
+    let children = || environment.syntax().children();
+    children()
+        .filter_map(latex::LabelDefinition::cast)
+        .find_map(|label| Some(label.name()?.key()?.to_string()))
+        .or_else(|| {
+            children()
+                .filter_map(latex::Caption::cast)
+                .find_map(|caption| caption.long()?.content_text())
+        })
+}
@@ -1,4 +1,7 @@
+mod citation;
+mod environment;
 mod label;
+mod parameter;
 
 use base_db::{util::RenderedLabel, FeatureParams};
 use rowan::{TextRange, TextSize};
@@ -18,6 +21,9 @@ pub struct InlayHint<'a> {
 pub enum InlayHintData<'a> {
     LabelDefinition(RenderedLabel<'a>),
     LabelReference(RenderedLabel<'a>),
+    Citation(String),
+    EnvironmentEnd(String),
+    ParameterName(&'static str),
 }
 
 pub fn find_all<'a>(params: &InlayHintParams<'a>) -> Option<Vec<InlayHint<'a>>> {
@@ -27,6 +33,10 @@ pub fn find_all<'a>(params: &InlayHintParams<'a>) -> Option<Vec<InlayHint<'a>>>
     };
 
     label::find_hints(&mut builder);
+    citation::find_hints(&mut builder);
+    environment::find_hints(&mut builder);
+    parameter::find_hints(&mut builder);
+    builder.hints.sort_by_key(|hint| hint.offset);
     Some(builder.hints)
 }
 
@@ -0,0 +1,41 @@
+use rowan::ast::AstNode;
+use syntax::latex;
+
+use crate::{InlayHint, InlayHintBuilder, InlayHintData};
+
+pub(super) fn find_hints(builder: &mut InlayHintBuilder) -> Option<()> {
+    let params = &builder.params.feature;
+    if !params.workspace.config().inlay_hints.parameter_names {
+        return None;
+    }
+
+    let data = params.document.data.as_tex()?;
+    let range = builder.params.range;
+    for command in data
+        .root_node()
+        .descendants()
+        .filter_map(latex::GenericCommand::cast)
+        .filter(|command| command.syntax().text_range().intersect(range).is_some())
+    {
+        let Some(names) = command
+            .name()
+            .and_then(|name| completion_data::parameter_names(&name.text()[1..]))
+        else {
+            continue;
+        };
+
+        let groups = command
+            .syntax()
+            .children()
+            .filter(|node| node.kind() == latex::CURLY_GROUP);
+
+        for (group, name) in groups.zip(names) {
+            builder.hints.push(InlayHint {
+                offset: group.text_range().start(),
+                data: InlayHintData::ParameterName(name),
+            });
+        }
+    }
+
+    Some(())
+}
@@ -106,3 +106,78 @@ fn test_label_definition() {
         "#]],
     );
 }
+
+#[test]
+fn test_citation() {
+    check(
+        r#"
+%! main.tex
+\addbibresource{main.bib}
+\cite{foo} \cite{bar,baz}
+          !              !
+
+%! main.bib
+@article{foo, author = {Smith, John}, title = {Foo}, year = {2019}}
+@article{bar, author = {Smith, John and Doe, Jane}, title = {Bar}, year = {2020}}
+@article{baz, author = {Smith, John and Doe, Jane and Roe, Richard}, title = {Baz}}"#,
+        expect![[r#"
+            [
+                Citation(
+                    "Smith 2019",
+                ),
+                Citation(
+                    "Smith and Doe 2020; Smith et al.",
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_environment_end() {
+    check(
+        r#"
+%! main.tex
+\begin{figure}
+    \caption{Foo}
+    %
+    %
+    %
+    %
+    %
+    %
+    %
+\end{figure}
+            !
+\begin{figure}
+    \label{fig:bar}
+\end{figure}"#,
+        expect![[r#"
+            [
+                EnvironmentEnd(
+                    "Foo",
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_parameter_names() {
+    check(
+        r#"
+%! main.tex
+$\frac{a}[b]{c}$
+      !     !"#,
+        expect![[r#"
+            [
+                ParameterName(
+                    "numerator",
+                ),
+                ParameterName(
+                    "denominator",
+                ),
+            ]
+        "#]],
+    );
+}
@@ -12,11 +12,18 @@ pub struct LineIndex {
     /// Offset the the beginning of each line, zero-based
     pub newlines: Vec<TextSize>,
     /// List of non-ASCII characters on each line
-    pub(crate) utf16_lines: FxHashMap<u32, Vec<Utf16Char>>,
+    pub(crate) wide_chars: FxHashMap<u32, Vec<WideChar>>,
 }
 
+/// The encoding of the columns of a [`WideLineCol`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct LineColUtf16 {
+pub enum WideEncoding {
+    Utf16,
+    Utf32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WideLineCol {
     /// Zero-based
     pub line: u32,
     /// Zero-based
@@ -32,33 +39,38 @@ pub struct LineCol {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
-pub(crate) struct Utf16Char {
+pub(crate) struct WideChar {
     /// Start offset of a character inside a line, zero-based
     pub(crate) start: TextSize,
     /// End offset of a character inside a line, zero-based
     pub(crate) end: TextSize,
 }
 
-impl Utf16Char {
+impl WideChar {
     /// Returns the length in 8-bit UTF-8 code units.
     fn len(&self) -> TextSize {
         self.end - self.start
     }
 
-    /// Returns the length in 16-bit UTF-16 code units.
-    fn len_utf16(&self) -> usize {
-        if self.len() == TextSize::from(4) {
-            2
-        } else {
-            1
+    /// Returns the length in UTF-16 or UTF-32 code units.
+    fn wide_len(&self, enc: WideEncoding) -> usize {
+        match enc {
+            WideEncoding::Utf16 => {
+                if self.len() == TextSize::from(4) {
+                    2
+                } else {
+                    1
+                }
+            }
+            WideEncoding::Utf32 => 1,
         }
     }
 }
 
 impl LineIndex {
     pub fn new(text: &str) -> LineIndex {
-        let mut utf16_lines = FxHashMap::default();
-        let mut utf16_chars = Vec::new();
+        let mut wide_lines = FxHashMap::default();
+        let mut wide_chars = Vec::new();
 
         let mut newlines = vec![0.into()];
         let mut curr_row = 0.into();
@@ -70,10 +82,10 @@ impl LineIndex {
             if c == '\n' {
                 newlines.push(curr_row);
 
-                // Save any utf-16 characters seen in the previous line
-                if !utf16_chars.is_empty() {
-                    utf16_lines.insert(line, utf16_chars);
-                    utf16_chars = Vec::new();
+                // Save any wide characters seen in the previous line
+                if !wide_chars.is_empty() {
+                    wide_lines.insert(line, wide_chars);
+                    wide_chars = Vec::new();
                 }
 
                 // Prepare for processing the next line
@@ -83,7 +95,7 @@ impl LineIndex {
             }
 
             if !c.is_ascii() {
-                utf16_chars.push(Utf16Char {
+                wide_chars.push(WideChar {
                     start: curr_col,
                     end: curr_col + c_len,
                 });
@@ -92,14 +104,14 @@ impl LineIndex {
             curr_col += c_len;
         }
 
-        // Save any utf-16 characters seen in the last line
-        if !utf16_chars.is_empty() {
-            utf16_lines.insert(line, utf16_chars);
+        // Save any wide characters seen in the last line
+        if !wide_chars.is_empty() {
+            wide_lines.insert(line, wide_chars);
         }
 
         LineIndex {
             newlines,
-            utf16_lines,
+            wide_chars: wide_lines,
         }
     }
 
@@ -117,16 +129,16 @@ impl LineIndex {
         Some(self.newlines.get(line_col.line as usize)? + TextSize::from(line_col.col))
     }
 
-    pub fn to_utf16(&self, line_col: LineCol) -> Option<LineColUtf16> {
-        let col = self.utf8_to_utf16_col(line_col.line, line_col.col.into());
-        Some(LineColUtf16 {
+    pub fn to_wide(&self, enc: WideEncoding, line_col: LineCol) -> Option<WideLineCol> {
+        let col = self.utf8_to_wide_col(enc, line_col.line, line_col.col.into());
+        Some(WideLineCol {
             line: line_col.line,
             col: col as u32,
         })
     }
 
-    pub fn to_utf8(&self, line_col: LineColUtf16) -> Option<LineCol> {
-        let col = self.utf16_to_utf8_col(line_col.line, line_col.col);
+    pub fn to_utf8(&self, enc: WideEncoding, line_col: WideLineCol) -> Option<LineCol> {
+        let col = self.wide_to_utf8_col(enc, line_col.line, line_col.col);
         Some(LineCol {
             line: line_col.line,
             col: col.into(),
@@ -146,14 +158,14 @@ impl LineIndex {
             .filter(|it| !it.is_empty())
     }
 
-    fn utf8_to_utf16_col(&self, line: u32, col: TextSize) -> usize {
+    fn utf8_to_wide_col(&self, enc: WideEncoding, line: u32, col: TextSize) -> usize {
         let mut res: usize = col.into();
-        if let Some(utf16_chars) = self.utf16_lines.get(&line) {
-            for c in utf16_chars {
+        if let Some(wide_chars) = self.wide_chars.get(&line) {
+            for c in wide_chars {
                 if c.end <= col {
-                    res -= usize::from(c.len()) - c.len_utf16();
+                    res -= usize::from(c.len()) - c.wide_len(enc);
                 } else {
-                    // From here on, all utf16 characters come *after* the character we are mapping,
+                    // From here on, all wide characters come *after* the character we are mapping,
                     // so we don't need to take them into account
                     break;
                 }
@@ -162,13 +174,13 @@ impl LineIndex {
         res
     }
 
-    fn utf16_to_utf8_col(&self, line: u32, mut col: u32) -> TextSize {
-        if let Some(utf16_chars) = self.utf16_lines.get(&line) {
-            for c in utf16_chars {
+    fn wide_to_utf8_col(&self, enc: WideEncoding, line: u32, mut col: u32) -> TextSize {
+        if let Some(wide_chars) = self.wide_chars.get(&line) {
+            for c in wide_chars {
                 if col > u32::from(c.start) {
-                    col += u32::from(c.len()) - c.len_utf16() as u32;
+                    col += u32::from(c.len()) - c.wide_len(enc) as u32;
                 } else {
-                    // From here on, all utf16 characters come *after* the character we are mapping,
+                    // From here on, all wide characters come *after* the character we are mapping,
                     // so we don't need to take them into account
                     break;
                 }
@@ -0,0 +1,85 @@
+use base_db::{semantics::bib, FeatureParams};
+use bibtex_utils::field::text::TextFieldData;
+use rowan::{ast::AstNode, TextRange, TextSize};
+use syntax::bibtex::{self, HasName, HasValue};
+use url::Url;
+
+use crate::DocumentLink;
+
+pub(super) fn find_links(params: &FeatureParams, results: &mut Vec<DocumentLink>) -> Option<()> {
+    let data = params.document.data.as_bib()?;
+    for entry in data.root_node().children().filter_map(bibtex::Entry::cast) {
+        let is_arxiv = entry.fields().any(|field| {
+            field.name_token().is_some_and(|name| {
+                let name = name.text();
+                name.eq_ignore_ascii_case("archiveprefix")
+                    || name.eq_ignore_ascii_case("eprinttype")
+            }) && field
+                .value()
+                .and_then(|value| TextFieldData::parse(&value))
+                .is_some_and(|data| data.text.eq_ignore_ascii_case("arxiv"))
+        });
+
+        for field in entry.fields() {
+            if let Some(link) = find_field_link(&field, is_arxiv) {
+                results.push(link);
+            }
+        }
+    }
+
+    Some(())
+}
+
+fn find_field_link(field: &bibtex::Field, is_arxiv: bool) -> Option<DocumentLink> {
+    let name = field.name_token()?.text().to_ascii_lowercase();
+    let value = field.value()?;
+    let text = raw_text(&value)?;
+    let text = text.trim();
+
+    let target = match name.as_str() {
+        "url" => Url::parse(text).ok()?,
+        "doi" => {
+            let doi = bib::strip_doi_prefix(text);
+            Url::parse(&format!("https://doi.org/{doi}")).ok()?
+        }
+        "eprint" if is_arxiv => {
+            let id = text.strip_prefix("arXiv:").unwrap_or(text);
+            Url::parse(&format!("https://arxiv.org/abs/{id}")).ok()?
+        }
+        _ => return None,
+    };
+
+    Some(DocumentLink {
+        range: content_range(&value),
+        target,
+    })
+}
+
+/// Takes the content of `{...}` and `"..."` verbatim, like the argument of `\url`,
+/// because characters like `~` are part of URLs and identifiers.
+fn raw_text(value: &bibtex::Value) -> Option<String> {
+    match value {
+        bibtex::Value::CurlyGroup(_) | bibtex::Value::QuoteGroup(_) => {
+            let offset = value.syntax().text_range().start();
+            let range = content_range(value) - offset;
+            Some(value.syntax().text().slice(range).to_string())
+        }
+        _ => Some(TextFieldData::parse(value)?.text),
+    }
+}
+
+/// Excludes the delimiters of `{...}` and `"..."` from the range of the link.
+fn content_range(value: &bibtex::Value) -> TextRange {
+    let range = value.syntax().text_range();
+    match value {
+        bibtex::Value::CurlyGroup(_) | bibtex::Value::QuoteGroup(_)
+            if range.len() >= TextSize::from(2) =>
+        {
+            TextRange::new(
+                range.start() + TextSize::from(1),
+                range.end() - TextSize::from(1),
+            )
+        }
+        _ => range,
+    }
+}
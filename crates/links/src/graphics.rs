@@ -0,0 +1,70 @@
+use base_db::FeatureParams;
+use rowan::ast::AstNode;
+use syntax::latex;
+use url::Url;
+
+use crate::DocumentLink;
+
+const EXTENSIONS: &[&str] = &["pdf", "png", "jpg", "jpeg", "eps", "svg"];
+
+pub(super) fn find_links(params: &FeatureParams, results: &mut Vec<DocumentLink>) -> Option<()> {
+    let data = params.document.data.as_tex()?;
+    let parent = *params
+        .workspace
+        .parents(params.document)
+        .iter()
+        .next()
+        .unwrap_or(&params.document);
+
+    let base_dir = params.workspace.current_dir(&parent.dir);
+    let graphics_paths: Vec<_> = params
+        .project
+        .documents
+        .iter()
+        .filter_map(|document| document.data.as_tex())
+        .flat_map(|data| data.semantics.graphics_paths.iter())
+        .filter_map(|path| {
+            let path = if path.ends_with('/') {
+                path.clone()
+            } else {
+                format!("{path}/")
+            };
+
+            base_dir.join(&path).ok()
+        })
+        .collect();
+
+    for include in data
+        .root_node()
+        .descendants()
+        .filter(|node| node.kind() == latex::GRAPHICS_INCLUDE)
+        .filter_map(latex::Include::cast)
+    {
+        for path in include.path_list().into_iter().flat_map(|list| list.keys()) {
+            let text = path.to_string();
+            let target = std::iter::once(&base_dir)
+                .chain(graphics_paths.iter())
+                .flat_map(|dir| candidates(dir, &text))
+                .find(|uri| exists(params, uri));
+
+            if let Some(target) = target {
+                let range = latex::small_range(&path);
+                results.push(DocumentLink { range, target });
+            }
+        }
+    }
+
+    Some(())
+}
+
+/// `\includegraphics` allows to omit the extension of the image.
+fn candidates(dir: &Url, path: &str) -> Vec<Url> {
+    std::iter::once(path.to_string())
+        .chain(EXTENSIONS.iter().map(|ext| format!("{path}.{ext}")))
+        .filter_map(|file_name| dir.join(&file_name).ok())
+        .collect()
+}
+
+fn exists(params: &FeatureParams, uri: &Url) -> bool {
+    params.workspace.lookup(uri).is_some() || uri.to_file_path().is_ok_and(|path| path.is_file())
+}
@@ -0,0 +1,59 @@
+use base_db::FeatureParams;
+use rowan::{ast::AstNode, TextRange, TextSize};
+use syntax::latex::{self, HasCurly};
+use url::Url;
+
+use crate::DocumentLink;
+
+pub(super) fn find_links(params: &FeatureParams, results: &mut Vec<DocumentLink>) -> Option<()> {
+    let data = params.document.data.as_tex()?;
+    for command in data
+        .root_node()
+        .descendants()
+        .filter_map(latex::GenericCommand::cast)
+    {
+        let Some(name) = command.name() else {
+            continue;
+        };
+
+        if !matches!(name.text(), "\\url" | "\\href") {
+            continue;
+        }
+
+        let Some(group) = command
+            .syntax()
+            .children()
+            .find_map(latex::CurlyGroup::cast)
+        else {
+            continue;
+        };
+
+        if let Some(link) = find_url(&group) {
+            results.push(link);
+        }
+    }
+
+    Some(())
+}
+
+/// The argument of `\url` is taken verbatim instead of using the words of the group.
+fn find_url(group: &latex::CurlyGroup) -> Option<DocumentLink> {
+    let start = group.left_curly()?.text_range().end();
+    let end = group.right_curly()?.text_range().start();
+    let offset = group.syntax().text_range().start();
+    let text = group
+        .syntax()
+        .text()
+        .slice(TextRange::new(start - offset, end - offset))
+        .to_string();
+
+    let url = text.trim();
+    if url.contains(['\r', '\n']) {
+        return None;
+    }
+
+    let start = start + TextSize::of(&text[..text.len() - text.trim_start().len()]);
+    let range = TextRange::at(start, TextSize::of(url));
+    let target = Url::parse(url).ok()?;
+    Some(DocumentLink { range, target })
+}
@@ -1,9 +1,9 @@
-use base_db::{DocumentLocation, FeatureParams};
+use base_db::FeatureParams;
+use itertools::Itertools;
 
-pub(super) fn find_links<'a>(
-    params: &FeatureParams<'a>,
-    results: &mut Vec<DocumentLocation<'a>>,
-) -> Option<()> {
+use crate::DocumentLink;
+
+pub(super) fn find_links(params: &FeatureParams, results: &mut Vec<DocumentLink>) -> Option<()> {
     let document = params.document;
     let parent = *params
         .workspace
@@ -14,13 +14,87 @@ pub(super) fn find_links<'a>(
 
     let graph = base_db::graph::Graph::new(params.workspace, parent);
 
+    let mut resolved = Vec::new();
     for edge in &graph.edges {
         if edge.source == document {
             if let Some(weight) = &edge.weight {
-                results.push(DocumentLocation::new(edge.target, weight.link.path.range));
+                results.push(DocumentLink {
+                    range: weight.link.path.range,
+                    target: edge.target.uri.clone(),
+                });
+                resolved.push(weight.link);
             }
         }
     }
 
+    // Paths which are computed by macros can only be resolved with the help of the `.fls` file.
+    let recorded: Vec<_> = graph
+        .edges
+        .iter()
+        .filter(|edge| edge.source.data.as_fls().is_some())
+        .map(|edge| edge.target)
+        .unique_by(|target| &target.uri)
+        .collect();
+
+    let data = document.data.as_tex()?;
+    for link in &data.semantics.links {
+        if resolved.iter().any(|other| std::ptr::eq(*other, link)) {
+            continue;
+        }
+
+        // Only the trailing components of the path which are not computed by a macro are compared.
+        let suffix: Vec<_> = link
+            .path
+            .text
+            .rsplit('/')
+            .take_while(|component| !component.contains(['\\', '{', '}']))
+            .collect();
+
+        let Some((stem, parents)) = suffix.split_first() else {
+            continue;
+        };
+
+        if stem.is_empty() {
+            continue;
+        }
+
+        let file_names: Vec<_> = std::iter::once(stem.to_string())
+            .chain(
+                link.kind
+                    .extensions()
+                    .iter()
+                    .map(|ext| format!("{stem}.{ext}")),
+            )
+            .collect();
+
+        let mut candidates = recorded.iter().filter(|target| {
+            let Some(path) = target.path.as_deref() else {
+                return false;
+            };
+
+            let mut components = path.iter().rev();
+            let name_matches = components.next().is_some_and(|name| {
+                file_names
+                    .iter()
+                    .any(|file_name| name == file_name.as_str())
+            });
+
+            name_matches
+                && parents
+                    .iter()
+                    .all(|parent| components.next().is_some_and(|name| name == *parent))
+        });
+
+        // Files with the same name in different directories cannot be told apart.
+        let target = candidates.next().filter(|_| candidates.next().is_none());
+
+        if let Some(target) = target {
+            results.push(DocumentLink {
+                range: link.path.range,
+                target: target.uri.clone(),
+            });
+        }
+    }
+
     Some(())
 }
@@ -1,10 +1,24 @@
-use base_db::{DocumentLocation, FeatureParams};
+use base_db::FeatureParams;
+use rowan::TextRange;
+use url::Url;
 
+mod entry;
+mod graphics;
+mod hyperlink;
 mod include;
 
-pub fn find_links<'a>(params: &FeatureParams<'a>) -> Vec<DocumentLocation<'a>> {
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DocumentLink {
+    pub range: TextRange,
+    pub target: Url,
+}
+
+pub fn find_links(params: &FeatureParams) -> Vec<DocumentLink> {
     let mut results = Vec::new();
     include::find_links(params, &mut results);
+    graphics::find_links(params, &mut results);
+    hyperlink::find_links(params, &mut results);
+    entry::find_links(params, &mut results);
     results
 }
 
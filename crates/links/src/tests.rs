@@ -16,7 +16,7 @@ fn check(input: &str, expect: Expect) {
 
     let actual_targets = links
         .iter()
-        .map(|link| link.document.uri.as_str())
+        .map(|link| link.target.as_str())
         .collect::<Vec<_>>();
 
     expect.assert_debug_eq(&actual_targets);
@@ -55,3 +55,175 @@ fn test_document_import() {
         "#]],
     );
 }
+
+#[test]
+fn test_recorded_include() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\newcommand{\chapterdir}{chapters}
+\begin{document}
+\input{\chapterdir/intro}
+       ^^^^^^^^^^^^^^^^^
+\end{document}
+
+%! main.fls
+PWD /texlab
+INPUT main.tex
+INPUT ./chapters/intro.tex
+OUTPUT main.aux
+
+%! chapters/intro.tex
+Foo"#,
+        expect![[r#"
+            [
+                "file:///texlab/chapters/intro.tex",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_recorded_include_suffix() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\newcommand{\partdir}{parts}
+\begin{document}
+\input{\partdir/a/intro}
+       ^^^^^^^^^^^^^^^^
+\end{document}
+
+%! main.fls
+PWD /texlab
+INPUT main.tex
+INPUT ./parts/a/intro.tex
+INPUT ./parts/b/intro.tex
+OUTPUT main.aux
+
+%! parts/a/intro.tex
+Foo
+
+%! parts/b/intro.tex
+Bar"#,
+        expect![[r#"
+            [
+                "file:///texlab/parts/a/intro.tex",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_recorded_include_ambiguous() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\newcommand{\partdir}{parts/a}
+\begin{document}
+\input{\partdir/intro}
+\end{document}
+
+%! main.fls
+PWD /texlab
+INPUT main.tex
+INPUT ./parts/a/intro.tex
+INPUT ./parts/b/intro.tex
+OUTPUT main.aux
+
+%! parts/a/intro.tex
+Foo
+
+%! parts/b/intro.tex
+Bar"#,
+        expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn test_graphics() {
+    check(
+        r#"
+%! main.tex
+\graphicspath{{img}}
+\includegraphics{plot}
+                 ^^^^
+
+%! img/plot.png"#,
+        expect![[r#"
+            [
+                "file:///texlab/img/plot.png",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_url() {
+    check(
+        r#"
+%! main.tex
+\url{https://example.org/a_b#c}
+     ^^^^^^^^^^^^^^^^^^^^^^^^^
+\href{ https://example.org }{Example}
+       ^^^^^^^^^^^^^^^^^^^"#,
+        expect![[r#"
+            [
+                "https://example.org/a_b#c",
+                "https://example.org/",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_bibtex_fields() {
+    check(
+        r#"
+%! main.bib
+@article{foo,
+    url = {https://example.org},
+           ^^^^^^^^^^^^^^^^^^^
+    doi = "10.1000/182",
+           ^^^^^^^^^^^
+    eprint = {2101.00001},
+              ^^^^^^^^^^
+    archivePrefix = {arXiv},
+}
+@article{bar,
+    eprint = {2101.00002},
+}"#,
+        expect![[r#"
+            [
+                "https://example.org/",
+                "https://doi.org/10.1000/182",
+                "https://arxiv.org/abs/2101.00001",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_bibtex_url_verbatim() {
+    check(
+        r#"
+%! main.bib
+@misc{foo,
+    url = {https://example.org/~foo/a%20b},
+           ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    doi = {10.1002/(SICI)1097-4571(199806)49:8<693::AID-ASI4>3.0.CO;2-0},
+           ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+}"#,
+        expect![[r#"
+            [
+                "https://example.org/~foo/a%20b",
+                "https://doi.org/10.1002/(SICI)1097-4571(199806)49:8%3C693::AID-ASI4%3E3.0.CO;2-0",
+            ]
+        "#]],
+    );
+}
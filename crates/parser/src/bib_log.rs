@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use syntax::{BuildError, BuildErrorLevel, BuildLog};
+
+static BIBTEX_LOCATION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("^(?P<msg>.*?)-{2,3}line (?P<line>\\d+) of file (?P<file>.+?)\\s*$").unwrap()
+});
+
+static BIBER_MESSAGE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^\\[\\d+\\] [^>]*> (?P<level>ERROR|WARN) - (?P<msg>.*)$").unwrap());
+
+static BIBER_SYNTAX_ERROR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("BibTeX subsystem: (?P<file>.+?)(_\\d+)?\\.utf8, line (?P<line>\\d+), (?P<msg>.*)$")
+        .unwrap()
+});
+
+static BIBER_ENTRY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("Entry '[^']*' \\((?P<file>[^()]+\\.bib)\\)").unwrap());
+
+/// Extracts the errors and warnings of a BibTeX or biber `.blg` file.
+///
+/// Messages which do not refer to a specific line of a database file
+/// are reported with an empty path, so they are shown on the root document.
+pub fn parse_bib_log(log: &str) -> BuildLog {
+    if log
+        .lines()
+        .take(5)
+        .any(|line| line.contains("This is Biber"))
+    {
+        parse_biber_log(log)
+    } else {
+        parse_bibtex_log(log)
+    }
+}
+
+fn parse_bibtex_log(log: &str) -> BuildLog {
+    let mut errors: Vec<BuildError> = Vec::new();
+    let mut previous_line = "";
+    for line in log.lines() {
+        if let Some(message) = line.strip_prefix("Warning--") {
+            errors.push(BuildError {
+                relative_path: PathBuf::new(),
+                level: BuildErrorLevel::Warning,
+                message: message.into(),
+                hint: None,
+                line: None,
+            });
+        } else if let Some(captures) = BIBTEX_LOCATION_REGEX.captures(line) {
+            let location_only = captures["msg"].trim().is_empty();
+            let relative_path = PathBuf::from(&captures["file"]);
+            let line_number = captures["line"]
+                .parse::<u32>()
+                .ok()
+                .map(|n| n.saturating_sub(1));
+
+            match errors.last_mut() {
+                // A warning like `Warning--empty journal in foo` is followed by its location.
+                Some(error)
+                    if location_only
+                        && error.level == BuildErrorLevel::Warning
+                        && error.line.is_none()
+                        && previous_line.starts_with("Warning--") =>
+                {
+                    error.relative_path = relative_path;
+                    error.line = line_number;
+                }
+                _ => {
+                    let message = if location_only {
+                        previous_line.trim()
+                    } else {
+                        captures["msg"].trim()
+                    };
+
+                    errors.push(BuildError {
+                        relative_path,
+                        level: BuildErrorLevel::Error,
+                        message: message.into(),
+                        hint: None,
+                        line: line_number,
+                    });
+                }
+            };
+        }
+
+        previous_line = line;
+    }
+
+    BuildLog { errors }
+}
+
+fn parse_biber_log(log: &str) -> BuildLog {
+    let mut errors = Vec::new();
+    for captures in log
+        .lines()
+        .filter_map(|line| BIBER_MESSAGE_REGEX.captures(line))
+    {
+        let level = match &captures["level"] {
+            "ERROR" => BuildErrorLevel::Error,
+            _ => BuildErrorLevel::Warning,
+        };
+
+        let message = &captures["msg"];
+        let error = if let Some(syntax_error) = BIBER_SYNTAX_ERROR_REGEX.captures(message) {
+            // Biber reports the location inside of a temporary copy of the database.
+            let file_name = PathBuf::from(&syntax_error["file"])
+                .file_name()
+                .map(PathBuf::from)
+                .unwrap_or_default();
+
+            BuildError {
+                relative_path: file_name,
+                level,
+                message: syntax_error["msg"].into(),
+                hint: None,
+                line: syntax_error["line"]
+                    .parse::<u32>()
+                    .ok()
+                    .map(|n| n.saturating_sub(1)),
+            }
+        } else {
+            let relative_path = BIBER_ENTRY_REGEX
+                .captures(message)
+                .map(|entry| PathBuf::from(&entry["file"]))
+                .unwrap_or_default();
+
+            BuildError {
+                relative_path,
+                level,
+                message: message.into(),
+                hint: None,
+                line: None,
+            }
+        };
+
+        errors.push(error);
+    }
+
+    BuildLog { errors }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,114 @@
+use expect_test::{expect, Expect};
+
+use crate::parse_bib_log;
+
+fn check(input: &str, expect: Expect) {
+    expect.assert_debug_eq(&parse_bib_log(input));
+}
+
+#[test]
+fn test_bibtex() {
+    check(
+        r#"This is BibTeX, Version 0.99d (TeX Live 2023)
+Capacity: max_strings=200000, hash_size=200000, hash_prime=170003
+The top-level auxiliary file: main.aux
+The style file: plain.bst
+Database file #1: refs.bib
+I was expecting a `,' or a `}'---line 42 of file refs.bib
+ :
+ :   title = {Foo}
+(Error may have been on previous line)
+I'm skipping whatever remains of this entry
+Warning--I didn't find a database entry for "foo"
+Warning--empty journal in bar
+--line 12 of file refs.bib
+I couldn't open database file missing.bib
+---line 3 of file main.aux
+ : \bibdata{refs,missing
+ :                      }
+(There were 2 error messages)
+"#,
+        expect![[r#"
+            BuildLog {
+                errors: [
+                    BuildError {
+                        relative_path: "refs.bib",
+                        level: Error,
+                        message: "I was expecting a `,' or a `}'",
+                        hint: None,
+                        line: Some(
+                            41,
+                        ),
+                    },
+                    BuildError {
+                        relative_path: "",
+                        level: Warning,
+                        message: "I didn't find a database entry for \"foo\"",
+                        hint: None,
+                        line: None,
+                    },
+                    BuildError {
+                        relative_path: "refs.bib",
+                        level: Warning,
+                        message: "empty journal in bar",
+                        hint: None,
+                        line: Some(
+                            11,
+                        ),
+                    },
+                    BuildError {
+                        relative_path: "main.aux",
+                        level: Error,
+                        message: "I couldn't open database file missing.bib",
+                        hint: None,
+                        line: Some(
+                            2,
+                        ),
+                    },
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_biber() {
+    check(
+        r#"[0] Config.pm:307> INFO - This is Biber 2.19
+[0] Config.pm:310> INFO - Logfile is 'main.blg'
+[50] Utils.pm:411> ERROR - BibTeX subsystem: /tmp/biber_tmp_Xyz1/refs.bib_12345.utf8, line 5, syntax error: at end of input, expected end of entry ("}" or ")") (skipping to next "@")
+[61] Utils.pm:395> WARN - Datamodel: Entry 'foo' (refs.bib): Invalid field 'publisher' for entrytype 'article'
+[62] Biber.pm:131> WARN - I didn't find a database entry for 'bar' (section 0)
+[70] Biber.pm:133> INFO - WARNINGS: 2
+"#,
+        expect![[r#"
+            BuildLog {
+                errors: [
+                    BuildError {
+                        relative_path: "refs.bib",
+                        level: Error,
+                        message: "syntax error: at end of input, expected end of entry (\"}\" or \")\") (skipping to next \"@\")",
+                        hint: None,
+                        line: Some(
+                            4,
+                        ),
+                    },
+                    BuildError {
+                        relative_path: "refs.bib",
+                        level: Warning,
+                        message: "Datamodel: Entry 'foo' (refs.bib): Invalid field 'publisher' for entrytype 'article'",
+                        hint: None,
+                        line: None,
+                    },
+                    BuildError {
+                        relative_path: "",
+                        level: Warning,
+                        message: "I didn't find a database entry for 'bar' (section 0)",
+                        hint: None,
+                        line: None,
+                    },
+                ],
+            }
+        "#]],
+    );
+}
@@ -0,0 +1,32 @@
+use rustc_hash::FxHashSet;
+use syntax::fls::FlsData;
+
+/// Parses the `PWD`, `INPUT` and `OUTPUT` records of a `.fls` file.
+/// Files which are recorded multiple times are only reported once.
+pub fn parse_fls(input: &str) -> FlsData {
+    let mut data = FlsData::default();
+    let mut seen_inputs = FxHashSet::default();
+    let mut seen_outputs = FxHashSet::default();
+    for line in input.lines() {
+        let Some((kind, path)) = line.trim_end().split_once(' ') else {
+            continue;
+        };
+
+        let path = path.trim();
+        if path.is_empty() {
+            continue;
+        }
+
+        match kind {
+            "PWD" => data.pwd = Some(path.into()),
+            "INPUT" if seen_inputs.insert(path) => data.inputs.push(path.into()),
+            "OUTPUT" if seen_outputs.insert(path) => data.outputs.push(path.into()),
+            _ => {}
+        }
+    }
+
+    data
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,65 @@
+use expect_test::{expect, Expect};
+
+use crate::parse_fls;
+
+fn check(input: &str, expect: Expect) {
+    expect.assert_debug_eq(&parse_fls(input));
+}
+
+#[test]
+fn test_records() {
+    check(
+        r#"PWD /home/user/thesis
+INPUT /usr/share/texlive/texmf-dist/web2c/texmf.cnf
+INPUT /usr/share/texlive/texmf-dist/tex/latex/base/article.cls
+INPUT main.tex
+OUTPUT main.log
+INPUT ./chapters/intro.tex
+INPUT ./chapters/intro.tex
+INPUT main.aux
+OUTPUT main.aux
+OUTPUT main.pdf
+"#,
+        expect![[r#"
+            FlsData {
+                pwd: Some(
+                    "/home/user/thesis",
+                ),
+                inputs: [
+                    "/usr/share/texlive/texmf-dist/web2c/texmf.cnf",
+                    "/usr/share/texlive/texmf-dist/tex/latex/base/article.cls",
+                    "main.tex",
+                    "./chapters/intro.tex",
+                    "main.aux",
+                ],
+                outputs: [
+                    "main.log",
+                    "main.aux",
+                    "main.pdf",
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_paths_with_spaces() {
+    check(
+        r#"PWD C:\Users\Jane Doe\thesis
+INPUT C:\Users\Jane Doe\thesis\my chapter.tex
+UNKNOWN foo
+INPUT
+"#,
+        expect![[r#"
+            FlsData {
+                pwd: Some(
+                    "C:\\Users\\Jane Doe\\thesis",
+                ),
+                inputs: [
+                    "C:\\Users\\Jane Doe\\thesis\\my chapter.tex",
+                ],
+                outputs: [],
+            }
+        "#]],
+    );
+}
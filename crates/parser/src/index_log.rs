@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use syntax::{BuildError, BuildErrorLevel, BuildLog};
+
+static MAKEINDEX_MESSAGE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("^(?P<kind>!!|##) [^(]*\\((file|input) = (?P<file>[^,]+), line = (?P<line>\\d+)")
+        .unwrap()
+});
+
+static XINDY_MESSAGE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^(?P<level>ERROR|WARNING): (?P<msg>.*)$").unwrap());
+
+/// Extracts the errors and warnings of a `makeindex` or `xindy` `.ilg` file.
+///
+/// The lines refer to the generated `.idx` file instead of the TeX document,
+/// so the location is kept as part of the message and the error is reported at the root document.
+pub fn parse_index_log(log: &str) -> BuildLog {
+    let mut errors = Vec::new();
+    let mut lines = log.lines().peekable();
+    while let Some(line) = lines.next() {
+        if let Some(captures) = MAKEINDEX_MESSAGE_REGEX.captures(line) {
+            let level = match &captures["kind"] {
+                "!!" => BuildErrorLevel::Error,
+                _ => BuildErrorLevel::Warning,
+            };
+
+            let reason = lines
+                .next_if(|next| next.trim_start().starts_with("--"))
+                .and_then(|next| next.trim_start().strip_prefix("--"))
+                .map_or("Index error", str::trim);
+
+            let file = &captures["file"];
+            errors.push(BuildError {
+                relative_path: PathBuf::new(),
+                level,
+                message: format!("{reason} (index file {file}, line {})", &captures["line"]),
+                hint: None,
+                line: None,
+            });
+        } else if let Some(captures) = XINDY_MESSAGE_REGEX.captures(line) {
+            let level = match &captures["level"] {
+                "ERROR" => BuildErrorLevel::Error,
+                _ => BuildErrorLevel::Warning,
+            };
+
+            errors.push(BuildError {
+                relative_path: PathBuf::new(),
+                level,
+                message: captures["msg"].into(),
+                hint: None,
+                line: None,
+            });
+        }
+    }
+
+    BuildLog { errors }
+}
+
+#[cfg(test)]
+mod tests;
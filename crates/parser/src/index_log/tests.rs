@@ -0,0 +1,75 @@
+use expect_test::{expect, Expect};
+
+use crate::parse_index_log;
+
+fn check(input: &str, expect: Expect) {
+    expect.assert_debug_eq(&parse_index_log(input));
+}
+
+#[test]
+fn test_makeindex() {
+    check(
+        r#"This is makeindex, version 2.16 [TeX Live 2023] (kpathsea + Thai support).
+Scanning input file main.idx...
+!! Input index error (file = main.idx, line = 3):
+   -- Extra `@' at position 12 of first argument.
+done (5 entries accepted, 1 rejected).
+Sorting entries....done (12 comparisons).
+Generating output file main.ind....
+## Warning (input = main.idx, line = 9; output = main.ind, line = 13):
+   -- Unmatched range closing operator ).
+done (20 lines written, 1 warning).
+"#,
+        expect![[r#"
+            BuildLog {
+                errors: [
+                    BuildError {
+                        relative_path: "",
+                        level: Error,
+                        message: "Extra `@' at position 12 of first argument. (index file main.idx, line 3)",
+                        hint: None,
+                        line: None,
+                    },
+                    BuildError {
+                        relative_path: "",
+                        level: Warning,
+                        message: "Unmatched range closing operator ). (index file main.idx, line 9)",
+                        hint: None,
+                        line: None,
+                    },
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_xindy() {
+    check(
+        r#"xindy release: 2.5.1
+Loading module "./main.xdy"...
+WARNING: unknown cross-reference-class `see'! (ignored)
+ERROR: CHAR: index 0 should be less than the length of the string
+"#,
+        expect![[r#"
+            BuildLog {
+                errors: [
+                    BuildError {
+                        relative_path: "",
+                        level: Warning,
+                        message: "unknown cross-reference-class `see'! (ignored)",
+                        hint: None,
+                        line: None,
+                    },
+                    BuildError {
+                        relative_path: "",
+                        level: Error,
+                        message: "CHAR: index 0 should be less than the length of the string",
+                        hint: None,
+                        line: None,
+                    },
+                ],
+            }
+        "#]],
+    );
+}
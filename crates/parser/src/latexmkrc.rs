@@ -1,7 +1,171 @@
 use syntax::latexmkrc::LatexmkrcData;
 use tempfile::tempdir;
 
-pub fn parse_latexmkrc(_input: &str) -> std::io::Result<LatexmkrcData> {
+/// Evaluates the common assignments of a `latexmkrc` file.
+///
+/// Only literal values are understood. If the output directories are computed
+/// by arbitrary Perl code, `latexmk` is asked to report them instead.
+pub fn parse_latexmkrc(input: &str) -> LatexmkrcData {
+    let mut data = LatexmkrcData::default();
+    let mut needs_dir_report = false;
+    for statement in split_statements(input) {
+        match parse_assignment(&statement) {
+            Some(("$out_dir", Value::Scalar(dir))) => data.out_dir = Some(dir),
+            Some(("$aux_dir", Value::Scalar(dir))) => data.aux_dir = Some(dir),
+            Some(("$pdf_mode", value)) => {
+                data.pdf_mode = value.into_scalar().and_then(|mode| mode.parse().ok());
+            }
+            Some(("$jobname", value)) => data.jobname = value.into_scalar(),
+            Some(("$pdflatex", value)) => data.pdflatex = value.into_scalar(),
+            Some(("$lualatex", value)) => data.lualatex = value.into_scalar(),
+            Some(("@default_files", value)) => {
+                data.default_files = value.into_list().unwrap_or_default();
+            }
+            _ if statement.contains("$out_dir") || statement.contains("$aux_dir") => {
+                needs_dir_report = true;
+            }
+            _ => {}
+        }
+    }
+
+    if needs_dir_report {
+        if let Ok((aux_dir, out_dir)) = dir_report() {
+            data.aux_dir = Some(aux_dir);
+            data.out_dir = Some(out_dir);
+        }
+    }
+
+    data
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Value {
+    Scalar(String),
+    List(Vec<String>),
+    Unknown,
+}
+
+impl Value {
+    fn into_scalar(self) -> Option<String> {
+        match self {
+            Self::Scalar(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn into_list(self) -> Option<Vec<String>> {
+        match self {
+            Self::List(items) => Some(items),
+            Self::Scalar(item) => Some(vec![item]),
+            Self::Unknown => None,
+        }
+    }
+}
+
+/// Splits the input at semicolons while dropping comments.
+fn split_statements(input: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(_), '\\') => {
+                current.push(c);
+                current.extend(chars.next());
+            }
+            (Some(q), c) if q == c => {
+                quote = None;
+                current.push(c);
+            }
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                current.push(c);
+            }
+            (None, '#') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        current.push(c);
+                        break;
+                    }
+                }
+            }
+            (None, ';') => statements.push(std::mem::take(&mut current)),
+            (None, c) => current.push(c),
+        }
+    }
+
+    statements.push(current);
+    statements
+}
+
+fn parse_assignment(statement: &str) -> Option<(&str, Value)> {
+    let (variable, value) = statement.split_once('=')?;
+    let variable = variable.trim();
+    let name = variable.strip_prefix(['$', '@'])?;
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    Some((variable, parse_value(value.trim())))
+}
+
+fn parse_value(text: &str) -> Value {
+    if let Some(items) = text
+        .strip_prefix("qw")
+        .map(str::trim_start)
+        .and_then(|text| text.strip_prefix('('))
+        .and_then(|text| text.strip_suffix(')'))
+    {
+        return Value::List(items.split_whitespace().map(String::from).collect());
+    }
+
+    if let Some(items) = text
+        .strip_prefix('(')
+        .and_then(|text| text.strip_suffix(')'))
+    {
+        let items: Option<Vec<String>> = items
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(parse_literal)
+            .collect();
+
+        return items.map_or(Value::Unknown, Value::List);
+    }
+
+    parse_literal(text).map_or(Value::Unknown, Value::Scalar)
+}
+
+fn parse_literal(text: &str) -> Option<String> {
+    if let Some(text) = text.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')) {
+        return Some(text.replace("\\'", "'").replace("\\\\", "\\"));
+    }
+
+    if let Some(text) = text.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+        // Interpolated variables cannot be evaluated without running Perl.
+        let mut result = String::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => result.extend(chars.next()),
+                '$' | '@' => return None,
+                c => result.push(c),
+            }
+        }
+
+        return Some(result);
+    }
+
+    if !text.is_empty() && text.chars().all(|c| c.is_ascii_digit()) {
+        return Some(text.into());
+    }
+
+    None
+}
+
+fn dir_report() -> std::io::Result<(String, String)> {
     let temp_dir = tempdir()?;
     let non_existent_tex = temp_dir.path().join("NONEXISTENT.tex");
 
@@ -19,16 +183,11 @@ pub fn parse_latexmkrc(_input: &str) -> std::io::Result<LatexmkrcData> {
 
     let stderr = String::from_utf8_lossy(&output.stderr);
 
-    let (aux_dir, out_dir) = stderr.lines().find_map(extract_dirs).ok_or_else(|| {
+    stderr.lines().find_map(extract_dirs).ok_or_else(|| {
         std::io::Error::new(
             std::io::ErrorKind::InvalidData,
             "Normalized aux and out dir were not found in latexmk output",
         )
-    })?;
-
-    Ok(LatexmkrcData {
-        aux_dir: Some(aux_dir),
-        out_dir: Some(out_dir),
     })
 }
 
@@ -50,3 +209,6 @@ fn extract_dirs(line: &str) -> Option<(String, String)> {
 
     Some((String::from(aux_dir), String::from(out_dir)))
 }
+
+#[cfg(test)]
+mod tests;
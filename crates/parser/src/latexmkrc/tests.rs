@@ -0,0 +1,130 @@
+use expect_test::{expect, Expect};
+
+use crate::parse_latexmkrc;
+
+fn check(input: &str, expect: Expect) {
+    expect.assert_debug_eq(&parse_latexmkrc(input));
+}
+
+#[test]
+fn test_dirs() {
+    check(
+        r#"
+$out_dir = 'build'; # comment with 'quotes'
+$aux_dir = "build/aux";
+"#,
+        expect![[r#"
+            LatexmkrcData {
+                aux_dir: Some(
+                    "build/aux",
+                ),
+                out_dir: Some(
+                    "build",
+                ),
+                pdf_mode: None,
+                default_files: [],
+                jobname: None,
+                pdflatex: None,
+                lualatex: None,
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_commands() {
+    check(
+        r#"
+$pdf_mode = 4;
+$pdflatex = 'pdflatex -synctex=1 -interaction=nonstopmode %O %S';
+$lualatex = "lualatex --shell-escape %O %S";
+$jobname = "%A-draft";
+"#,
+        expect![[r#"
+            LatexmkrcData {
+                aux_dir: None,
+                out_dir: None,
+                pdf_mode: Some(
+                    4,
+                ),
+                default_files: [],
+                jobname: Some(
+                    "%A-draft",
+                ),
+                pdflatex: Some(
+                    "pdflatex -synctex=1 -interaction=nonstopmode %O %S",
+                ),
+                lualatex: Some(
+                    "lualatex --shell-escape %O %S",
+                ),
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_default_files() {
+    check(
+        r#"
+@default_files = ('main.tex', "appendix.tex");
+"#,
+        expect![[r#"
+            LatexmkrcData {
+                aux_dir: None,
+                out_dir: None,
+                pdf_mode: None,
+                default_files: [
+                    "main.tex",
+                    "appendix.tex",
+                ],
+                jobname: None,
+                pdflatex: None,
+                lualatex: None,
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_default_files_qw() {
+    check(
+        r#"
+@default_files = qw(main.tex slides.tex);
+"#,
+        expect![[r#"
+            LatexmkrcData {
+                aux_dir: None,
+                out_dir: None,
+                pdf_mode: None,
+                default_files: [
+                    "main.tex",
+                    "slides.tex",
+                ],
+                jobname: None,
+                pdflatex: None,
+                lualatex: None,
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_unsupported() {
+    check(
+        r#"
+$clean_ext = "bbl $ENV{HOME}";
+$jobname = "$ENV{USER}";
+"#,
+        expect![[r#"
+            LatexmkrcData {
+                aux_dir: None,
+                out_dir: None,
+                pdf_mode: None,
+                default_files: [],
+                jobname: None,
+                pdflatex: None,
+                lualatex: None,
+            }
+        "#]],
+    );
+}
@@ -1,10 +1,18 @@
+mod bib_log;
 mod bibtex;
 mod build_log;
 mod config;
+mod fls;
+mod index_log;
 mod latex;
 mod latexmkrc;
+mod tectonic;
 
 pub use self::{
-    bibtex::parse_bibtex, build_log::parse_build_log, config::*, latex::parse_latex,
-    latexmkrc::parse_latexmkrc,
+    bib_log::parse_bib_log, bibtex::parse_bibtex, build_log::parse_build_log, config::*,
+    fls::parse_fls, index_log::parse_index_log, latex::parse_latex, latexmkrc::parse_latexmkrc,
+    tectonic::parse_tectonic,
 };
+
+/// The version of the parsers, which is part of the keys of the persistent caches.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,65 @@
+use serde::Deserialize;
+use syntax::tectonic::{TectonicData, TectonicOutput};
+
+/// Extracts the build outputs from a `Tectonic.toml` file.
+/// An invalid manifest does not have any outputs.
+pub fn parse_tectonic(input: &str) -> TectonicData {
+    let outputs = toml::from_str::<Manifest>(input)
+        .map(|manifest| manifest.output.into_iter().map(Output::finish).collect())
+        .unwrap_or_default();
+
+    TectonicData { outputs }
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    output: Vec<Output>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Output {
+    name: Option<String>,
+    #[serde(rename = "type")]
+    output_type: Option<String>,
+    inputs: Option<Vec<Input>>,
+    preamble: Option<String>,
+    index: Option<String>,
+    postamble: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Input {
+    File(String),
+    /// Inline tables (e.g. `{ inline = "..." }`) do not refer to files.
+    Other(serde::de::IgnoredAny),
+}
+
+impl Output {
+    fn finish(self) -> TectonicOutput {
+        let inputs = match self.inputs {
+            Some(inputs) => inputs
+                .into_iter()
+                .filter_map(|input| match input {
+                    Input::File(path) => Some(path),
+                    Input::Other(_) => None,
+                })
+                .collect(),
+            None => vec![
+                self.preamble.unwrap_or_else(|| "_preamble.tex".into()),
+                self.index.unwrap_or_else(|| "index.tex".into()),
+                self.postamble.unwrap_or_else(|| "_postamble.tex".into()),
+            ],
+        };
+
+        TectonicOutput {
+            name: self.name.unwrap_or_else(|| "default".into()),
+            output_type: self.output_type.unwrap_or_else(|| "pdf".into()),
+            inputs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,150 @@
+use expect_test::{expect, Expect};
+
+use crate::parse_tectonic;
+
+fn check(input: &str, expect: Expect) {
+    expect.assert_debug_eq(&parse_tectonic(input));
+}
+
+#[test]
+fn test_default_inputs() {
+    check(
+        r#"
+[doc]
+name = "book"
+bundle = "https://data1.fullyjustified.net/tlextras-2022.0r0.tar"
+
+[[output]]
+name = "default"
+type = "pdf"
+"#,
+        expect![[r#"
+            TectonicData {
+                outputs: [
+                    TectonicOutput {
+                        name: "default",
+                        output_type: "pdf",
+                        inputs: [
+                            "_preamble.tex",
+                            "index.tex",
+                            "_postamble.tex",
+                        ],
+                    },
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_preamble_postamble() {
+    check(
+        r#"
+[[output]]
+name = 'paper' # comment
+type = "pdf"
+preamble = "pre.tex"
+index = "main.tex"
+postamble = "post.tex"
+"#,
+        expect![[r#"
+            TectonicData {
+                outputs: [
+                    TectonicOutput {
+                        name: "paper",
+                        output_type: "pdf",
+                        inputs: [
+                            "pre.tex",
+                            "main.tex",
+                            "post.tex",
+                        ],
+                    },
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_inputs() {
+    check(
+        r#"
+[[output]]
+name = "slides"
+type = "pdf"
+inputs = [
+    "_preamble.tex", # shared preamble
+    { inline = "\\newcommand{\\foo}{bar}" },
+    "slides.tex",
+]
+
+[[output]]
+name = "notes"
+type = "html"
+inputs = ["notes.tex"]
+"#,
+        expect![[r#"
+            TectonicData {
+                outputs: [
+                    TectonicOutput {
+                        name: "slides",
+                        output_type: "pdf",
+                        inputs: [
+                            "_preamble.tex",
+                            "slides.tex",
+                        ],
+                    },
+                    TectonicOutput {
+                        name: "notes",
+                        output_type: "html",
+                        inputs: [
+                            "notes.tex",
+                        ],
+                    },
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_toml_syntax() {
+    check(
+        r#"
+doc.name = "book"
+output = [
+    { name = """multi
+line""", type = 'html', inputs = ['''main.tex''', "dir/\"quoted\".tex"] },
+]
+"#,
+        expect![[r#"
+            TectonicData {
+                outputs: [
+                    TectonicOutput {
+                        name: "multi\nline",
+                        output_type: "html",
+                        inputs: [
+                            "main.tex",
+                            "dir/\"quoted\".tex",
+                        ],
+                    },
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_invalid() {
+    check(
+        r#"
+[[output]
+name = "default"
+"#,
+        expect![[r#"
+            TectonicData {
+                outputs: [],
+            }
+        "#]],
+    );
+}
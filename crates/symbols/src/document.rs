@@ -1,7 +1,7 @@
 mod bib;
 mod tex;
 
-use base_db::{util, Document, DocumentData, SymbolConfig, Workspace};
+use base_db::{util, Document, DocumentData, SymbolConfig, TexDocumentData, Workspace};
 
 use crate::Symbol;
 
@@ -9,7 +9,8 @@ pub fn document_symbols(workspace: &Workspace, document: &Document) -> Vec<Symbo
     let project = workspace.project(document);
     let mut symbols = match &document.data {
         DocumentData::Tex(data) => {
-            let builder = tex::SymbolBuilder::new(&project, workspace.config());
+            let frame_offset = count_previous_frames(workspace, document, data);
+            let builder = tex::SymbolBuilder::new(&project, workspace.config(), frame_offset);
             builder.visit(&data.root_node())
         }
         DocumentData::Bib(data) => {
@@ -21,15 +22,34 @@ pub fn document_symbols(workspace: &Workspace, document: &Document) -> Vec<Symbo
         }
         DocumentData::Aux(_)
         | DocumentData::Log(_)
+        | DocumentData::Fls(_)
         | DocumentData::Root
         | DocumentData::Latexmkrc(_)
-        | DocumentData::Tectonic => Vec::new(),
+        | DocumentData::Tectonic(_) => Vec::new(),
     };
 
     filter_symbols(&mut symbols, &workspace.config().symbols);
     symbols
 }
 
+/// Counts the frames of the project which are typeset before the frames of the document.
+fn count_previous_frames(
+    workspace: &Workspace,
+    document: &Document,
+    data: &TexDocumentData,
+) -> usize {
+    if util::find_frames(&data.root_node()).next().is_none() {
+        return 0;
+    }
+
+    let parents = workspace.parents(document);
+    let root = parents.iter().next().copied().unwrap_or(document);
+    util::find_project_frames(workspace, root)
+        .iter()
+        .position(|(other, _)| *other == document)
+        .unwrap_or(0)
+}
+
 fn filter_symbols(container: &mut Vec<Symbol>, config: &SymbolConfig) {
     let allowed = &config.allowed_patterns;
     let ignored = &config.ignored_patterns;
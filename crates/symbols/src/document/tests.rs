@@ -625,3 +625,103 @@ fn test_ignored_patterns() {
     "#]],
     );
 }
+
+#[test]
+fn test_beamer_frames() {
+    let fixture = Fixture::parse(
+        r#"
+%! main.tex
+\documentclass{beamer}
+\begin{document}
+\section{Intro}
+\begin{frame}{Hello \pause World}
+\end{frame}
+\begin{frame}<2->[fragile]
+    \frametitle<2>{Only \alert<3>{this}}
+    \label{fr:foo}
+\end{frame}
+\begin{frame}
+\end{frame}
+\end{document}"#,
+    );
+
+    check(
+        &fixture,
+        expect![[r#"
+        [
+            Symbol {
+                name: "Intro",
+                kind: Section,
+                label: None,
+                full_range: 40..226,
+                selection_range: 40..226,
+                children: [
+                    Symbol {
+                        name: "Frame 1: Hello World",
+                        kind: Frame,
+                        label: None,
+                        full_range: 56..101,
+                        selection_range: 56..101,
+                        children: [],
+                    },
+                    Symbol {
+                        name: "Frame 2: Only \\alert{this}",
+                        kind: Frame,
+                        label: Some(
+                            Span(
+                                "fr:foo",
+                                174..188,
+                            ),
+                        ),
+                        full_range: 102..200,
+                        selection_range: 174..188,
+                        children: [],
+                    },
+                    Symbol {
+                        name: "Frame 3",
+                        kind: Frame,
+                        label: None,
+                        full_range: 201..226,
+                        selection_range: 201..226,
+                        children: [],
+                    },
+                ],
+            },
+        ]
+    "#]],
+    );
+}
+
+#[test]
+fn test_beamer_frames_project() {
+    let fixture = Fixture::parse(
+        r#"
+%! slides.tex
+\begin{frame}{Bar}
+\end{frame}
+
+%! main.tex
+\documentclass{beamer}
+\begin{document}
+\begin{frame}{Foo}
+\end{frame}
+\input{slides}
+\end{document}"#,
+    );
+
+    check(
+        &fixture,
+        expect![[r#"
+        [
+            Symbol {
+                name: "Frame 2: Bar",
+                kind: Frame,
+                label: None,
+                full_range: 0..30,
+                selection_range: 0..30,
+                children: [],
+            },
+        ]
+    "#]],
+    );
+}
@@ -1,6 +1,10 @@
-use std::str::FromStr;
+use std::{cell::Cell, str::FromStr};
 
-use base_db::{semantics::Span, util::FloatKind, Config, Project};
+use base_db::{
+    semantics::Span,
+    util::{FloatKind, Frame},
+    Config, Project,
+};
 use rowan::ast::AstNode;
 use syntax::latex::{self, HasBrack, HasCurly, LatexLanguage};
 use titlecase::titlecase;
@@ -11,11 +15,18 @@ use crate::{Symbol, SymbolKind};
 pub struct SymbolBuilder<'a> {
     project: &'a Project<'a>,
     config: &'a Config,
+    frame_count: Cell<usize>,
 }
 
 impl<'a> SymbolBuilder<'a> {
-    pub fn new(project: &'a Project<'a>, config: &'a Config) -> Self {
-        Self { project, config }
+    /// The frames are numbered over the whole project,
+    /// so the number of frames in the preceding documents has to be passed.
+    pub fn new(project: &'a Project<'a>, config: &'a Config, frame_offset: usize) -> Self {
+        Self {
+            project,
+            config,
+            frame_count: Cell::new(frame_offset),
+        }
     }
 
     pub fn visit(&self, node: &latex::SyntaxNode) -> Vec<Symbol> {
@@ -34,6 +45,8 @@ impl<'a> SymbolBuilder<'a> {
                     self.visit_enumeration(&environment, &name)
                 } else if let Ok(float_kind) = FloatKind::from_str(&name) {
                     self.visit_float(&environment, float_kind)
+                } else if let Some(frame) = Frame::cast(environment.clone()) {
+                    self.visit_frame(&frame)
                 } else {
                     self.visit_theorem(&environment, &name)
                 }
@@ -197,6 +210,25 @@ impl<'a> SymbolBuilder<'a> {
         Some(symbol)
     }
 
+    fn visit_frame(&self, frame: &Frame) -> Option<Symbol> {
+        let number = self.frame_count.get() + 1;
+        self.frame_count.set(number);
+
+        let range = latex::small_range(&frame.environment);
+        let kind = SymbolKind::Frame;
+        let name = match frame.title_text() {
+            Some(title) => format!("Frame {number}: {title}"),
+            None => format!("Frame {number}"),
+        };
+
+        let symbol = match self.find_label(frame.environment.syntax()) {
+            Some(label) => Symbol::new_label(name, kind, range, label),
+            None => Symbol::new_simple(name, kind, range, range),
+        };
+
+        Some(symbol)
+    }
+
     fn visit_enumeration(
         &self,
         environment: &latex::Environment,
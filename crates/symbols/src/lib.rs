@@ -5,5 +5,5 @@ mod workspace;
 pub use self::{
     document::document_symbols,
     types::{Symbol, SymbolKind, SymbolLocation},
-    workspace::workspace_symbols,
+    workspace::{workspace_symbols, SymbolIndex, SymbolSnapshot},
 };
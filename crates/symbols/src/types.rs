@@ -12,8 +12,12 @@ pub enum SymbolKind {
     EnumerationItem,
     Theorem,
     Equation,
+    Frame,
     Entry(BibtexEntryTypeCategory),
     Field,
+    Command,
+    Environment,
+    GlossaryEntry,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -65,11 +69,18 @@ impl Symbol {
             SymbolKind::EnumerationItem => vec![&self.name, "latex", "enumeration", "item"],
             SymbolKind::Theorem => vec![&self.name, "latex", "math"],
             SymbolKind::Equation => vec![&self.name, "latex", "math", "equation"],
+            SymbolKind::Frame => vec![&self.name, "latex", "beamer", "frame"],
             SymbolKind::Entry(BibtexEntryTypeCategory::String) => {
                 vec![&self.name, "bibtex", "string"]
             }
             SymbolKind::Entry(_) => vec![&self.name, "bibtex", "entry"],
             SymbolKind::Field => vec![&self.name, "bibtex", "field"],
+            SymbolKind::Command => {
+                let name = self.name.trim_start_matches('\\');
+                vec![&self.name, name, "latex", "command"]
+            }
+            SymbolKind::Environment => vec![&self.name, "latex", "environment"],
+            SymbolKind::GlossaryEntry => vec![&self.name, "latex", "glossary"],
         }
     }
 
@@ -1,46 +1,158 @@
+mod definitions;
 mod sort;
 
-use std::cmp::Reverse;
+use std::{cmp::Reverse, sync::Arc};
 
-use base_db::Workspace;
+use base_db::{
+    util::matchers::{self, Matcher},
+    Document, Workspace,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use url::Url;
 
-use crate::{document_symbols, types::SymbolLocation, SymbolKind};
+use crate::{document_symbols, types::SymbolLocation, Symbol, SymbolKind};
 
-use self::sort::ProjectOrdering;
+use self::{definitions::definition_symbols, sort::ProjectOrdering};
 
 pub fn workspace_symbols<'a>(workspace: &'a Workspace, query: &str) -> Vec<SymbolLocation<'a>> {
-    let query = query.split_whitespace().collect::<Vec<_>>();
-    let mut results = Vec::new();
+    SymbolIndex::default()
+        .snapshot(workspace)
+        .search(workspace, query)
+}
+
+/// Caches the symbols of every document in the workspace.
+///
+/// Only the documents which have been invalidated since the last query are processed again,
+/// so searching the workspace stays fast while the query is typed.
+#[derive(Debug, Default)]
+pub struct SymbolIndex {
+    entries: FxHashMap<Url, Arc<Vec<Symbol>>>,
+    ordering: Option<Arc<FxHashMap<Url, usize>>>,
+    outdated: FxHashSet<Url>,
+}
+
+impl SymbolIndex {
+    /// Marks the symbols of the given document as outdated.
+    /// The other documents of the project are updated lazily by the next query
+    /// because their symbols depend on the labels and theorem definitions of the project.
+    pub fn invalidate(&mut self, document: &Document) {
+        self.entries.remove(&document.uri);
+        self.outdated.insert(document.uri.clone());
+        self.ordering = None;
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.outdated.clear();
+        self.ordering = None;
+    }
+
+    /// Brings the cached symbols up to date with the workspace.
+    /// The returned snapshot can be searched without borrowing the index.
+    pub fn snapshot<'a>(&mut self, workspace: &'a Workspace) -> SymbolSnapshot<'a> {
+        let count = self.entries.len();
+        self.entries
+            .retain(|uri, _| workspace.lookup(uri).is_some());
+        if self.entries.len() != count {
+            self.ordering = None;
+        }
+
+        for uri in std::mem::take(&mut self.outdated) {
+            match workspace.lookup(&uri) {
+                Some(document) => {
+                    for other in &workspace.project(document).documents {
+                        self.entries.remove(&other.uri);
+                    }
+                }
+                None => self.entries.clear(),
+            }
+        }
+
+        let entries = workspace
+            .iter()
+            .map(|document| {
+                let symbols = self.entries.entry(document.uri.clone()).or_insert_with(|| {
+                    self.ordering = None;
+                    Arc::new(Self::collect(workspace, document))
+                });
+
+                (document, Arc::clone(symbols))
+            })
+            .collect();
 
-    for document in workspace.iter() {
+        let ordering = self.ordering.get_or_insert_with(|| {
+            let ordering = ProjectOrdering::from(workspace);
+            let ordering = workspace
+                .iter()
+                .map(|document| (document.uri.clone(), ordering.get(&document.uri)))
+                .collect();
+
+            Arc::new(ordering)
+        });
+
+        SymbolSnapshot {
+            entries,
+            ordering: Arc::clone(ordering),
+        }
+    }
+
+    fn collect(workspace: &Workspace, document: &Document) -> Vec<Symbol> {
         let mut buf = Vec::new();
-        let symbols = document_symbols(workspace, document);
-        for symbol in symbols {
+        for symbol in document_symbols(workspace, document) {
             symbol.flatten(&mut buf);
         }
 
-        for symbol in buf
-            .into_iter()
-            .filter(|symbol| symbol.kind != SymbolKind::Field)
-        {
-            let keywords = symbol.keywords();
-            if query.is_empty()
-                || itertools::iproduct!(keywords.iter(), query.iter())
-                    .any(|(keyword, query)| keyword.eq_ignore_ascii_case(query))
-            {
-                results.push(SymbolLocation { document, symbol });
+        buf.retain(|symbol| symbol.kind != SymbolKind::Field);
+        buf.append(&mut definition_symbols(document));
+        buf
+    }
+}
+
+/// The symbols of the workspace at the time of the last update of the [`SymbolIndex`].
+#[derive(Debug)]
+pub struct SymbolSnapshot<'a> {
+    entries: Vec<(&'a Document, Arc<Vec<Symbol>>)>,
+    ordering: Arc<FxHashMap<Url, usize>>,
+}
+
+impl<'a> SymbolSnapshot<'a> {
+    /// Finds the symbols matching all words of the query using the configured matcher.
+    /// The results are ranked by their score and then by their position in the project.
+    pub fn search(&self, workspace: &Workspace, query: &str) -> Vec<SymbolLocation<'a>> {
+        let matcher = matchers::create(&workspace.config().completion.matcher);
+        let query = query.split_whitespace().collect::<Vec<_>>();
+        let mut results = Vec::new();
+        for (document, symbols) in &self.entries {
+            for symbol in symbols.iter() {
+                if let Some(score) = Self::score(matcher.as_ref(), symbol, &query) {
+                    let symbol = symbol.clone();
+                    let document = *document;
+                    results.push((score, SymbolLocation { document, symbol }));
+                }
             }
         }
+
+        results.sort_by_key(|(score, item)| {
+            let index = self.ordering.get(&item.document.uri).copied();
+            let range = item.symbol.full_range;
+            (Reverse(*score), index, range.start(), Reverse(range.end()))
+        });
+
+        results.into_iter().map(|(_, item)| item).collect()
     }
 
-    let ordering = ProjectOrdering::from(workspace);
-    results.sort_by_key(|item| {
-        let index = ordering.get(&item.document.uri);
-        let range = item.symbol.full_range;
-        (index, range.start(), Reverse(range.end()))
-    });
+    /// Every word of the query has to match one of the keywords of the symbol.
+    fn score(matcher: &dyn Matcher, symbol: &Symbol, query: &[&str]) -> Option<i32> {
+        let keywords = symbol.keywords();
+        query.iter().try_fold(0, |total, word| {
+            let score = keywords
+                .iter()
+                .filter_map(|keyword| matcher.score(keyword, word))
+                .max()?;
 
-    results
+            Some(total + score)
+        })
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,41 @@
+use base_db::Document;
+use rowan::{ast::AstNode, TextRange};
+use syntax::latex;
+
+use crate::{Symbol, SymbolKind};
+
+/// Finds the commands, environments and glossary entries which are defined in the given document.
+/// These symbols are not part of the document outline but can be found using the workspace symbols.
+pub fn definition_symbols(document: &Document) -> Vec<Symbol> {
+    let Some(data) = document.data.as_tex() else {
+        return Vec::new();
+    };
+
+    data.root_node()
+        .descendants()
+        .filter_map(|node| {
+            let (kind, name, selection_range) = visit_definition(&node)?;
+            let full_range = node.text_range();
+            Some(Symbol::new_simple(name, kind, full_range, selection_range))
+        })
+        .collect()
+}
+
+fn visit_definition(node: &latex::SyntaxNode) -> Option<(SymbolKind, String, TextRange)> {
+    if let Some(definition) = latex::CommandDefinition::cast(node.clone()) {
+        let name = definition.name()?.command()?;
+        return Some((SymbolKind::Command, name.text().into(), name.text_range()));
+    }
+
+    let (kind, name) = if let Some(definition) = latex::EnvironmentDefinition::cast(node.clone()) {
+        (SymbolKind::Environment, definition.name()?.key()?)
+    } else if let Some(definition) = latex::GlossaryEntryDefinition::cast(node.clone()) {
+        (SymbolKind::GlossaryEntry, definition.name()?.key()?)
+    } else if let Some(definition) = latex::AcronymDefinition::cast(node.clone()) {
+        (SymbolKind::GlossaryEntry, definition.name()?.key()?)
+    } else {
+        return None;
+    };
+
+    Some((kind, name.to_string(), latex::small_range(&name)))
+}
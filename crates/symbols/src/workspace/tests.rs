@@ -1,7 +1,10 @@
+use base_db::{Owner, Workspace};
+use distro::Language;
 use expect_test::{expect, Expect};
+use line_index::LineCol;
 use test_utils::fixture::Fixture;
 
-use crate::workspace_symbols;
+use crate::{workspace_symbols, SymbolIndex};
 
 static FIXTURE: &str = r#"
 %! main.tex
@@ -338,3 +341,95 @@ fn test_filter_bibtex() {
     "#]],
     );
 }
+
+#[test]
+fn test_fuzzy_ranking() {
+    let fixture = Fixture::parse(FIXTURE);
+    let symbols = workspace_symbols(&fixture.workspace, "fgr");
+    let names: Vec<_> = symbols
+        .iter()
+        .map(|location| location.symbol.name.as_str())
+        .collect();
+
+    assert_eq!(names, vec!["Figure 1: Bar"]);
+}
+
+#[test]
+fn test_definitions() {
+    let fixture = Fixture::parse(
+        r#"
+%! main.tex
+\newcommand{\foo}{Foo}
+\newenvironment{foobar}{}{}
+\newglossaryentry{fooglossary}{name=Foo, description=Bar}
+\newacronym{fooacronym}{FA}{Foo Acronym}
+\newcommand{\baz}{Baz}"#,
+    );
+
+    let symbols = workspace_symbols(&fixture.workspace, "foo");
+    let names: Vec<_> = symbols
+        .iter()
+        .map(|location| (location.symbol.name.as_str(), location.symbol.kind))
+        .collect();
+
+    expect![[r#"
+        [
+            (
+                "\\foo",
+                Command,
+            ),
+            (
+                "foobar",
+                Environment,
+            ),
+            (
+                "fooglossary",
+                GlossaryEntry,
+            ),
+            (
+                "fooacronym",
+                GlossaryEntry,
+            ),
+        ]
+    "#]]
+    .assert_debug_eq(&names);
+}
+
+#[test]
+fn test_invalidate_project() {
+    let mut fixture = Fixture::parse(
+        r#"
+%! main.tex
+\documentclass{article}
+\begin{document}
+\section{Foo}\label{sec:foo}
+\end{document}
+
+%! main.aux
+\newlabel{sec:foo}{{1}{1}}"#,
+    );
+
+    fn names(index: &mut SymbolIndex, workspace: &Workspace) -> Vec<String> {
+        index
+            .snapshot(workspace)
+            .search(workspace, "foo")
+            .into_iter()
+            .map(|location| location.symbol.name)
+            .collect()
+    }
+
+    let mut index = SymbolIndex::default();
+    assert_eq!(names(&mut index, &fixture.workspace), vec!["1 Foo"]);
+
+    let aux = &fixture.documents[1];
+    fixture.workspace.open(
+        aux.uri.clone(),
+        String::from("\\newlabel{sec:foo}{{2}{1}}"),
+        Language::Aux,
+        Owner::Client,
+        LineCol { line: 0, col: 0 },
+    );
+
+    index.invalidate(fixture.workspace.lookup(&aux.uri).unwrap());
+    assert_eq!(names(&mut index, &fixture.workspace), vec!["2 Foo"]);
+}
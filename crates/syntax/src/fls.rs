@@ -0,0 +1,8 @@
+/// The files recorded by a TeX engine which has been run with the `-recorder` flag.
+#[derive(Debug, Clone, Default)]
+pub struct FlsData {
+    /// The working directory of the engine.
+    pub pwd: Option<String>,
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
@@ -2,4 +2,9 @@
 pub struct LatexmkrcData {
     pub aux_dir: Option<String>,
     pub out_dir: Option<String>,
+    pub pdf_mode: Option<u8>,
+    pub default_files: Vec<String>,
+    pub jobname: Option<String>,
+    pub pdflatex: Option<String>,
+    pub lualatex: Option<String>,
 }
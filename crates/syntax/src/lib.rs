@@ -1,6 +1,8 @@
 pub mod bibtex;
+pub mod fls;
 pub mod latex;
 pub mod latexmkrc;
+pub mod tectonic;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
 pub enum BuildErrorLevel {
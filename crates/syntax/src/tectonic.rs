@@ -0,0 +1,12 @@
+#[derive(Debug, Clone, Default)]
+pub struct TectonicData {
+    pub outputs: Vec<TectonicOutput>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TectonicOutput {
+    pub name: String,
+    pub output_type: String,
+    /// Input files relative to the `src` directory of the project.
+    pub inputs: Vec<String>,
+}
@@ -0,0 +1,262 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use distro::Language;
+use line_index::LineIndex;
+use syntax::bibtex;
+
+#[derive(Debug, clap::Args)]
+pub struct FormatArgs {
+    /// The files to format
+    #[clap(required = true, value_parser)]
+    pub files: Vec<PathBuf>,
+
+    /// Do not write the files but print a diff and exit with a non-zero status
+    /// if any file is not formatted correctly
+    #[clap(long)]
+    pub check: bool,
+
+    /// The number of spaces used for one level of indentation
+    #[clap(long, default_value_t = 4)]
+    pub tab_size: usize,
+
+    /// Indent using tabs instead of spaces
+    #[clap(long)]
+    pub use_tabs: bool,
+
+    /// The maximum line length before values get wrapped
+    #[clap(long, default_value_t = 80)]
+    pub line_length: usize,
+}
+
+impl FormatArgs {
+    fn options(&self) -> bibfmt::Options {
+        bibfmt::Options {
+            insert_spaces: !self.use_tabs,
+            tab_size: self.tab_size,
+            line_length: self.line_length,
+        }
+    }
+}
+
+/// Formats the given files and returns `true` if all of them were formatted correctly
+/// (in `--check` mode) or were formatted successfully.
+pub fn format(args: &FormatArgs) -> Result<bool> {
+    let options = args.options();
+    let mut success = true;
+    for path in &args.files {
+        let old_text = std::fs::read_to_string(path)?;
+        let Some(new_text) = format_text(path, &old_text, &options) else {
+            eprintln!("{}: no formatter available for this file", path.display());
+            success = false;
+            continue;
+        };
+
+        if old_text == new_text {
+            continue;
+        }
+
+        if args.check {
+            print!("{}", diff(path, &old_text, &new_text));
+            success = false;
+        } else {
+            std::fs::write(path, new_text)?;
+        }
+    }
+
+    Ok(success)
+}
+
+fn format_text(path: &Path, text: &str, options: &bibfmt::Options) -> Option<String> {
+    match Language::from_path(path)? {
+        Language::Bib => {
+            let root = bibtex::SyntaxNode::new_root(parser::parse_bibtex(text));
+            let line_index = LineIndex::new(text);
+            Some(bibfmt::format(&root, &line_index, options))
+        }
+        Language::Tex
+        | Language::Aux
+        | Language::Log
+        | Language::Blg
+        | Language::Ilg
+        | Language::Fls
+        | Language::Root
+        | Language::Latexmkrc
+        | Language::Tectonic => None,
+    }
+}
+
+/// Renders a unified diff between `old_text` and `new_text` with three lines of context.
+fn diff(path: &Path, old_text: &str, new_text: &str) -> String {
+    const CONTEXT: usize = 3;
+
+    let old_lines: Vec<_> = old_text.lines().collect();
+    let new_lines: Vec<_> = new_text.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+
+    let mut output = format!("--- {0}\n+++ {0}\n", path.display());
+    let mut i = 0;
+    while i < ops.len() {
+        if matches!(ops[i], DiffOp::Equal(..)) {
+            i += 1;
+            continue;
+        }
+
+        let start = i.saturating_sub(CONTEXT);
+        let mut end = i;
+        let mut equal_run = 0;
+        while end < ops.len() && equal_run <= 2 * CONTEXT {
+            if matches!(ops[end], DiffOp::Equal(..)) {
+                equal_run += 1;
+            } else {
+                equal_run = 0;
+            }
+
+            end += 1;
+        }
+
+        end -= equal_run.saturating_sub(CONTEXT);
+        let hunk = &ops[start..end];
+
+        let (old_start, new_start) = ops[..start].iter().fold((0, 0), |(old, new), op| {
+            (old + op.old_len(), new + op.new_len())
+        });
+
+        let old_len: usize = hunk.iter().map(DiffOp::old_len).sum();
+        let new_len: usize = hunk.iter().map(DiffOp::new_len).sum();
+        output.push_str(&format!(
+            "@@ -{},{old_len} +{},{new_len} @@\n",
+            old_start + 1,
+            new_start + 1
+        ));
+
+        for op in hunk {
+            let (prefix, line) = match op {
+                DiffOp::Equal(line) => (' ', line),
+                DiffOp::Delete(line) => ('-', line),
+                DiffOp::Insert(line) => ('+', line),
+            };
+
+            output.push(prefix);
+            output.push_str(line);
+            output.push('\n');
+        }
+
+        i = end;
+    }
+
+    output
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum DiffOp<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+impl<'a> DiffOp<'a> {
+    fn old_len(&self) -> usize {
+        match self {
+            Self::Equal(_) | Self::Delete(_) => 1,
+            Self::Insert(_) => 0,
+        }
+    }
+
+    fn new_len(&self) -> usize {
+        match self {
+            Self::Equal(_) | Self::Insert(_) => 1,
+            Self::Delete(_) => 0,
+        }
+    }
+}
+
+/// Computes the line changes with the Myers algorithm, which only needs linear space.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffOp<'a>> {
+    similar::capture_diff_slices(similar::Algorithm::Myers, old, new)
+        .iter()
+        .flat_map(|op| op.iter_changes(old, new))
+        .map(|change| match change.tag() {
+            similar::ChangeTag::Equal => DiffOp::Equal(change.value()),
+            similar::ChangeTag::Delete => DiffOp::Delete(change.value()),
+            similar::ChangeTag::Insert => DiffOp::Insert(change.value()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{diff, format_text};
+
+    #[test]
+    fn test_format_bibtex() {
+        let output = format_text(
+            Path::new("foo.bib"),
+            "@ARTICLE{foo, author = {Foo Bar}, title={Baz}}",
+            &bibfmt::Options::default(),
+        );
+
+        assert_eq!(
+            output.as_deref(),
+            Some("@article{foo,\n    author = {Foo Bar},\n    title = {Baz},\n}")
+        );
+    }
+
+    #[test]
+    fn test_format_latex_unsupported() {
+        let output = format_text(
+            Path::new("foo.tex"),
+            "\\documentclass{article}",
+            &bibfmt::Options::default(),
+        );
+
+        assert_eq!(output, None);
+    }
+
+    #[test]
+    fn test_diff() {
+        let old_text = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let new_text = "a\nB\nc\nd\ne\nf\ng\nh\ni\nJ\n";
+        let expected = "\
+--- foo.bib
++++ foo.bib
+@@ -1,5 +1,5 @@
+ a
+-b
++B
+ c
+ d
+ e
+@@ -7,4 +7,4 @@
+ g
+ h
+ i
+-j
++J
+";
+
+        assert_eq!(diff(Path::new("foo.bib"), old_text, new_text), expected);
+    }
+
+    #[test]
+    fn test_diff_large() {
+        let old_text: String = (0..50_000).map(|i| format!("{i}\n")).collect();
+        let new_text = old_text.replacen("25000\n", "25000\nfoo\n", 1);
+        let expected = "\
+--- foo.bib
++++ foo.bib
+@@ -24999,6 +24999,7 @@
+ 24998
+ 24999
+ 25000
++foo
+ 25001
+ 25002
+ 25003
+";
+
+        assert_eq!(diff(Path::new("foo.bib"), &old_text, &new_text), expected);
+    }
+}
@@ -0,0 +1,24 @@
+use base_db::Workspace;
+
+use crate::util::{from_proto, line_index_ext::PositionEncoding, to_proto};
+
+pub fn find_all(
+    workspace: &Workspace,
+    params: lsp_types::CodeActionParams,
+    encoding: PositionEncoding,
+) -> Option<Vec<lsp_types::CodeActionOrCommand>> {
+    let (feature, offset) = from_proto::feature_params_offset(
+        workspace,
+        params.text_document,
+        params.range.start,
+        encoding,
+    )?;
+
+    let results = commands::merge_duplicate_entries(workspace, feature.document, offset);
+    let actions = results
+        .into_iter()
+        .map(|result| to_proto::merge_entries_code_action(result, encoding))
+        .map(lsp_types::CodeActionOrCommand::CodeAction);
+
+    Some(actions.collect())
+}
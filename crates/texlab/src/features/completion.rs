@@ -1,5 +1,8 @@
 use base_db::{util::RenderedObject, MatchingAlgo, Workspace};
-use completion::{ArgumentData, CompletionItem, CompletionItemData, EntryTypeData, FieldTypeData};
+use completion::{
+    ArgumentData, CompletionItem, CompletionItemData, EntryTypeData, FieldTypeData,
+    PackageOptionData,
+};
 use line_index::LineIndex;
 use rowan::ast::AstNode;
 use serde::{Deserialize, Serialize};
@@ -12,10 +15,11 @@ pub fn complete(
     params: lsp_types::CompletionParams,
     client_flags: &ClientFlags,
 ) -> Option<lsp_types::CompletionList> {
-    let params = from_proto::completion_params(workspace, params)?;
+    let params = from_proto::completion_params(workspace, params, client_flags.position_encoding)?;
     let result = completion::complete(&params);
 
     let item_builder = ItemBuilder {
+        workspace,
         line_index: &params.feature.document.line_index,
         client_flags,
     };
@@ -27,8 +31,14 @@ pub fn complete(
         MatchingAlgo::PrefixIgnoreCase => false,
     };
 
+    // Citation queries with field filters cannot be filtered by the client.
+    let has_citation_filter = result.items.iter().any(|item| {
+        matches!(&item.data, CompletionItemData::Citation(data) if data.filter_text.is_some())
+    });
+
     let is_incomplete = client_flags.completion_always_incomplete
         || !is_fuzzy
+        || has_citation_filter
         || result.items.len() >= completion::LIMIT;
 
     let items = result
@@ -61,7 +71,7 @@ pub fn resolve(workspace: &Workspace, item: &mut lsp_types::CompletionItem) -> O
             let data = workspace.lookup(&uri)?.data.as_bib()?;
             let root = bibtex::Root::cast(data.root_node())?;
             let entry = root.find_entry(&key)?;
-            let value = citeproc::render(&entry)?;
+            let value = citeproc::render(&entry, workspace.config().citation.style)?;
             item.documentation = Some(lsp_types::Documentation::MarkupContent(
                 lsp_types::MarkupContent {
                     kind: lsp_types::MarkupKind::Markdown,
@@ -75,6 +85,7 @@ pub fn resolve(workspace: &Workspace, item: &mut lsp_types::CompletionItem) -> O
 }
 
 struct ItemBuilder<'a> {
+    workspace: &'a Workspace,
     line_index: &'a LineIndex,
     client_flags: &'a ClientFlags,
 }
@@ -82,7 +93,9 @@ struct ItemBuilder<'a> {
 impl<'a> ItemBuilder<'a> {
     pub fn convert(&self, item: CompletionItem, index: usize) -> Option<lsp_types::CompletionItem> {
         let mut result = lsp_types::CompletionItem::default();
-        let range = self.line_index.line_col_lsp_range(item.range)?;
+        let range = self
+            .line_index
+            .line_col_lsp_range(item.range, self.client_flags.position_encoding)?;
 
         match item.data {
             CompletionItemData::Command(data) => {
@@ -133,6 +146,9 @@ impl<'a> ItemBuilder<'a> {
             CompletionItemData::TikzLibrary(name) => {
                 self.convert_tikz_library(&mut result, range, name);
             }
+            CompletionItemData::PackageOption(data) => {
+                self.convert_package_option(&mut result, range, data);
+            }
         }
 
         if result
@@ -201,7 +217,17 @@ impl<'a> ItemBuilder<'a> {
     ) {
         result.label = data.entry.name.text.clone();
         result.kind = Some(Structure::Entry(data.entry.category).completion_kind());
-        result.filter_text = Some(data.entry.keywords.clone());
+        result.filter_text = Some(
+            data.filter_text
+                .map_or_else(|| data.entry.keywords.clone(), String::from),
+        );
+
+        result.detail = data.document.data.as_bib().and_then(|bib| {
+            let node = bib.root_node().covering_element(data.entry.full_range);
+            let entry = node.into_node().and_then(bibtex::Entry::cast)?;
+            citeproc::render_plain(&entry, self.workspace.config().citation.style)
+        });
+
         let text_edit = lsp_types::TextEdit::new(range, data.entry.name.text.clone());
         result.text_edit = Some(text_edit.into());
         let resolve_info = serde_json::to_value(ResolveInfo::Citation {
@@ -397,6 +423,25 @@ impl<'a> ItemBuilder<'a> {
         result.text_edit = Some(lsp_types::TextEdit::new(range, name.into()).into());
     }
 
+    fn convert_package_option(
+        &self,
+        result: &mut lsp_types::CompletionItem,
+        range: lsp_types::Range,
+        data: PackageOptionData,
+    ) {
+        result.label = data.0.name.into();
+        result.kind = Some(Structure::PackageOption.completion_kind());
+        result.documentation = Some(lsp_types::Documentation::MarkupContent(
+            lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: data.0.description.to_string(),
+            },
+        ));
+
+        let text_edit = lsp_types::TextEdit::new(range, data.0.name.into());
+        result.text_edit = Some(text_edit.into());
+    }
+
     fn inline_image(&self, name: &str, base64: &str) -> Option<lsp_types::Documentation> {
         if self.client_flags.completion_markdown {
             let kind = lsp_types::MarkupKind::Markdown;
@@ -4,27 +4,33 @@ mod latexindent;
 use base_db::{Formatter, Workspace};
 use distro::Language;
 
+use crate::util::line_index_ext::PositionEncoding;
+
 use self::{bibtex_internal::format_bibtex_internal, latexindent::format_with_latexindent};
 
 pub fn format_source_code(
     workspace: &Workspace,
     uri: &lsp_types::Url,
     options: &lsp_types::FormattingOptions,
+    encoding: PositionEncoding,
 ) -> Option<Vec<lsp_types::TextEdit>> {
     let document = workspace.lookup(uri)?;
     match document.language {
         Language::Tex => match workspace.config().formatting.tex_formatter {
             Formatter::Null => None,
             Formatter::Server => None,
-            Formatter::LatexIndent => format_with_latexindent(workspace, document),
+            Formatter::LatexIndent => format_with_latexindent(workspace, document, encoding),
         },
         Language::Bib => match workspace.config().formatting.bib_formatter {
             Formatter::Null => None,
-            Formatter::Server => format_bibtex_internal(workspace, document, options),
-            Formatter::LatexIndent => format_with_latexindent(workspace, document),
+            Formatter::Server => format_bibtex_internal(workspace, document, options, encoding),
+            Formatter::LatexIndent => format_with_latexindent(workspace, document, encoding),
         },
         Language::Aux
         | Language::Log
+        | Language::Blg
+        | Language::Ilg
+        | Language::Fls
         | Language::Root
         | Language::Latexmkrc
         | Language::Tectonic => None,
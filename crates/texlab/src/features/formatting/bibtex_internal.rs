@@ -1,12 +1,13 @@
 use base_db::{Document, Workspace};
 use rowan::TextLen;
 
-use crate::util::line_index_ext::LineIndexExt;
+use crate::util::line_index_ext::{LineIndexExt, PositionEncoding};
 
 pub fn format_bibtex_internal(
     workspace: &Workspace,
     document: &Document,
     options: &lsp_types::FormattingOptions,
+    encoding: PositionEncoding,
 ) -> Option<Vec<lsp_types::TextEdit>> {
     let data = document.data.as_bib()?;
     let options = bibfmt::Options {
@@ -16,7 +17,9 @@ pub fn format_bibtex_internal(
     };
 
     let output = bibfmt::format(&data.root_node(), &document.line_index, &options);
-    let end = document.line_index.line_col_lsp(document.text.text_len())?;
+    let end = document
+        .line_index
+        .line_col_lsp(document.text.text_len(), encoding)?;
     let range = lsp_types::Range::new(lsp_types::Position::new(0, 0), end);
     Some(vec![lsp_types::TextEdit::new(range, output)])
 }
@@ -8,11 +8,12 @@ use distro::Language;
 use rowan::TextLen;
 use tempfile::tempdir;
 
-use crate::util::line_index_ext::LineIndexExt;
+use crate::util::line_index_ext::{LineIndexExt, PositionEncoding};
 
 pub fn format_with_latexindent(
     workspace: &Workspace,
     document: &Document,
+    encoding: PositionEncoding,
 ) -> Option<Vec<lsp_types::TextEdit>> {
     let config = workspace.config();
     let target_dir = tempdir().ok()?;
@@ -51,7 +52,7 @@ pub fn format_with_latexindent(
     } else {
         let line_index = &document.line_index;
         let start = lsp_types::Position::new(0, 0);
-        let end = line_index.line_col_lsp(old_text.text_len())?;
+        let end = line_index.line_col_lsp(old_text.text_len(), encoding)?;
         Some(vec![lsp_types::TextEdit {
             range: lsp_types::Range::new(start, end),
             new_text,
@@ -1,15 +1,18 @@
 use base_db::Workspace;
 
-use crate::util::{from_proto, to_proto};
+use crate::util::{from_proto, line_index_ext::PositionEncoding, to_proto};
 
 pub fn find_all(
     workspace: &Workspace,
     params: lsp_types::InlayHintParams,
+    encoding: PositionEncoding,
 ) -> Option<Vec<lsp_types::InlayHint>> {
-    let params = from_proto::inlay_hint_params(workspace, params)?;
+    let params = from_proto::inlay_hint_params(workspace, params, encoding)?;
     let hints = inlay_hints::find_all(&params)?
         .into_iter()
-        .filter_map(|hint| to_proto::inlay_hint(hint, &params.feature.document.line_index))
+        .filter_map(|hint| {
+            to_proto::inlay_hint(hint, &params.feature.document.line_index, encoding)
+        })
         .collect();
 
     Some(hints)
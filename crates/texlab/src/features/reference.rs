@@ -1,16 +1,17 @@
 use base_db::Workspace;
 
-use crate::util::{from_proto, to_proto};
+use crate::util::{from_proto, line_index_ext::PositionEncoding, to_proto};
 
 pub fn find_all(
     workspace: &Workspace,
     params: lsp_types::ReferenceParams,
+    encoding: PositionEncoding,
 ) -> Option<Vec<lsp_types::Location>> {
-    let params = from_proto::reference_params(workspace, params)?;
+    let params = from_proto::reference_params(workspace, params, encoding)?;
 
     let results = references::find_all(&params)
         .into_iter()
-        .filter_map(to_proto::location)
+        .filter_map(|location| to_proto::location(location, encoding))
         .collect();
 
     Some(results)
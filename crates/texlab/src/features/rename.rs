@@ -1,22 +1,32 @@
 use base_db::Workspace;
 
-use crate::util::{from_proto, line_index_ext::LineIndexExt, to_proto};
+use crate::util::{
+    from_proto,
+    line_index_ext::{LineIndexExt, PositionEncoding},
+    to_proto,
+};
 
 pub fn prepare_rename_all(
     workspace: &Workspace,
     params: lsp_types::TextDocumentPositionParams,
+    encoding: PositionEncoding,
 ) -> Option<lsp_types::Range> {
-    let params = from_proto::rename_params(workspace, params)?;
+    let params = from_proto::rename_params(workspace, params, encoding)?;
     let range = rename::prepare_rename(&params)?;
-    params.feature.document.line_index.line_col_lsp_range(range)
+    params
+        .feature
+        .document
+        .line_index
+        .line_col_lsp_range(range, encoding)
 }
 
 pub fn rename_all(
     workspace: &Workspace,
     params: lsp_types::RenameParams,
+    encoding: PositionEncoding,
 ) -> Option<lsp_types::WorkspaceEdit> {
     let new_name = &params.new_name;
-    let params = from_proto::rename_params(workspace, params.text_document_position)?;
+    let params = from_proto::rename_params(workspace, params.text_document_position, encoding)?;
     let result = rename::rename(params);
-    Some(to_proto::workspace_edit(result, &new_name))
+    Some(to_proto::workspace_edit(result, &new_name, encoding))
 }
@@ -1,6 +1,8 @@
 use base_db::Workspace;
+use parking_lot::Mutex;
+use symbols::SymbolIndex;
 
-use crate::util::{from_proto, to_proto, ClientFlags};
+use crate::util::{from_proto, line_index_ext::PositionEncoding, to_proto, ClientFlags};
 
 pub fn document_symbols(
     workspace: &Workspace,
@@ -16,11 +18,18 @@ pub fn document_symbols(
     ))
 }
 
-pub fn workspace_symbols(workspace: &Workspace, query: &str) -> lsp_types::WorkspaceSymbolResponse {
-    let symbols = symbols::workspace_symbols(workspace, query);
+pub fn workspace_symbols(
+    workspace: &Workspace,
+    index: &Mutex<SymbolIndex>,
+    query: &str,
+    encoding: PositionEncoding,
+) -> lsp_types::WorkspaceSymbolResponse {
+    // The lock is only held while the cached symbols are updated, not while searching them.
+    let snapshot = index.lock().snapshot(workspace);
+    let symbols = snapshot.search(workspace, query);
     let mut results = Vec::new();
     for symbols::SymbolLocation { symbol, document } in symbols {
-        to_proto::symbol_information(symbol, document, &mut results);
+        to_proto::symbol_information(symbol, document, &mut results, encoding);
     }
 
     lsp_types::WorkspaceSymbolResponse::Flat(results)
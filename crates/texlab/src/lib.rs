@@ -1,3 +1,4 @@
+pub mod cli;
 mod client;
 pub(crate) mod features;
 mod server;
@@ -1,10 +1,13 @@
-use std::{fs::OpenOptions, io, path::PathBuf};
+use std::{fs::OpenOptions, io, path::PathBuf, process::ExitCode};
 
 use anyhow::Result;
-use clap::{ArgAction, Parser};
+use clap::{ArgAction, Parser, Subcommand};
 use log::LevelFilter;
 use lsp_server::Connection;
-use texlab::Server;
+use texlab::{
+    cli::{self, FormatArgs},
+    Server,
+};
 
 /// An implementation of the Language Server Protocol for LaTeX
 #[derive(Debug, Parser)]
@@ -21,17 +24,36 @@ struct Opts {
     /// Write the logging output to FILE
     #[clap(long, name = "FILE", value_parser)]
     log_file: Option<PathBuf>,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Format BibTeX files in place
+    Format(FormatArgs),
 }
 
-fn main() -> Result<()> {
-    let opts = Opts::parse();
+fn main() -> Result<ExitCode> {
+    let mut opts = Opts::parse();
+    let command = opts.command.take();
     setup_logger(opts);
 
+    if let Some(Command::Format(args)) = command {
+        let success = cli::format(&args)?;
+        return Ok(if success {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        });
+    }
+
     let (connection, threads) = Connection::stdio();
     Server::exec(connection)?;
     threads.join()?;
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
 fn setup_logger(opts: Opts) {
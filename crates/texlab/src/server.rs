@@ -1,3 +1,5 @@
+mod build_queue;
+mod cache;
 mod dispatch;
 mod extensions;
 pub mod options;
@@ -6,14 +8,21 @@ mod progress;
 use std::{
     collections::HashMap,
     path::PathBuf,
-    sync::{atomic::AtomicI32, Arc},
-    time::Duration,
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
+use ::symbols::SymbolIndex;
 use anyhow::Result;
-use base_db::{Config, Owner, Workspace};
-use commands::{BuildCommand, CleanCommand, CleanTarget, ForwardSearch};
-use crossbeam_channel::{Receiver, Sender};
+use base_db::{Config, DocumentCache, Owner, Workspace};
+use commands::{
+    BuildCommand, BuildError, BuildProgress, CleanCommand, CleanTarget, ForwardSearch,
+    ShadowDirectory,
+};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
 use distro::{Distro, Language};
 use line_index::LineCol;
 use lsp_server::{Connection, ErrorCode, Message, RequestId};
@@ -21,36 +30,51 @@ use lsp_types::{notification::*, request::*, *};
 use notify::event::ModifyKind;
 use notify_debouncer_full::{DebouncedEvent, Debouncer, FileIdMap};
 use parking_lot::{Mutex, RwLock};
+use rowan::TextLen;
 use rustc_hash::FxHashSet;
 use serde::{de::DeserializeOwned, Serialize};
+use syntax::{BuildErrorLevel, BuildLog};
 use threadpool::ThreadPool;
 
 use crate::{
     client::LspClient,
     features::{
-        completion, definition, folding, formatting, highlight, hover, inlay_hint, link, reference,
-        rename, symbols,
+        code_action, completion, definition, folding, formatting, highlight, hover, inlay_hint,
+        link, reference, rename, symbols,
+    },
+    util::{
+        from_proto,
+        line_index_ext::{LineIndexExt, PositionEncoding},
+        normalize_uri, to_proto, ClientFlags,
     },
-    util::{from_proto, line_index_ext::LineIndexExt, normalize_uri, to_proto, ClientFlags},
 };
 
 use self::{
+    build_queue::BuildQueue,
     extensions::{
-        BuildParams, BuildRequest, BuildResult, BuildStatus, EnvironmentLocation,
-        ForwardSearchRequest, ForwardSearchResult, ForwardSearchStatus, TextWithRange,
+        BuildParams, BuildRequest, BuildResult, BuildStatus, BuildStatusNotification,
+        BuildStatusParams, CleanParams, EnvironmentLocation, FindFrameParams, ForwardSearchRequest,
+        ForwardSearchResult, ForwardSearchStatus, FrameLocation, SortBibliographyParams,
+        TextWithRange,
     },
     options::{Options, StartupOptions},
     progress::ProgressReporter,
 };
 
+static NEXT_TOKEN: AtomicI32 = AtomicI32::new(1);
+
 #[derive(Debug)]
 enum InternalMessage {
     SetDistro(Distro),
     SetOptions(Options),
+    ConfigurationFailed,
     FileEvent(Vec<DebouncedEvent>),
     Diagnostics,
     ChktexFinished(Url, Vec<diagnostics::Diagnostic>),
+    BuildOutput(Url, BuildLog),
     ForwardSearch(Url, Option<Position>),
+    PreviewForwardSearch(Url, ShadowDirectory),
+    FoldersScanned(Vec<(PathBuf, Language)>, Option<ProgressReporter>),
 }
 
 pub struct Server {
@@ -61,9 +85,15 @@ pub struct Server {
     client: LspClient,
     client_flags: Arc<ClientFlags>,
     diagnostic_manager: diagnostics::Manager,
+    symbol_index: Arc<Mutex<SymbolIndex>>,
     watcher: FileWatcher,
     pool: ThreadPool,
-    pending_builds: Arc<Mutex<FxHashSet<u32>>>,
+    build_queue: BuildQueue,
+    idle_build: Option<Url>,
+    idle_timer: Receiver<Instant>,
+    cache_timer: Receiver<Instant>,
+    initial_scan_pending: bool,
+    cache_dir: Option<PathBuf>,
 }
 
 impl Server {
@@ -73,6 +103,10 @@ impl Server {
         let watcher = FileWatcher::new(internal_tx.clone()).expect("init file watcher");
 
         let mut workspace = Workspace::default();
+        let cache_dir = cache::open_cache_dir();
+        if let Some(dir) = &cache_dir {
+            workspace.set_cache(DocumentCache::new(dir.join("documents")));
+        }
 
         let (id, params) = connection.initialize_start()?;
         let params: InitializeParams = serde_json::from_value(params)?;
@@ -87,8 +121,10 @@ impl Server {
 
         workspace.set_folders(workspace_folders);
 
+        let client_flags = from_proto::client_flags(params.capabilities, params.client_info);
+
         let result = InitializeResult {
-            capabilities: Self::capabilities(),
+            capabilities: Self::capabilities(client_flags.position_encoding),
             server_info: Some(ServerInfo {
                 name: "TexLab".to_owned(),
                 version: Some(env!("CARGO_PKG_VERSION").to_owned()),
@@ -97,20 +133,24 @@ impl Server {
 
         connection.initialize_finish(id, serde_json::to_value(result)?)?;
 
+        let pool = threadpool::Builder::new().build();
         let server = Self {
             connection: Arc::new(connection),
             internal_tx,
             internal_rx,
             workspace: Arc::new(RwLock::new(workspace)),
             client,
-            client_flags: Arc::new(from_proto::client_flags(
-                params.capabilities,
-                params.client_info,
-            )),
+            client_flags: Arc::new(client_flags),
             diagnostic_manager: diagnostics::Manager::default(),
+            symbol_index: Default::default(),
             watcher,
-            pool: threadpool::Builder::new().build(),
-            pending_builds: Default::default(),
+            build_queue: BuildQueue::default(),
+            pool,
+            idle_build: None,
+            idle_timer: crossbeam_channel::never(),
+            cache_timer: crossbeam_channel::never(),
+            initial_scan_pending: true,
+            cache_dir,
         };
 
         let options = serde_json::from_value(params.initialization_options.unwrap_or_default())
@@ -120,8 +160,9 @@ impl Server {
         Ok(())
     }
 
-    fn capabilities() -> ServerCapabilities {
+    fn capabilities(position_encoding: PositionEncoding) -> ServerCapabilities {
         ServerCapabilities {
+            position_encoding: Some(position_encoding.kind()),
             text_document_sync: Some(TextDocumentSyncCapability::Options(
                 TextDocumentSyncOptions {
                     open_close: Some(true),
@@ -161,18 +202,28 @@ impl Server {
             })),
             document_highlight_provider: Some(OneOf::Left(true)),
             document_formatting_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
             execute_command_provider: Some(ExecuteCommandOptions {
                 commands: vec![
                     "texlab.cleanAuxiliary".into(),
                     "texlab.cleanArtifacts".into(),
                     "texlab.changeEnvironment".into(),
                     "texlab.findEnvironments".into(),
+                    "texlab.findFrame".into(),
                     "texlab.showDependencyGraph".into(),
                     "texlab.cancelBuild".into(),
+                    "texlab.sortBibliography".into(),
                 ],
                 ..Default::default()
             }),
             inlay_hint_provider: Some(OneOf::Left(true)),
+            workspace: Some(WorkspaceServerCapabilities {
+                workspace_folders: Some(WorkspaceFoldersServerCapabilities {
+                    supported: Some(true),
+                    change_notifications: Some(OneOf::Left(true)),
+                }),
+                file_operations: None,
+            }),
             ..ServerCapabilities::default()
         }
     }
@@ -235,7 +286,10 @@ impl Server {
     }
 
     fn update_workspace(&mut self) {
-        let mut checked_paths = FxHashSet::default();
+        self.update_workspace_with(FxHashSet::default());
+    }
+
+    fn update_workspace_with(&mut self, mut checked_paths: FxHashSet<PathBuf>) {
         let mut workspace = self.workspace.write();
         workspace.discover(&mut checked_paths);
         self.watcher.watch(&mut workspace);
@@ -245,14 +299,32 @@ impl Server {
             .filter_map(|path| workspace.lookup_path(path))
         {
             self.diagnostic_manager.update_syntax(&workspace, document);
+            self.symbol_index.lock().invalidate(document);
         }
 
         drop(workspace);
         self.publish_diagnostics_with_delay();
+        self.flush_cache_with_delay();
+    }
+
+    /// Restarts the timer that writes the newly parsed documents to the on-disk cache,
+    /// so a burst of file changes results in a single write.
+    fn flush_cache_with_delay(&mut self) {
+        const CACHE_FLUSH_DELAY: Duration = Duration::from_secs(5);
+        if self.workspace.read().cache().is_some() {
+            self.cache_timer = crossbeam_channel::after(CACHE_FLUSH_DELAY);
+        }
+    }
+
+    fn flush_cache(&self) {
+        if let Some(cache) = self.workspace.read().cache() {
+            cache.flush();
+        }
     }
 
     fn publish_diagnostics(&mut self) -> Result<()> {
         let workspace = self.workspace.read();
+        let encoding = self.client_flags.position_encoding;
 
         for (uri, diagnostics) in self.diagnostic_manager.get(&workspace) {
             let Some(document) = workspace.lookup(&uri) else {
@@ -261,7 +333,9 @@ impl Server {
 
             let diagnostics = diagnostics
                 .into_iter()
-                .filter_map(|diagnostic| to_proto::diagnostic(&workspace, document, &diagnostic))
+                .filter_map(|diagnostic| {
+                    to_proto::diagnostic(&workspace, document, &diagnostic, encoding)
+                })
                 .collect();
 
             let version = None;
@@ -312,6 +386,7 @@ impl Server {
                 }
                 Err(why) => {
                     log::error!("Retrieving configuration failed: {}", why);
+                    sender.send(InternalMessage::ConfigurationFailed).unwrap();
                 }
             };
         });
@@ -320,6 +395,7 @@ impl Server {
     fn update_options(&mut self, options: Options) {
         let mut workspace = self.workspace.write();
         workspace.set_config(Config::from(options));
+        self.symbol_index.lock().clear();
         self.watcher.watch(&mut workspace);
     }
 
@@ -331,6 +407,109 @@ impl Server {
         Ok(())
     }
 
+    fn did_change_workspace_folders(
+        &mut self,
+        params: DidChangeWorkspaceFoldersParams,
+    ) -> Result<()> {
+        let mut workspace = self.workspace.write();
+        for folder in params.event.removed {
+            let Ok(path) = folder.uri.to_file_path() else {
+                continue;
+            };
+
+            for uri in workspace.remove_folder(&path) {
+                let params = PublishDiagnosticsParams {
+                    uri,
+                    diagnostics: Vec::new(),
+                    version: None,
+                };
+
+                self.client
+                    .send_notification::<PublishDiagnostics>(params)?;
+            }
+        }
+
+        let added = params
+            .event
+            .added
+            .into_iter()
+            .filter(|folder| folder.uri.scheme() == "file")
+            .flat_map(|folder| folder.uri.to_file_path())
+            .collect::<Vec<_>>();
+
+        for folder in &added {
+            workspace.add_folder(folder.clone());
+        }
+
+        self.watcher.watch(&mut workspace);
+        drop(workspace);
+        self.symbol_index.lock().clear();
+        self.scan_folders(added);
+        Ok(())
+    }
+
+    /// Searches the workspace folders for documents in the background,
+    /// so the projects are known before their root documents are opened.
+    fn scan_folders(&self, folders: Vec<PathBuf>) {
+        if folders.is_empty() {
+            return;
+        }
+
+        let exclude = self.workspace.read().config().workspace.exclude.clone();
+        let client = self.client.clone();
+        let sender = self.internal_tx.clone();
+        let progress = self.client_flags.progress;
+        self.pool.execute(move || {
+            let progress_reporter = if progress {
+                let token = NEXT_TOKEN.fetch_add(1, Ordering::SeqCst);
+                let message = String::from("Scanning workspace folders");
+                Some(ProgressReporter::new(client, token, "Indexing", message))
+            } else {
+                None
+            };
+
+            let mut files = Vec::new();
+            for (i, folder) in folders.iter().enumerate() {
+                if let Some(progress_reporter) = &progress_reporter {
+                    let percentage = (i * 100 / folders.len()) as u32;
+                    progress_reporter.report(folder.display().to_string(), percentage);
+                }
+
+                files.extend(base_db::util::scan_folder(folder, &exclude));
+            }
+
+            // The documents are loaded by the main loop so that they cannot replace
+            // documents which have been opened by the client in the meantime.
+            sender
+                .send(InternalMessage::FoldersScanned(files, progress_reporter))
+                .unwrap();
+        });
+    }
+
+    fn start_initial_scan(&mut self) {
+        if std::mem::take(&mut self.initial_scan_pending) {
+            let folders = self.workspace.read().folders().to_vec();
+            self.scan_folders(folders);
+        }
+    }
+
+    fn load_scanned_files(&mut self, files: Vec<(PathBuf, Language)>) {
+        let mut checked_paths = FxHashSet::default();
+        let mut workspace = self.workspace.write();
+        for (path, language) in files {
+            if !workspace.contains(&path) || workspace.lookup_path(&path).is_some() {
+                continue;
+            }
+
+            if workspace.load(&path, language).is_ok() {
+                checked_paths.insert(path);
+            }
+        }
+
+        drop(workspace);
+        self.update_workspace_with(checked_paths);
+    }
+
     fn did_change_configuration(&mut self, params: DidChangeConfigurationParams) -> Result<()> {
         if self.client_flags.configuration_pull {
             self.pull_options();
@@ -359,8 +538,9 @@ impl Server {
         self.update_workspace();
 
         let workspace = self.workspace.read();
-        self.diagnostic_manager
-            .update_syntax(&workspace, workspace.lookup(&uri).unwrap());
+        let document = workspace.lookup(&uri).unwrap();
+        self.diagnostic_manager.update_syntax(&workspace, document);
+        self.symbol_index.lock().invalidate(document);
 
         if workspace.config().diagnostics.chktex.on_open {
             drop(workspace);
@@ -375,6 +555,7 @@ impl Server {
         normalize_uri(&mut uri);
 
         let mut workspace = self.workspace.write();
+        let encoding = self.client_flags.position_encoding;
 
         for change in params.content_changes {
             let Some(document) = workspace.lookup(&uri) else {
@@ -382,7 +563,10 @@ impl Server {
             };
             match change.range {
                 Some(range) => {
-                    let range = document.line_index.offset_lsp_range(range).unwrap();
+                    let range = document
+                        .line_index
+                        .offset_lsp_range(range, encoding)
+                        .unwrap();
                     workspace.edit(&uri, range, &change.text);
                 }
                 None => {
@@ -402,8 +586,9 @@ impl Server {
             };
         }
 
-        self.diagnostic_manager
-            .update_syntax(&workspace, workspace.lookup(&uri).unwrap());
+        let document = workspace.lookup(&uri).unwrap();
+        self.diagnostic_manager.update_syntax(&workspace, document);
+        self.symbol_index.lock().invalidate(document);
 
         drop(workspace);
         self.update_workspace();
@@ -412,9 +597,21 @@ impl Server {
             self.run_chktex(&uri);
         }
 
+        if self.workspace.read().config().build.on_idle {
+            self.build_on_idle(uri);
+        }
+
         Ok(())
     }
 
+    /// Restarts the timer of the idle build, which compiles the document
+    /// once no edits have been made for `texlab.build.idleDelay`.
+    fn build_on_idle(&mut self, uri: Url) {
+        let delay = self.workspace.read().config().build.idle_delay;
+        self.idle_build = Some(uri);
+        self.idle_timer = crossbeam_channel::after(delay);
+    }
+
     fn did_save(&mut self, params: DidSaveTextDocumentParams) -> Result<()> {
         let mut uri = params.text_document.uri;
         normalize_uri(&mut uri);
@@ -424,6 +621,7 @@ impl Server {
             let params = BuildParams {
                 text_document,
                 position: None,
+                profile: None,
             };
 
             self.build(None, params)?;
@@ -466,8 +664,9 @@ impl Server {
 
     fn document_link(&self, id: RequestId, mut params: DocumentLinkParams) -> Result<()> {
         normalize_uri(&mut params.text_document.uri);
+        let encoding = self.client_flags.position_encoding;
         self.run_query(id, move |workspace| {
-            link::find_all(workspace, params).unwrap_or_default()
+            link::find_all(workspace, params, encoding).unwrap_or_default()
         });
         Ok(())
     }
@@ -484,8 +683,10 @@ impl Server {
     }
 
     fn workspace_symbols(&self, id: RequestId, params: WorkspaceSymbolParams) -> Result<()> {
+        let index = Arc::clone(&self.symbol_index);
+        let encoding = self.client_flags.position_encoding;
         self.run_query(id, move |workspace| {
-            symbols::workspace_symbols(workspace, &params.query)
+            symbols::workspace_symbols(workspace, &index, &params.query, encoding)
         });
 
         Ok(())
@@ -534,8 +735,9 @@ impl Server {
 
     fn references(&self, id: RequestId, mut params: ReferenceParams) -> Result<()> {
         normalize_uri(&mut params.text_document_position.text_document.uri);
+        let encoding = self.client_flags.position_encoding;
         self.run_query(id, move |db| {
-            reference::find_all(db, params).unwrap_or_default()
+            reference::find_all(db, params, encoding).unwrap_or_default()
         });
 
         Ok(())
@@ -545,32 +747,41 @@ impl Server {
         normalize_uri(&mut params.text_document_position_params.text_document.uri);
         let uri_and_pos = &params.text_document_position_params;
         self.update_cursor(&uri_and_pos.text_document.uri, uri_and_pos.position);
-        self.run_query(id, move |db| hover::find(db, params));
+        let encoding = self.client_flags.position_encoding;
+        self.run_query(id, move |db| hover::find(db, params, encoding));
         Ok(())
     }
 
     fn goto_definition(&self, id: RequestId, mut params: GotoDefinitionParams) -> Result<()> {
         normalize_uri(&mut params.text_document_position_params.text_document.uri);
-        self.run_query(id, move |db| definition::goto_definition(db, params));
+        let encoding = self.client_flags.position_encoding;
+        self.run_query(id, move |db| {
+            definition::goto_definition(db, params, encoding)
+        });
         Ok(())
     }
 
     fn prepare_rename(&self, id: RequestId, mut params: TextDocumentPositionParams) -> Result<()> {
         normalize_uri(&mut params.text_document.uri);
-        self.run_query(id, move |db| rename::prepare_rename_all(db, params));
+        let encoding = self.client_flags.position_encoding;
+        self.run_query(id, move |db| {
+            rename::prepare_rename_all(db, params, encoding)
+        });
         Ok(())
     }
 
     fn rename(&self, id: RequestId, mut params: RenameParams) -> Result<()> {
         normalize_uri(&mut params.text_document_position.text_document.uri);
-        self.run_query(id, move |db| rename::rename_all(db, params));
+        let encoding = self.client_flags.position_encoding;
+        self.run_query(id, move |db| rename::rename_all(db, params, encoding));
         Ok(())
     }
 
     fn document_highlight(&self, id: RequestId, mut params: DocumentHighlightParams) -> Result<()> {
         normalize_uri(&mut params.text_document_position_params.text_document.uri);
+        let encoding = self.client_flags.position_encoding;
         self.run_query(id, move |db| {
-            highlight::find_all(db, params).unwrap_or_default()
+            highlight::find_all(db, params, encoding).unwrap_or_default()
         });
 
         Ok(())
@@ -579,8 +790,19 @@ impl Server {
     fn formatting(&self, id: RequestId, params: DocumentFormattingParams) -> Result<()> {
         let mut uri = params.text_document.uri;
         normalize_uri(&mut uri);
+        let encoding = self.client_flags.position_encoding;
+        self.run_query(id, move |db| {
+            formatting::format_source_code(db, &uri, &params.options, encoding)
+        });
+
+        Ok(())
+    }
+
+    fn code_action(&self, id: RequestId, mut params: CodeActionParams) -> Result<()> {
+        normalize_uri(&mut params.text_document.uri);
+        let encoding = self.client_flags.position_encoding;
         self.run_query(id, move |db| {
-            formatting::format_source_code(db, &uri, &params.options)
+            code_action::find_all(db, params, encoding).unwrap_or_default()
         });
 
         Ok(())
@@ -589,12 +811,10 @@ impl Server {
     fn execute_command(&self, id: RequestId, params: ExecuteCommandParams) -> Result<()> {
         match params.command.as_str() {
             "texlab.cleanAuxiliary" => {
-                let command = self.prepare_clean_command(params, CleanTarget::Auxiliary);
-                self.run_fallible(id, || command?.run());
+                self.run_clean_command(id, params, CleanTarget::Auxiliary);
             }
             "texlab.cleanArtifacts" => {
-                let command = self.prepare_clean_command(params, CleanTarget::Artifacts);
-                self.run_fallible(id, || command?.run());
+                self.run_clean_command(id, params, CleanTarget::Artifacts);
             }
             "texlab.changeEnvironment" => {
                 let client = self.client.clone();
@@ -607,6 +827,10 @@ impl Server {
                 let result = self.find_environments(params);
                 self.run_fallible(id, move || result);
             }
+            "texlab.findFrame" => {
+                let result = self.find_frame(params);
+                self.run_fallible(id, move || result);
+            }
             "texlab.showDependencyGraph" => {
                 let workspace = self.workspace.read();
                 let dot = commands::show_dependency_graph(&workspace).unwrap();
@@ -614,15 +838,20 @@ impl Server {
                     .send_response(lsp_server::Response::new_ok(id, dot))?;
             }
             "texlab.cancelBuild" => {
-                let pending_builds = Arc::clone(&self.pending_builds);
+                let roots = self.prepare_cancel_build(params);
+                let build_queue = self.build_queue.clone();
                 self.run_fallible(id, move || {
-                    for pid in pending_builds.lock().drain() {
+                    for pid in build_queue.cancel(roots.as_deref()) {
                         let _ = BuildCommand::cancel(pid);
                     }
 
                     Ok(())
                 });
             }
+            "texlab.sortBibliography" => {
+                let result = self.sort_bibliography(params);
+                self.run_fallible(id, move || result);
+            }
             _ => {
                 self.client
                     .send_error(
@@ -639,8 +868,9 @@ impl Server {
 
     fn inlay_hints(&self, id: RequestId, mut params: InlayHintParams) -> Result<()> {
         normalize_uri(&mut params.text_document.uri);
+        let encoding = self.client_flags.position_encoding;
         self.run_query(id, move |db| {
-            inlay_hint::find_all(db, params).unwrap_or_default()
+            inlay_hint::find_all(db, params, encoding).unwrap_or_default()
         });
         Ok(())
     }
@@ -660,9 +890,6 @@ impl Server {
     }
 
     fn build(&self, id: Option<RequestId>, params: BuildParams) -> Result<()> {
-        static LOCK: Mutex<()> = Mutex::new(());
-        static NEXT_TOKEN: AtomicI32 = AtomicI32::new(1);
-
         let mut uri = params.text_document.uri;
         normalize_uri(&mut uri);
 
@@ -672,50 +899,66 @@ impl Server {
 
         let fwd_search_after = workspace.config().build.forward_search_after;
 
-        let (sender, receiver) = crossbeam_channel::unbounded();
-        self.redirect_build_log(receiver);
+        let command = BuildCommand::new(&workspace, &uri, params.profile.as_deref());
+        let root = command
+            .as_ref()
+            .map_or_else(|_| uri.clone(), |command| command.root.clone());
 
-        let command = BuildCommand::new(&workspace, &uri);
         let internal = self.internal_tx.clone();
         let progress = self.client_flags.progress;
-        let pending_builds = Arc::clone(&self.pending_builds);
+        let build_queue = self.build_queue.clone();
+
+        // Builds triggered by saving a document are not awaited by the client,
+        // so repeated saves only need to compile the root document once more.
+        let coalesce = id.is_none();
+        let job_root = root.clone();
+        self.build_queue.submit(root, coalesce, move |cancelled| {
+            if cancelled {
+                if let Some(id) = id {
+                    let result = BuildResult {
+                        status: BuildStatus::CANCELLED,
+                    };
 
-        self.pool.execute(move || {
-            let guard = LOCK.lock();
+                    let _ = client.send_response(lsp_server::Response::new_ok(id, result));
+                }
+
+                return;
+            }
+
+            let root = job_root;
+            let (sender, receiver) = crossbeam_channel::unbounded();
+            let (status_sender, status_receiver) = crossbeam_channel::bounded(1);
+            let log_root = command.as_ref().ok().map(|_| root.clone());
+            Self::redirect_build_log(
+                &client,
+                &internal,
+                uri.clone(),
+                log_root,
+                receiver,
+                status_receiver,
+            );
 
             let progress_reporter = if progress {
-                let token = NEXT_TOKEN.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                Some(ProgressReporter::new(client.clone(), token, &uri))
+                let token = NEXT_TOKEN.fetch_add(1, Ordering::SeqCst);
+                Some(ProgressReporter::new(
+                    client.clone(),
+                    token,
+                    "Building",
+                    uri.to_string(),
+                ))
             } else {
                 None
             };
 
             let status = command
-                .and_then(|command| {
-                    let mut process = command.spawn(sender)?;
-                    let pid = process.id();
-                    pending_builds.lock().insert(pid);
-                    let result = process.wait();
-
-                    let status = if pending_builds.lock().remove(&pid) {
-                        if result?.success() {
-                            BuildStatus::SUCCESS
-                        } else {
-                            BuildStatus::ERROR
-                        }
-                    } else {
-                        BuildStatus::CANCELLED
-                    };
-
-                    Ok(status)
-                })
+                .and_then(|command| Self::run_build(&build_queue, &root, command, sender))
                 .unwrap_or_else(|why| {
                     log::error!("Failed to compile document \"{uri}\": {why}");
                     BuildStatus::FAILURE
                 });
 
+            let _ = status_sender.send(status);
             drop(progress_reporter);
-            drop(guard);
 
             if let Some(id) = id {
                 let result = BuildResult { status };
@@ -730,15 +973,162 @@ impl Server {
         Ok(())
     }
 
-    fn redirect_build_log(&self, receiver: Receiver<String>) {
+    /// Compiles the in-memory state of the project containing the given document
+    /// and shows the result in the PDF viewer afterwards.
+    fn preview(&self, uri: Url) {
+        let command = match BuildCommand::new(&self.workspace.read(), &uri, None) {
+            Ok(command) => command,
+            Err(why) => {
+                log::warn!("Unable to preview document \"{uri}\": {why}");
+                return;
+            }
+        };
+
+        let root = command.root.clone();
+        let workspace = Arc::clone(&self.workspace);
         let client = self.client.clone();
-        self.pool.execute(move || {
+        let internal = self.internal_tx.clone();
+        let build_queue = self.build_queue.clone();
+        let job_root = root.clone();
+        self.build_queue.submit(root, true, move |cancelled| {
+            if cancelled {
+                return;
+            }
+
+            let root = job_root;
+
+            // Writing the shadow directory is deferred until the build starts
+            // to keep the main loop responsive and to compile the latest state of the project.
+            let (command, shadow) = match command.into_shadow(&workspace.read()) {
+                Ok(result) => result,
+                Err(why) => {
+                    log::warn!("Unable to preview document \"{uri}\": {why}");
+                    return;
+                }
+            };
+
+            let (sender, receiver) = crossbeam_channel::unbounded();
+            let (status_sender, status_receiver) = crossbeam_channel::bounded(1);
+            let log_root = Some(root.clone());
+            Self::redirect_build_log(
+                &client,
+                &internal,
+                uri.clone(),
+                log_root,
+                receiver,
+                status_receiver,
+            );
+
+            let status = match Self::run_build(&build_queue, &root, command, sender) {
+                Ok(status) => status,
+                Err(why) => {
+                    log::error!("Failed to preview document \"{uri}\": {why}");
+                    BuildStatus::FAILURE
+                }
+            };
+
+            let _ = status_sender.send(status);
+            if matches!(status, BuildStatus::SUCCESS | BuildStatus::ERROR) {
+                let _ = internal.send(InternalMessage::PreviewForwardSearch(uri, shadow));
+            }
+        });
+    }
+
+    fn run_build(
+        build_queue: &BuildQueue,
+        root: &Url,
+        command: BuildCommand,
+        sender: Sender<String>,
+    ) -> Result<BuildStatus, BuildError> {
+        let mut process = command.spawn(sender)?;
+        build_queue.attach(root, process.id());
+        let result = process.wait();
+
+        let status = if build_queue.detach(root) {
+            if result?.success() {
+                BuildStatus::SUCCESS
+            } else {
+                BuildStatus::ERROR
+            }
+        } else {
+            BuildStatus::CANCELLED
+        };
+
+        Ok(status)
+    }
+
+    /// Forwards the output of the compiler on a dedicated thread, which lives as long as the build.
+    fn redirect_build_log(
+        client: &LspClient,
+        internal: &Sender<InternalMessage>,
+        uri: Url,
+        root: Option<Url>,
+        receiver: Receiver<String>,
+        status: Receiver<BuildStatus>,
+    ) {
+        const STATUS_INTERVAL: Duration = Duration::from_millis(500);
+
+        let client = client.clone();
+        let internal = internal.clone();
+        std::thread::spawn(move || {
+            let report = |progress: &BuildProgress,
+                          percentage: Option<u32>,
+                          status: Option<BuildStatus>| {
+                let log = progress.log();
+                let (errors, warnings) = log
+                    .errors
+                    .iter()
+                    .partition::<Vec<_>, _>(|error| error.level == BuildErrorLevel::Error);
+
+                let _ = client.send_notification::<BuildStatusNotification>(BuildStatusParams {
+                    uri: uri.clone(),
+                    pass: progress.pass,
+                    rule: progress.rule.clone(),
+                    page: progress.page,
+                    percentage,
+                    errors: errors.len(),
+                    warnings: warnings.len(),
+                    status,
+                });
+
+                if let Some(root) = &root {
+                    let _ = internal.send(InternalMessage::BuildOutput(root.clone(), log));
+                }
+            };
+
             let typ = MessageType::LOG;
-            for message in receiver {
-                client
-                    .send_notification::<LogMessage>(LogMessageParams { message, typ })
-                    .unwrap();
+            let mut progress = BuildProgress::default();
+            let mut last_report = Instant::now();
+            let mut changed = false;
+            loop {
+                match receiver.recv_timeout(STATUS_INTERVAL) {
+                    Ok(message) => {
+                        progress.push_line(&message);
+                        changed = true;
+                        client
+                            .send_notification::<LogMessage>(LogMessageParams { message, typ })
+                            .unwrap();
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                };
+
+                if changed && last_report.elapsed() >= STATUS_INTERVAL {
+                    report(&progress, progress.percentage(), None);
+                    last_report = Instant::now();
+                    changed = false;
+                }
             }
+
+            // The log ends once the process exits, which does not mean that the build succeeded.
+            let status = status.recv().unwrap_or(BuildStatus::FAILURE);
+            let percentage = if status == BuildStatus::SUCCESS {
+                Some(100)
+            } else {
+                progress.percentage()
+            };
+
+            report(&progress, percentage, Some(status));
         });
     }
 
@@ -777,12 +1167,6 @@ impl Server {
         Ok(())
     }
 
-    fn code_actions(&self, id: RequestId, _params: CodeActionParams) -> Result<()> {
-        self.client
-            .send_response(lsp_server::Response::new_ok(id, Vec::<CodeAction>::new()))?;
-        Ok(())
-    }
-
     fn code_action_resolve(&self, id: RequestId, action: CodeAction) -> Result<()> {
         self.client
             .send_response(lsp_server::Response::new_ok(id, action))?;
@@ -800,6 +1184,7 @@ impl Server {
                     if let Some(document) = workspace.lookup_path(&path) {
                         if document.owner == Owner::Server {
                             let uri = document.uri.clone();
+                            self.symbol_index.lock().invalidate(document);
                             workspace.remove(&uri);
                             changed = true;
                         }
@@ -817,6 +1202,7 @@ impl Server {
 
                             if let Some(document) = workspace.lookup_path(&path) {
                                 self.diagnostic_manager.update_syntax(&workspace, document);
+                                self.symbol_index.lock().invalidate(document);
                             }
                         }
                     }
@@ -835,19 +1221,50 @@ impl Server {
         &self,
         params: ExecuteCommandParams,
         target: CleanTarget,
-    ) -> Result<CleanCommand> {
+    ) -> Result<(CleanCommand, bool)> {
         let workspace = self.workspace.read();
-        let mut params = self.parse_command_params::<TextDocumentIdentifier>(params.arguments)?;
+        let mut params = self.parse_command_params::<CleanParams>(params.arguments)?;
         normalize_uri(&mut params.uri);
         let Some(document) = workspace.lookup(&params.uri) else {
             anyhow::bail!("Document {} is not opened!", params.uri)
         };
 
-        CleanCommand::new(&workspace, document, target)
+        let command = CleanCommand::new(&workspace, document, target)?;
+        Ok((command, params.dry_run))
+    }
+
+    fn run_clean_command(&self, id: RequestId, params: ExecuteCommandParams, target: CleanTarget) {
+        let command = self.prepare_clean_command(params, target);
+        self.run_fallible(id, || {
+            let (command, dry_run) = command?;
+            if dry_run {
+                Ok(command.files().to_vec())
+            } else {
+                command.run()
+            }
+        });
+    }
+
+    fn prepare_cancel_build(&self, params: ExecuteCommandParams) -> Option<Vec<Url>> {
+        let mut params = self
+            .parse_command_params::<TextDocumentIdentifier>(params.arguments)
+            .ok()?;
+
+        normalize_uri(&mut params.uri);
+        let workspace = self.workspace.read();
+        let mut roots = vec![params.uri.clone()];
+        roots.extend(
+            BuildCommand::new(&workspace, &params.uri, None)
+                .ok()
+                .map(|command| command.root),
+        );
+
+        Some(roots)
     }
 
     fn change_environment(&self, params: ExecuteCommandParams) -> Result<ApplyWorkspaceEditParams> {
         let workspace = self.workspace.read();
+        let encoding = self.client_flags.position_encoding;
         let params = self.parse_command_params::<RenameParams>(params.arguments)?;
         let mut uri = params.text_document_position.text_document.uri;
         normalize_uri(&mut uri);
@@ -857,7 +1274,9 @@ impl Server {
         };
 
         let line_index = &document.line_index;
-        let Some(position) = line_index.offset_lsp(params.text_document_position.position) else {
+        let Some(position) =
+            line_index.offset_lsp(params.text_document_position.position, encoding)
+        else {
             anyhow::bail!("Invalid position for document {uri}!")
         };
 
@@ -866,8 +1285,10 @@ impl Server {
             anyhow::bail!("No environment found at the current position");
         };
 
-        let range1 = line_index.line_col_lsp_range(result.begin).unwrap();
-        let range2 = line_index.line_col_lsp_range(result.end).unwrap();
+        let range1 = line_index
+            .line_col_lsp_range(result.begin, encoding)
+            .unwrap();
+        let range2 = line_index.line_col_lsp_range(result.end, encoding).unwrap();
 
         let mut changes = HashMap::new();
         changes.insert(
@@ -894,6 +1315,7 @@ impl Server {
 
     fn find_environments(&self, params: ExecuteCommandParams) -> Result<Vec<EnvironmentLocation>> {
         let workspace = self.workspace.read();
+        let encoding = self.client_flags.position_encoding;
         let params = self.parse_command_params::<TextDocumentPositionParams>(params.arguments)?;
         let mut uri = params.text_document.uri;
         normalize_uri(&mut uri);
@@ -903,7 +1325,7 @@ impl Server {
         };
 
         let line_index = &document.line_index;
-        let Some(offset) = line_index.offset_lsp(params.position) else {
+        let Some(offset) = line_index.offset_lsp(params.position, encoding) else {
             anyhow::bail!("Invalid position for document {uri}!")
         };
 
@@ -911,16 +1333,101 @@ impl Server {
             .into_iter()
             .map(|result| EnvironmentLocation {
                 name: TextWithRange {
-                    range: line_index.line_col_lsp_range(result.name.range).unwrap(),
+                    range: line_index
+                        .line_col_lsp_range(result.name.range, encoding)
+                        .unwrap(),
                     text: result.name.text,
                 },
-                full_range: line_index.line_col_lsp_range(result.full_range).unwrap(),
+                full_range: line_index
+                    .line_col_lsp_range(result.full_range, encoding)
+                    .unwrap(),
             })
             .collect();
 
         Ok(results)
     }
 
+    fn find_frame(&self, params: ExecuteCommandParams) -> Result<Option<FrameLocation>> {
+        let workspace = self.workspace.read();
+        let encoding = self.client_flags.position_encoding;
+        let params = self.parse_command_params::<FindFrameParams>(params.arguments)?;
+        let mut uri = params.text_document.uri;
+        normalize_uri(&mut uri);
+
+        let Some(document) = workspace.lookup(&uri) else {
+            anyhow::bail!("Document {} is not opened!", uri)
+        };
+
+        let Some(result) = commands::find_frame(&workspace, document, params.number) else {
+            return Ok(None);
+        };
+
+        let document = workspace.lookup(&result.uri).unwrap();
+        Ok(Some(FrameLocation {
+            uri: result.uri,
+            title: result.title,
+            full_range: document
+                .line_index
+                .line_col_lsp_range(result.full_range, encoding)
+                .unwrap(),
+        }))
+    }
+
+    fn sort_bibliography(&self, params: ExecuteCommandParams) -> Result<WorkspaceEdit> {
+        let workspace = self.workspace.read();
+        let encoding = self.client_flags.position_encoding;
+        let params = if params.arguments.is_empty() {
+            SortBibliographyParams::default()
+        } else {
+            self.parse_command_params::<SortBibliographyParams>(params.arguments)?
+        };
+
+        let documents = match &params.text_document {
+            Some(text_document) => {
+                let mut uri = text_document.uri.clone();
+                normalize_uri(&mut uri);
+                let Some(document) = workspace.lookup(&uri) else {
+                    anyhow::bail!("Document {} is not opened!", uri)
+                };
+
+                vec![document]
+            }
+            None => workspace
+                .iter()
+                .filter(|document| document.language == Language::Bib)
+                .filter(|document| document.owner != Owner::Distro)
+                .collect(),
+        };
+
+        let options = commands::SortBibliographyOptions::from(params);
+        let mut changes = HashMap::new();
+        for document in documents {
+            let Some(new_text) = commands::sort_bibliography(document, &options) else {
+                continue;
+            };
+
+            if new_text == document.text {
+                continue;
+            }
+
+            let Some(end) = document
+                .line_index
+                .line_col_lsp(document.text.text_len(), encoding)
+            else {
+                anyhow::bail!("Unable to compute the end of document {}", document.uri);
+            };
+
+            let range = Range::new(Position::new(0, 0), end);
+            changes.insert(document.uri.clone(), vec![TextEdit::new(range, new_text)]);
+        }
+
+        Ok(WorkspaceEdit {
+            changes: Some(changes),
+            document_changes: None,
+            change_annotations: None,
+        })
+    }
+
     fn parse_command_params<T: DeserializeOwned>(
         &self,
         params: Vec<serde_json::Value>,
@@ -970,6 +1477,7 @@ impl Server {
                                     self.document_highlight(id, params)
                                 })?
                                 .on::<Formatting, _>(|id, params| self.formatting(id, params))?
+                                .on::<CodeActionRequest, _>(|id, params| self.code_action(id, params))?
                                 .on::<BuildRequest, _>(|id, params| self.build(Some(id), params))?
                                 .on::<ForwardSearchRequest, _>(|id, params| {
                                     self.forward_search(Some(id), params.text_document.uri, Some(params.position))
@@ -984,9 +1492,6 @@ impl Server {
                                 .on::<InlayHintResolveRequest,_>(|id, params| {
                                     self.inlay_hint_resolve(id, params)
                                 })?
-                                .on::<CodeActionRequest, _>(|id, params| {
-                                    self.code_actions(id, params)
-                                })?
                                 .on::<CodeActionResolveRequest, _>(|id, params| {
                                     self.code_action_resolve(id, params)
                                 })?
@@ -1004,6 +1509,9 @@ impl Server {
                                 .on::<DidChangeWatchedFiles, _>(|params| {
                                     self.did_change_watched_files(params)
                                 })?
+                                .on::<DidChangeWorkspaceFolders, _>(|params| {
+                                    self.did_change_workspace_folders(params)
+                                })?
                                 .on::<DidOpenTextDocument, _>(|params| self.did_open(params))?
                                 .on::<DidChangeTextDocument, _>(|params| self.did_change(params))?
                                 .on::<DidSaveTextDocument, _>(|params| self.did_save(params))?
@@ -1022,6 +1530,12 @@ impl Server {
                         }
                         InternalMessage::SetOptions(options) => {
                             self.update_options(options);
+                            self.start_initial_scan();
+                        }
+                        InternalMessage::ConfigurationFailed => {
+                            // The folders are scanned with the current configuration instead,
+                            // which is the default one if the options have never been received.
+                            self.start_initial_scan();
                         }
                         InternalMessage::FileEvent(events) => {
                             for event in events {
@@ -1035,10 +1549,40 @@ impl Server {
                             self.diagnostic_manager.update_chktex(uri, diagnostics);
                             self.publish_diagnostics()?;
                         }
+                        InternalMessage::BuildOutput(root, log) => {
+                            let workspace = self.workspace.read();
+                            self.diagnostic_manager.update_build_output(&workspace, &root, &log);
+                            drop(workspace);
+                            self.publish_diagnostics()?;
+                        }
                         InternalMessage::ForwardSearch(uri, position) => {
                             self.forward_search(None, uri, position)?;
                         }
+                        InternalMessage::FoldersScanned(files, progress_reporter) => {
+                            self.load_scanned_files(files);
+                            drop(progress_reporter);
+                        }
+                        InternalMessage::PreviewForwardSearch(uri, shadow) => {
+                            let command = ForwardSearch::preview(&self.workspace.read(), &uri, &shadow);
+                            self.pool.execute(move || {
+                                if let Err(why) = command.and_then(ForwardSearch::run) {
+                                    log::warn!("Failed to show preview: {why}");
+                                }
+                            });
+                        }
                     };
+                },
+                recv(&self.idle_timer) -> _ => {
+                    self.idle_timer = crossbeam_channel::never();
+                    if let Some(uri) = self.idle_build.take() {
+                        self.preview(uri);
+                    }
+                }
+                recv(&self.cache_timer) -> _ => {
+                    self.cache_timer = crossbeam_channel::never();
+                    if let Some(cache) = self.workspace.read().cache().cloned() {
+                        self.pool.execute(move || cache.flush());
+                    }
                 }
             };
         }
@@ -1047,11 +1591,13 @@ impl Server {
     pub fn run(mut self, options: StartupOptions) -> Result<()> {
         if !options.skip_distro {
             let sender = self.internal_tx.clone();
+            let cache_file = self.cache_dir.as_ref().map(|dir| dir.join("distro"));
             self.pool.execute(move || {
-                let distro = Distro::detect().unwrap_or_else(|why| {
-                    log::warn!("Unable to load distro files: {}", why);
-                    Distro::default()
-                });
+                let distro =
+                    Distro::detect_with_cache(cache_file.as_deref()).unwrap_or_else(|why| {
+                        log::warn!("Unable to load distro files: {}", why);
+                        Distro::default()
+                    });
 
                 log::info!("Detected distribution: {:?}", distro.kind);
                 sender.send(InternalMessage::SetDistro(distro)).unwrap();
@@ -1060,8 +1606,16 @@ impl Server {
 
         self.register_configuration();
         self.pull_options();
+
+        // The exclude patterns are part of the configuration,
+        // so the scan waits for the options if the client provides them.
+        if !self.client_flags.configuration_pull {
+            self.start_initial_scan();
+        }
+
         self.process_messages()?;
         self.pool.join();
+        self.flush_cache();
         Ok(())
     }
 }
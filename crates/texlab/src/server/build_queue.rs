@@ -0,0 +1,237 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use lsp_types::Url;
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+use threadpool::ThreadPool;
+
+/// The maximum number of root documents that are compiled at the same time.
+const MAX_PARALLEL_BUILDS: usize = 4;
+
+/// A build job. The argument is `true` if the build has been cancelled before it started.
+type Job = Box<dyn FnOnce(bool) + Send>;
+
+struct PendingBuild {
+    root: Url,
+    coalesce: bool,
+    job: Job,
+}
+
+#[derive(Default)]
+struct State {
+    /// The running builds and the process ids of their compilers.
+    running: FxHashMap<Url, Option<u32>>,
+    pending: VecDeque<PendingBuild>,
+}
+
+/// Schedules builds so that every root document is compiled by at most one process at a time
+/// while builds of different root documents run in parallel.
+/// The builds run on their own threads, so they do not block the requests of the client.
+#[derive(Clone)]
+pub struct BuildQueue {
+    state: Arc<Mutex<State>>,
+    pool: ThreadPool,
+}
+
+impl Default for BuildQueue {
+    fn default() -> Self {
+        Self {
+            state: Default::default(),
+            pool: ThreadPool::with_name(String::from("build"), MAX_PARALLEL_BUILDS),
+        }
+    }
+}
+
+impl BuildQueue {
+    /// Enqueues a build of the given root document.
+    /// If `coalesce` is set and an equivalent build is already waiting, the job is discarded
+    /// and `false` is returned.
+    pub fn submit(
+        &self,
+        root: Url,
+        coalesce: bool,
+        job: impl FnOnce(bool) + Send + 'static,
+    ) -> bool {
+        let mut state = self.state.lock();
+        if coalesce
+            && state
+                .pending
+                .iter()
+                .any(|build| build.coalesce && build.root == root)
+        {
+            log::debug!("Skipping build of {root} because it is already queued");
+            return false;
+        }
+
+        state.pending.push_back(PendingBuild {
+            root,
+            coalesce,
+            job: Box::new(job),
+        });
+
+        self.schedule(&mut state);
+        true
+    }
+
+    /// Registers the compiler process of a running build.
+    pub fn attach(&self, root: &Url, pid: u32) {
+        if let Some(entry) = self.state.lock().running.get_mut(root) {
+            *entry = Some(pid);
+        }
+    }
+
+    /// Unregisters the compiler process of a running build.
+    /// Returns `false` if the build has been cancelled in the meantime.
+    pub fn detach(&self, root: &Url) -> bool {
+        self.state
+            .lock()
+            .running
+            .get_mut(root)
+            .is_some_and(|entry| entry.take().is_some())
+    }
+
+    /// Removes the queued builds of the given root documents (or of all root documents
+    /// if no root is specified) and returns the process ids of their running builds.
+    pub fn cancel(&self, roots: Option<&[Url]>) -> Vec<u32> {
+        let is_cancelled = |root: &Url| roots.map_or(true, |roots| roots.contains(root));
+        let (pids, cancelled) = {
+            let mut state = self.state.lock();
+            let pids = state
+                .running
+                .iter_mut()
+                .filter(|(root, _)| is_cancelled(root))
+                .filter_map(|(_, pid)| pid.take())
+                .collect::<Vec<_>>();
+
+            let (cancelled, pending) = std::mem::take(&mut state.pending)
+                .into_iter()
+                .partition::<VecDeque<_>, _>(|build| is_cancelled(&build.root));
+
+            state.pending = pending;
+            (pids, cancelled)
+        };
+
+        // The jobs are notified outside of the lock because they may respond to the client.
+        for build in cancelled {
+            (build.job)(true);
+        }
+
+        pids
+    }
+
+    fn schedule(&self, state: &mut State) {
+        let mut index = 0;
+        while index < state.pending.len() && state.running.len() < MAX_PARALLEL_BUILDS {
+            if state.running.contains_key(&state.pending[index].root) {
+                index += 1;
+                continue;
+            }
+
+            let PendingBuild { root, job, .. } = state.pending.remove(index).unwrap();
+            state.running.insert(root.clone(), None);
+
+            let guard = RunningBuild {
+                queue: self.clone(),
+                root,
+            };
+
+            self.pool.execute(move || {
+                job(false);
+                drop(guard);
+            });
+        }
+    }
+}
+
+/// Releases the slot of a build even if the job panics.
+struct RunningBuild {
+    queue: BuildQueue,
+    root: Url,
+}
+
+impl Drop for RunningBuild {
+    fn drop(&mut self) {
+        let mut state = self.queue.state.lock();
+        state.running.remove(&self.root);
+        self.queue.schedule(&mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use lsp_types::Url;
+
+    use super::BuildQueue;
+
+    #[test]
+    fn test_coalesce_and_parallel() {
+        let queue = BuildQueue::default();
+        let foo = Url::parse("file:///foo/main.tex").unwrap();
+        let bar = Url::parse("file:///bar/main.tex").unwrap();
+
+        let (block_tx, block_rx) = crossbeam_channel::unbounded::<()>();
+        let (done_tx, done_rx) = crossbeam_channel::unbounded();
+
+        let rx = block_rx.clone();
+        let tx = done_tx.clone();
+        assert!(queue.submit(foo.clone(), true, move |_| {
+            let _ = rx.recv();
+            tx.send("foo 1").unwrap();
+        }));
+
+        let tx = done_tx.clone();
+        assert!(queue.submit(foo.clone(), true, move |_| tx.send("foo 2").unwrap()));
+
+        let tx = done_tx.clone();
+        assert!(!queue.submit(foo.clone(), true, move |_| tx.send("foo 3").unwrap()));
+
+        let tx = done_tx.clone();
+        assert!(queue.submit(bar, true, move |_| tx.send("bar").unwrap()));
+
+        let timeout = Duration::from_secs(10);
+        assert_eq!(done_rx.recv_timeout(timeout), Ok("bar"));
+
+        drop(block_tx);
+        assert_eq!(done_rx.recv_timeout(timeout), Ok("foo 1"));
+        assert_eq!(done_rx.recv_timeout(timeout), Ok("foo 2"));
+        assert!(done_rx.recv_timeout(Duration::from_millis(100)).is_err());
+    }
+
+    #[test]
+    fn test_cancel_pending() {
+        let queue = BuildQueue::default();
+        let foo = Url::parse("file:///foo/main.tex").unwrap();
+        let bar = Url::parse("file:///bar/main.tex").unwrap();
+
+        let (block_tx, block_rx) = crossbeam_channel::unbounded::<()>();
+        let (done_tx, done_rx) = crossbeam_channel::unbounded();
+
+        let tx = done_tx.clone();
+        assert!(queue.submit(foo.clone(), false, move |cancelled| {
+            let _ = block_rx.recv();
+            tx.send(("foo 1", cancelled)).unwrap();
+        }));
+
+        let tx = done_tx.clone();
+        assert!(queue.submit(foo.clone(), false, move |cancelled| {
+            tx.send(("foo 2", cancelled)).unwrap()
+        }));
+
+        let tx = done_tx.clone();
+        assert!(queue.submit(bar.clone(), false, move |cancelled| {
+            tx.send(("bar", cancelled)).unwrap()
+        }));
+
+        let timeout = Duration::from_secs(10);
+        assert_eq!(done_rx.recv_timeout(timeout), Ok(("bar", false)));
+
+        assert!(queue.cancel(Some(&[foo])).is_empty());
+        assert_eq!(done_rx.recv_timeout(timeout), Ok(("foo 2", true)));
+
+        drop(block_tx);
+        assert_eq!(done_rx.recv_timeout(timeout), Ok(("foo 1", false)));
+        assert!(done_rx.recv_timeout(Duration::from_millis(100)).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+use std::{fs, path::PathBuf};
+
+/// Returns the directory of the on-disk cache (e.g. `~/.cache/texlab/5.13.0`).
+/// Every version of the server uses its own subdirectory because the layout
+/// of the cached syntax trees may change between versions.
+pub fn open_cache_dir() -> Option<PathBuf> {
+    let dir = dirs::cache_dir()?
+        .join("texlab")
+        .join(env!("CARGO_PKG_VERSION"));
+
+    fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
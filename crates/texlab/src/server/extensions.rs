@@ -1,7 +1,9 @@
 #![allow(non_camel_case_types)]
 
 use commands::ForwardSearchError;
-use lsp_types::{Position, Range, TextDocumentIdentifier, TextDocumentPositionParams};
+use lsp_types::{
+    FormattingOptions, Position, Range, TextDocumentIdentifier, TextDocumentPositionParams, Url,
+};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
@@ -22,6 +24,9 @@ pub struct BuildParams {
 
     #[serde(default)]
     pub position: Option<Position>,
+
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -39,6 +44,37 @@ pub enum BuildStatus {
     CANCELLED = 3,
 }
 
+pub struct BuildStatusNotification;
+
+impl lsp_types::notification::Notification for BuildStatusNotification {
+    type Params = BuildStatusParams;
+
+    const METHOD: &'static str = "$/texlab/buildStatus";
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildStatusParams {
+    pub uri: Url,
+    pub pass: u32,
+    pub rule: Option<String>,
+    pub page: u32,
+    pub percentage: Option<u32>,
+    pub errors: usize,
+    pub warnings: usize,
+    /// The result of the build, which is only set in the last notification.
+    pub status: Option<BuildStatus>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanParams {
+    pub uri: Url,
+
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
 pub struct ForwardSearchRequest;
 
 impl lsp_types::request::Request for ForwardSearchRequest {
@@ -90,3 +126,76 @@ pub struct TextWithRange {
     pub text: String,
     pub range: Range,
 }
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FindFrameParams {
+    pub text_document: TextDocumentIdentifier,
+    pub number: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameLocation {
+    pub uri: Url,
+    pub title: Option<String>,
+    pub full_range: Range,
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct SortBibliographyParams {
+    pub text_document: Option<TextDocumentIdentifier>,
+    pub sort_by: BibSortKey,
+    pub sort_fields: bool,
+    pub lowercase_field_names: bool,
+    pub delimiter: Option<BibDelimiter>,
+    pub remove_empty_fields: bool,
+    /// The formatting options of the client, which determine the indentation of rewritten entries.
+    pub options: Option<FormattingOptions>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BibSortKey {
+    #[default]
+    Key,
+    Year,
+    Author,
+    Type,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BibDelimiter {
+    Braces,
+    Quotes,
+}
+
+impl From<SortBibliographyParams> for commands::SortBibliographyOptions {
+    fn from(params: SortBibliographyParams) -> Self {
+        Self {
+            sort_by: match params.sort_by {
+                BibSortKey::Key => commands::BibSortKey::Key,
+                BibSortKey::Year => commands::BibSortKey::Year,
+                BibSortKey::Author => commands::BibSortKey::Author,
+                BibSortKey::Type => commands::BibSortKey::Type,
+            },
+            sort_fields: params.sort_fields,
+            lowercase_field_names: params.lowercase_field_names,
+            delimiter: params.delimiter.map(|delimiter| match delimiter {
+                BibDelimiter::Braces => commands::BibDelimiter::Braces,
+                BibDelimiter::Quotes => commands::BibDelimiter::Quotes,
+            }),
+            remove_empty_fields: params.remove_empty_fields,
+            formatting: params
+                .options
+                .map_or_else(Default::default, |options| bibfmt::Options {
+                    insert_spaces: options.insert_spaces,
+                    tab_size: options.tab_size as usize,
+                    ..Default::default()
+                }),
+        }
+    }
+}
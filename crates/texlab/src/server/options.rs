@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use base_db::{Config, Formatter, SynctexConfig};
+use base_db::{BuildProfile, Config, Formatter, SynctexConfig};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +22,8 @@ pub struct Options {
     pub forward_search: ForwardSearchOptions,
     pub completion: CompletionOptions,
     pub inlay_hints: InlayHintOptions,
+    pub citation: CitationOptions,
+    pub workspace: WorkspaceOptions,
     pub experimental: ExperimentalOptions,
 }
 
@@ -68,11 +70,23 @@ pub struct BuildOptions {
     pub executable: Option<String>,
     pub args: Option<Vec<String>>,
     pub on_save: bool,
+    pub on_idle: bool,
+    pub idle_delay: Option<u64>,
     pub forward_search_after: bool,
     pub aux_directory: Option<String>,
     pub log_directory: Option<String>,
     pub pdf_directory: Option<String>,
     pub filename: Option<String>,
+    pub profiles: Vec<BuildProfileOptions>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct BuildProfileOptions {
+    pub name: String,
+    pub executable: String,
+    pub args: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
@@ -106,6 +120,9 @@ pub struct DiagnosticsOptions {
 pub struct InlayHintOptions {
     pub label_definitions: Option<bool>,
     pub label_references: Option<bool>,
+    pub citations: Option<bool>,
+    pub environment_ends: Option<bool>,
+    pub parameter_names: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -116,6 +133,13 @@ pub struct SymbolOptions {
     pub ignored_patterns: Vec<RegexPattern>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct WorkspaceOptions {
+    pub exclude: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegexPattern(#[serde(with = "serde_regex")] pub Regex);
 
@@ -143,6 +167,7 @@ pub struct StartupOptions {
 #[serde(default)]
 pub struct CompletionOptions {
     pub matcher: CompletionMatcher,
+    pub citation_ranking: CitationRanking,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
@@ -160,6 +185,32 @@ impl Default for CompletionMatcher {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CitationRanking {
+    #[default]
+    Relevance,
+    Recency,
+    Usage,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct CitationOptions {
+    pub style: CitationStyle,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CitationStyle {
+    #[default]
+    Default,
+    Apa,
+    Ieee,
+    ChicagoAuthorDate,
+}
+
 impl From<Options> for Config {
     fn from(value: Options) -> Self {
         let mut config = Config::default();
@@ -168,6 +219,12 @@ impl From<Options> for Config {
         config.build.program = value.build.executable.unwrap_or(config.build.program);
         config.build.args = value.build.args.unwrap_or(config.build.args);
         config.build.on_save = value.build.on_save;
+        config.build.on_idle = value.build.on_idle;
+        config.build.idle_delay = value
+            .build
+            .idle_delay
+            .map_or(config.build.idle_delay, Duration::from_millis);
+
         config.build.forward_search_after = value.build.forward_search_after;
 
         config.build.aux_dir = value
@@ -189,6 +246,24 @@ impl From<Options> for Config {
 
         config.build.output_filename = value.build.filename;
 
+        for profile in value.build.profiles {
+            let profile = BuildProfile {
+                name: profile.name,
+                program: profile.executable,
+                args: profile.args,
+            };
+
+            match config
+                .build
+                .profiles
+                .iter_mut()
+                .find(|other| other.name == profile.name)
+            {
+                Some(other) => *other = profile,
+                None => config.build.profiles.push(profile),
+            }
+        }
+
         config.diagnostics.allowed_patterns = value
             .diagnostics
             .allowed_patterns
@@ -255,6 +330,9 @@ impl From<Options> for Config {
 
         config.inlay_hints.label_definitions = value.inlay_hints.label_definitions.unwrap_or(true);
         config.inlay_hints.label_references = value.inlay_hints.label_references.unwrap_or(true);
+        config.inlay_hints.citations = value.inlay_hints.citations.unwrap_or(true);
+        config.inlay_hints.environment_ends = value.inlay_hints.environment_ends.unwrap_or(true);
+        config.inlay_hints.parameter_names = value.inlay_hints.parameter_names.unwrap_or(true);
 
         config.completion.matcher = match value.completion.matcher {
             CompletionMatcher::Fuzzy => base_db::MatchingAlgo::Skim,
@@ -263,6 +341,21 @@ impl From<Options> for Config {
             CompletionMatcher::PrefixIgnoreCase => base_db::MatchingAlgo::PrefixIgnoreCase,
         };
 
+        config.completion.citation_ranking = match value.completion.citation_ranking {
+            CitationRanking::Relevance => base_db::CitationRanking::Relevance,
+            CitationRanking::Recency => base_db::CitationRanking::Recency,
+            CitationRanking::Usage => base_db::CitationRanking::Usage,
+        };
+
+        config.citation.style = match value.citation.style {
+            CitationStyle::Default => citeproc::CitationStyle::Default,
+            CitationStyle::Apa => citeproc::CitationStyle::Apa,
+            CitationStyle::Ieee => citeproc::CitationStyle::Ieee,
+            CitationStyle::ChicagoAuthorDate => citeproc::CitationStyle::ChicagoAuthorDate,
+        };
+
+        config.workspace.exclude = value.workspace.exclude;
+
         config
             .syntax
             .math_environments
@@ -1,7 +1,7 @@
 use lsp_types::{
     notification::Progress, request::WorkDoneProgressCreate, NumberOrString, ProgressParams,
-    ProgressParamsValue, Url, WorkDoneProgress, WorkDoneProgressBegin,
-    WorkDoneProgressCreateParams, WorkDoneProgressEnd,
+    ProgressParamsValue, WorkDoneProgress, WorkDoneProgressBegin, WorkDoneProgressCreateParams,
+    WorkDoneProgressEnd, WorkDoneProgressReport,
 };
 
 use crate::LspClient;
@@ -24,7 +24,7 @@ impl Drop for ProgressReporter {
 }
 
 impl ProgressReporter {
-    pub fn new(client: LspClient, token: i32, uri: &Url) -> Self {
+    pub fn new(client: LspClient, token: i32, title: &str, message: String) -> Self {
         let _ = client.send_request::<WorkDoneProgressCreate>(WorkDoneProgressCreateParams {
             token: NumberOrString::Number(token),
         });
@@ -32,8 +32,8 @@ impl ProgressReporter {
         let _ = client.send_notification::<Progress>(ProgressParams {
             token: NumberOrString::Number(token),
             value: ProgressParamsValue::WorkDone(WorkDoneProgress::Begin(WorkDoneProgressBegin {
-                title: "Building".into(),
-                message: Some(String::from(uri.as_str())),
+                title: title.into(),
+                message: Some(message),
                 cancellable: Some(false),
                 percentage: None,
             })),
@@ -41,4 +41,17 @@ impl ProgressReporter {
 
         Self { client, token }
     }
+
+    pub fn report(&self, message: String, percentage: u32) {
+        let _ = self.client.send_notification::<Progress>(ProgressParams {
+            token: NumberOrString::Number(self.token),
+            value: ProgressParamsValue::WorkDone(WorkDoneProgress::Report(
+                WorkDoneProgressReport {
+                    message: Some(message),
+                    cancellable: Some(false),
+                    percentage: Some(percentage),
+                },
+            )),
+        });
+    }
 }
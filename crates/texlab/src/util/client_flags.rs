@@ -1,3 +1,5 @@
+use super::line_index_ext::PositionEncoding;
+
 /// Contains information about the client's capabilities.
 /// This is used to determine which features the server can use.
 #[derive(Debug, Clone)]
@@ -35,6 +37,9 @@ pub struct ClientFlags {
     /// If `true`, the server can return custom kinds like `section`.
     pub folding_custom_kinds: bool,
 
+    /// The encoding of the positions negotiated with the client (UTF-16 by default).
+    pub position_encoding: PositionEncoding,
+
     /// If `true`, the server can report progress using `WorkDoneProgress`.
     pub progress: bool,
 }
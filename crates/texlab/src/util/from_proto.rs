@@ -10,7 +10,10 @@ use rowan::TextSize;
 
 use crate::features::completion::ResolveInfo;
 
-use super::{line_index_ext::LineIndexExt, ClientFlags};
+use super::{
+    line_index_ext::{LineIndexExt, PositionEncoding},
+    ClientFlags,
+};
 
 pub fn client_flags(
     capabilities: lsp_types::ClientCapabilities,
@@ -88,6 +91,13 @@ pub fn client_flags(
         .and_then(|cap| cap.value_set.as_ref())
         .is_some();
 
+    let position_encoding = capabilities
+        .general
+        .as_ref()
+        .and_then(|cap| cap.position_encodings.as_deref())
+        .map(PositionEncoding::negotiate)
+        .unwrap_or_default();
+
     let progress = capabilities
         .window
         .as_ref()
@@ -105,6 +115,7 @@ pub fn client_flags(
         configuration_push,
         definition_link,
         folding_custom_kinds,
+        position_encoding,
         progress,
     }
 }
@@ -112,9 +123,10 @@ pub fn client_flags(
 pub fn rename_params<'a>(
     workspace: &'a Workspace,
     params: lsp_types::TextDocumentPositionParams,
+    encoding: PositionEncoding,
 ) -> Option<RenameParams<'a>> {
     let (feature, offset) =
-        feature_params_offset(workspace, params.text_document, params.position)?;
+        feature_params_offset(workspace, params.text_document, params.position, encoding)?;
 
     Some(RenameParams { feature, offset })
 }
@@ -122,11 +134,13 @@ pub fn rename_params<'a>(
 pub fn hover_params<'a>(
     workspace: &'a Workspace,
     params: lsp_types::HoverParams,
+    encoding: PositionEncoding,
 ) -> Option<HoverParams<'a>> {
     let (feature, offset) = feature_params_offset(
         workspace,
         params.text_document_position_params.text_document,
         params.text_document_position_params.position,
+        encoding,
     )?;
 
     Some(HoverParams { feature, offset })
@@ -135,20 +149,26 @@ pub fn hover_params<'a>(
 pub fn inlay_hint_params<'a>(
     workspace: &'a Workspace,
     params: lsp_types::InlayHintParams,
+    encoding: PositionEncoding,
 ) -> Option<InlayHintParams> {
     let feature = feature_params(workspace, params.text_document)?;
-    let range = feature.document.line_index.offset_lsp_range(params.range)?;
+    let range = feature
+        .document
+        .line_index
+        .offset_lsp_range(params.range, encoding)?;
     Some(InlayHintParams { feature, range })
 }
 
 pub fn highlight_params<'a>(
     workspace: &'a Workspace,
     params: lsp_types::DocumentHighlightParams,
+    encoding: PositionEncoding,
 ) -> Option<HighlightParams<'a>> {
     let (feature, offset) = feature_params_offset(
         workspace,
         params.text_document_position_params.text_document,
         params.text_document_position_params.position,
+        encoding,
     )?;
 
     Some(HighlightParams { feature, offset })
@@ -157,11 +177,13 @@ pub fn highlight_params<'a>(
 pub fn definition_params<'a>(
     workspace: &'a Workspace,
     params: lsp_types::GotoDefinitionParams,
+    encoding: PositionEncoding,
 ) -> Option<DefinitionParams<'a>> {
     let (feature, offset) = feature_params_offset(
         workspace,
         params.text_document_position_params.text_document,
         params.text_document_position_params.position,
+        encoding,
     )?;
 
     Some(DefinitionParams { feature, offset })
@@ -170,11 +192,13 @@ pub fn definition_params<'a>(
 pub fn completion_params<'a>(
     workspace: &'a Workspace,
     params: lsp_types::CompletionParams,
+    encoding: PositionEncoding,
 ) -> Option<CompletionParams<'a>> {
     let (feature, offset) = feature_params_offset(
         workspace,
         params.text_document_position.text_document,
         params.text_document_position.position,
+        encoding,
     )?;
 
     Some(CompletionParams { feature, offset })
@@ -183,11 +207,13 @@ pub fn completion_params<'a>(
 pub fn reference_params<'a>(
     workspace: &'a Workspace,
     params: lsp_types::ReferenceParams,
+    encoding: PositionEncoding,
 ) -> Option<ReferenceParams<'a>> {
     let (feature, offset) = feature_params_offset(
         workspace,
         params.text_document_position.text_document,
         params.text_document_position.position,
+        encoding,
     )?;
 
     let include_declaration = params.context.include_declaration;
@@ -210,9 +236,10 @@ pub fn feature_params_offset<'a>(
     workspace: &'a Workspace,
     text_document: lsp_types::TextDocumentIdentifier,
     position: lsp_types::Position,
+    encoding: PositionEncoding,
 ) -> Option<(FeatureParams<'a>, TextSize)> {
     let feature = feature_params(workspace, text_document)?;
-    let offset = feature.document.line_index.offset_lsp(position)?;
+    let offset = feature.document.line_index.offset_lsp(position, encoding)?;
     Some((feature, offset))
 }
 
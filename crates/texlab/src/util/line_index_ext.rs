@@ -1,43 +1,94 @@
-use line_index::{LineCol, LineColUtf16, LineIndex};
-use lsp_types::{Position, Range};
+use line_index::{LineCol, LineIndex, WideEncoding, WideLineCol};
+use lsp_types::{Position, PositionEncodingKind, Range};
 use rowan::{TextRange, TextSize};
 
+/// The encoding of the `character` offsets of the positions exchanged with the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PositionEncoding {
+    Utf8,
+    Wide(WideEncoding),
+}
+
+impl PositionEncoding {
+    /// Picks the first encoding supported by the server from the encodings offered by the client.
+    /// UTF-16 is mandatory, so it is used if the client does not support the other encodings.
+    pub fn negotiate(offered: &[PositionEncodingKind]) -> Self {
+        offered
+            .iter()
+            .find_map(|kind| match kind.as_str() {
+                "utf-8" => Some(Self::Utf8),
+                "utf-16" => Some(Self::Wide(WideEncoding::Utf16)),
+                "utf-32" => Some(Self::Wide(WideEncoding::Utf32)),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn kind(self) -> PositionEncodingKind {
+        match self {
+            Self::Utf8 => PositionEncodingKind::UTF8,
+            Self::Wide(WideEncoding::Utf16) => PositionEncodingKind::UTF16,
+            Self::Wide(WideEncoding::Utf32) => PositionEncodingKind::UTF32,
+        }
+    }
+}
+
+impl Default for PositionEncoding {
+    fn default() -> Self {
+        Self::Wide(WideEncoding::Utf16)
+    }
+}
+
 pub trait LineIndexExt {
-    fn offset_lsp(&self, line_col: Position) -> Option<TextSize>;
+    fn offset_lsp(&self, line_col: Position, encoding: PositionEncoding) -> Option<TextSize>;
 
-    fn offset_lsp_range(&self, line_col: Range) -> Option<TextRange>;
+    fn offset_lsp_range(&self, line_col: Range, encoding: PositionEncoding) -> Option<TextRange>;
 
-    fn line_col_lsp(&self, offset: TextSize) -> Option<Position>;
+    fn line_col_lsp(&self, offset: TextSize, encoding: PositionEncoding) -> Option<Position>;
 
-    fn line_col_lsp_range(&self, offset: TextRange) -> Option<Range>;
+    fn line_col_lsp_range(&self, offset: TextRange, encoding: PositionEncoding) -> Option<Range>;
 }
 
 impl LineIndexExt for LineIndex {
-    fn offset_lsp(&self, line_col: Position) -> Option<TextSize> {
-        let line_col = LineColUtf16 {
-            line: line_col.line,
-            col: line_col.character,
+    fn offset_lsp(&self, line_col: Position, encoding: PositionEncoding) -> Option<TextSize> {
+        let line_col = match encoding {
+            PositionEncoding::Utf8 => LineCol {
+                line: line_col.line,
+                col: line_col.character,
+            },
+            PositionEncoding::Wide(enc) => {
+                let line_col = WideLineCol {
+                    line: line_col.line,
+                    col: line_col.character,
+                };
+
+                self.to_utf8(enc, line_col)?
+            }
         };
 
-        let line_col = self.to_utf8(line_col)?;
         self.offset(line_col)
     }
 
-    fn offset_lsp_range(&self, line_col: Range) -> Option<TextRange> {
-        let start = self.offset_lsp(line_col.start)?;
-        let end = self.offset_lsp(line_col.end)?;
+    fn offset_lsp_range(&self, line_col: Range, encoding: PositionEncoding) -> Option<TextRange> {
+        let start = self.offset_lsp(line_col.start, encoding)?;
+        let end = self.offset_lsp(line_col.end, encoding)?;
         Some(TextRange::new(start, end))
     }
 
-    fn line_col_lsp(&self, offset: TextSize) -> Option<Position> {
+    fn line_col_lsp(&self, offset: TextSize, encoding: PositionEncoding) -> Option<Position> {
         let line_col = self.line_col(offset);
-        let line_col = self.to_utf16(line_col)?;
-        Some(Position::new(line_col.line, line_col.col))
+        match encoding {
+            PositionEncoding::Utf8 => Some(Position::new(line_col.line, line_col.col)),
+            PositionEncoding::Wide(enc) => {
+                let line_col = self.to_wide(enc, line_col)?;
+                Some(Position::new(line_col.line, line_col.col))
+            }
+        }
     }
 
-    fn line_col_lsp_range(&self, offset: TextRange) -> Option<Range> {
-        let start = self.line_col_lsp(offset.start())?;
-        let mut end = self.line_col_lsp(offset.end())?;
+    fn line_col_lsp_range(&self, offset: TextRange, encoding: PositionEncoding) -> Option<Range> {
+        let start = self.line_col_lsp(offset.start(), encoding)?;
+        let mut end = self.line_col_lsp(offset.end(), encoding)?;
         if end.line != start.line && end.character == 0 {
             // Prefer keeping multi-line ranges on the same line
             let line_end = self.offset(LineCol {
@@ -45,9 +96,46 @@ impl LineIndexExt for LineIndex {
                 col: 0,
             })?;
 
-            end = self.line_col_lsp(line_end - TextSize::from(1))?;
+            end = self.line_col_lsp(line_end - TextSize::from(1), encoding)?;
         }
 
         Some(Range::new(start, end))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use line_index::{LineIndex, WideEncoding};
+    use lsp_types::{Position, PositionEncodingKind};
+    use rowan::TextSize;
+
+    use super::{LineIndexExt, PositionEncoding};
+
+    #[test]
+    fn test_negotiate() {
+        let offered = [PositionEncodingKind::UTF32, PositionEncodingKind::UTF8];
+        let encoding = PositionEncoding::negotiate(&offered);
+        assert_eq!(encoding, PositionEncoding::Wide(WideEncoding::Utf32));
+        assert_eq!(
+            PositionEncoding::negotiate(&[]),
+            PositionEncoding::default()
+        );
+    }
+
+    #[test]
+    fn test_encodings() {
+        let line_index = LineIndex::new("a😀b\nc");
+        let offset = TextSize::from(5);
+        let cases = [
+            (PositionEncoding::Utf8, 5),
+            (PositionEncoding::Wide(WideEncoding::Utf16), 3),
+            (PositionEncoding::Wide(WideEncoding::Utf32), 2),
+        ];
+
+        for (encoding, character) in cases {
+            let position = Position::new(0, character);
+            assert_eq!(line_index.line_col_lsp(offset, encoding), Some(position));
+            assert_eq!(line_index.offset_lsp(position, encoding), Some(offset));
+        }
+    }
+}
@@ -23,6 +23,7 @@ pub enum Structure {
     Field,
     Argument,
     GlossaryEntry,
+    PackageOption,
 }
 
 impl Structure {
@@ -54,6 +55,7 @@ impl Structure {
             Self::Field => CompletionItemKind::FIELD,
             Self::Argument => CompletionItemKind::VALUE,
             Self::GlossaryEntry => CompletionItemKind::KEYWORD,
+            Self::PackageOption => CompletionItemKind::PROPERTY,
         }
     }
 }
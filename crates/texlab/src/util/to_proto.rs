@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use base_db::{
     data::BibtexEntryTypeCategory, util::RenderedObject, Document, DocumentLocation, Workspace,
 };
+use commands::MergeEntriesResult;
 use definition::DefinitionResult;
 use diagnostics::{BibError, ChktexSeverity, Diagnostic, TexError};
 use folding::{FoldingRange, FoldingRangeKind};
@@ -10,21 +11,25 @@ use highlights::{Highlight, HighlightKind};
 use hover::{Hover, HoverData};
 use inlay_hints::{InlayHint, InlayHintData};
 use line_index::LineIndex;
-use lsp_types::NumberOrString;
+use lsp_types::{NumberOrString, Url};
 use rename::RenameResult;
 use rowan::TextRange;
 use syntax::BuildErrorLevel;
 
-use super::{line_index_ext::LineIndexExt, ClientFlags};
+use super::{
+    line_index_ext::{LineIndexExt, PositionEncoding},
+    ClientFlags,
+};
 
 pub fn diagnostic(
     workspace: &Workspace,
     document: &Document,
     diagnostic: &Diagnostic,
+    encoding: PositionEncoding,
 ) -> Option<lsp_types::Diagnostic> {
     let range = match diagnostic {
         Diagnostic::Tex(range, _) | Diagnostic::Bib(range, _) | Diagnostic::Build(range, _) => {
-            document.line_index.line_col_lsp_range(*range)?
+            document.line_index.line_col_lsp_range(*range, encoding)?
         }
         Diagnostic::Chktex(range) => {
             let start = lsp_types::Position::new(range.start.line, range.start.col);
@@ -42,6 +47,7 @@ pub fn diagnostic(
             TexError::UndefinedLabel => lsp_types::DiagnosticSeverity::ERROR,
             TexError::UndefinedCitation => lsp_types::DiagnosticSeverity::ERROR,
             TexError::DuplicateLabel(_) => lsp_types::DiagnosticSeverity::ERROR,
+            TexError::UnknownOption => lsp_types::DiagnosticSeverity::WARNING,
         },
         Diagnostic::Bib(_, error) => match error {
             BibError::ExpectingLCurly => lsp_types::DiagnosticSeverity::ERROR,
@@ -51,6 +57,7 @@ pub fn diagnostic(
             BibError::ExpectingFieldValue => lsp_types::DiagnosticSeverity::ERROR,
             BibError::UnusedEntry => lsp_types::DiagnosticSeverity::HINT,
             BibError::DuplicateEntry(_) => lsp_types::DiagnosticSeverity::ERROR,
+            BibError::DuplicateEntryContent(_) => lsp_types::DiagnosticSeverity::WARNING,
         },
         Diagnostic::Build(_, error) => match error.level {
             BuildErrorLevel::Error => lsp_types::DiagnosticSeverity::ERROR,
@@ -72,6 +79,7 @@ pub fn diagnostic(
             TexError::UndefinedLabel => Some(NumberOrString::Number(10)),
             TexError::UndefinedCitation => Some(NumberOrString::Number(11)),
             TexError::DuplicateLabel(_) => Some(NumberOrString::Number(14)),
+            TexError::UnknownOption => Some(NumberOrString::Number(16)),
         },
         Diagnostic::Bib(_, error) => match error {
             BibError::ExpectingLCurly => Some(NumberOrString::Number(4)),
@@ -81,6 +89,7 @@ pub fn diagnostic(
             BibError::ExpectingFieldValue => Some(NumberOrString::Number(8)),
             BibError::UnusedEntry => Some(NumberOrString::Number(12)),
             BibError::DuplicateEntry(_) => Some(NumberOrString::Number(13)),
+            BibError::DuplicateEntryContent(_) => Some(NumberOrString::Number(15)),
         },
         Diagnostic::Build(_, _) => None,
         Diagnostic::Chktex(error) => Some(NumberOrString::String(error.code.clone())),
@@ -101,6 +110,7 @@ pub fn diagnostic(
             TexError::UndefinedLabel => "Undefined reference",
             TexError::UndefinedCitation => "Undefined reference",
             TexError::DuplicateLabel(_) => "Duplicate label",
+            TexError::UnknownOption => "Unknown option",
         },
         Diagnostic::Bib(_, error) => match error {
             BibError::ExpectingLCurly => "Expecting a curly bracket: \"{\"",
@@ -110,6 +120,7 @@ pub fn diagnostic(
             BibError::ExpectingFieldValue => "Expecting a field value",
             BibError::UnusedEntry => "Unused entry",
             BibError::DuplicateEntry(_) => "Duplicate entry key",
+            BibError::DuplicateEntryContent(_) => "Duplicate entry",
         },
         Diagnostic::Build(_, error) => &error.message,
         Diagnostic::Chktex(error) => &error.message,
@@ -124,6 +135,7 @@ pub fn diagnostic(
             TexError::UndefinedLabel => None,
             TexError::UndefinedCitation => None,
             TexError::DuplicateLabel(_) => None,
+            TexError::UnknownOption => None,
         },
         Diagnostic::Bib(_, error) => match error {
             BibError::ExpectingLCurly => None,
@@ -133,6 +145,7 @@ pub fn diagnostic(
             BibError::ExpectingFieldValue => None,
             BibError::UnusedEntry => Some(vec![lsp_types::DiagnosticTag::UNNECESSARY]),
             BibError::DuplicateEntry(_) => None,
+            BibError::DuplicateEntryContent(_) => None,
         },
         Diagnostic::Build(_, _) => None,
         Diagnostic::Chktex(_) => None,
@@ -142,13 +155,14 @@ pub fn diagnostic(
         workspace: &Workspace,
         locations: &Vec<(lsp_types::Url, TextRange)>,
         object: &str,
+        encoding: PositionEncoding,
     ) -> Option<Vec<lsp_types::DiagnosticRelatedInformation>> {
         let mut items = Vec::new();
         for (uri, range) in locations {
             let range = workspace
                 .lookup(uri)?
                 .line_index
-                .line_col_lsp_range(*range)?;
+                .line_col_lsp_range(*range, encoding)?;
 
             let message = format!("conflicting {object} defined here");
             let location = lsp_types::Location::new(uri.clone(), range);
@@ -166,7 +180,10 @@ pub fn diagnostic(
             TexError::UnusedLabel => None,
             TexError::UndefinedLabel => None,
             TexError::UndefinedCitation => None,
-            TexError::DuplicateLabel(others) => make_conflict_info(workspace, others, "label"),
+            TexError::DuplicateLabel(others) => {
+                make_conflict_info(workspace, others, "label", encoding)
+            }
+            TexError::UnknownOption => None,
         },
         Diagnostic::Bib(_, error) => match error {
             BibError::ExpectingLCurly => None,
@@ -175,7 +192,9 @@ pub fn diagnostic(
             BibError::ExpectingEq => None,
             BibError::ExpectingFieldValue => None,
             BibError::UnusedEntry => None,
-            BibError::DuplicateEntry(others) => make_conflict_info(workspace, others, "entry"),
+            BibError::DuplicateEntry(others) | BibError::DuplicateEntryContent(others) => {
+                make_conflict_info(workspace, others, "entry", encoding)
+            }
         },
         Diagnostic::Build(_, _) => None,
         Diagnostic::Chktex(_) => None,
@@ -191,8 +210,12 @@ pub fn diagnostic(
     })
 }
 
-pub fn inlay_hint(hint: InlayHint, line_index: &LineIndex) -> Option<lsp_types::InlayHint> {
-    let position = line_index.line_col_lsp(hint.offset)?;
+pub fn inlay_hint(
+    hint: InlayHint,
+    line_index: &LineIndex,
+    encoding: PositionEncoding,
+) -> Option<lsp_types::InlayHint> {
+    let position = line_index.line_col_lsp(hint.offset, encoding)?;
     Some(match hint.data {
         InlayHintData::LabelDefinition(label) => {
             let number = label.number?;
@@ -236,18 +259,41 @@ pub fn inlay_hint(hint: InlayHint, line_index: &LineIndex) -> Option<lsp_types::
                 data: None,
             }
         }
+        InlayHintData::Citation(text) | InlayHintData::EnvironmentEnd(text) => {
+            lsp_types::InlayHint {
+                position,
+                label: lsp_types::InlayHintLabel::String(format!(" {text} ")),
+                kind: None,
+                text_edits: None,
+                tooltip: None,
+                padding_left: Some(true),
+                padding_right: None,
+                data: None,
+            }
+        }
+        InlayHintData::ParameterName(name) => lsp_types::InlayHint {
+            position,
+            label: lsp_types::InlayHintLabel::String(format!("{name}:")),
+            kind: Some(lsp_types::InlayHintKind::PARAMETER),
+            text_edits: None,
+            tooltip: None,
+            padding_left: None,
+            padding_right: Some(true),
+            data: None,
+        },
     })
 }
 
 pub fn document_link(
-    link: DocumentLocation,
+    link: links::DocumentLink,
     line_index: &LineIndex,
+    encoding: PositionEncoding,
 ) -> Option<lsp_types::DocumentLink> {
     Some(lsp_types::DocumentLink {
         data: None,
         tooltip: None,
-        target: Some(link.document.uri.clone()),
-        range: line_index.line_col_lsp_range(link.range)?,
+        target: Some(link.target),
+        range: line_index.line_col_lsp_range(link.range, encoding)?,
     })
 }
 
@@ -256,16 +302,29 @@ pub fn folding_range(
     line_index: &LineIndex,
     client_flags: &ClientFlags,
 ) -> Option<serde_json::Value> {
-    let range = line_index.line_col_lsp_range(folding.range)?;
+    let encoding = client_flags.position_encoding;
+    let range = line_index.line_col_lsp_range(folding.range, encoding)?;
 
     let kind = if client_flags.folding_custom_kinds {
         Some(match folding.kind {
             FoldingRangeKind::Section => "section",
             FoldingRangeKind::Environment => "environment",
             FoldingRangeKind::Entry => "entry",
+            FoldingRangeKind::Comment => "comment",
+            FoldingRangeKind::Preamble => "preamble",
+            FoldingRangeKind::Region => "region",
+            FoldingRangeKind::Group => "group",
         })
     } else {
-        None
+        match folding.kind {
+            FoldingRangeKind::Comment => Some("comment"),
+            FoldingRangeKind::Preamble => Some("imports"),
+            FoldingRangeKind::Region => Some("region"),
+            FoldingRangeKind::Section
+            | FoldingRangeKind::Environment
+            | FoldingRangeKind::Entry
+            | FoldingRangeKind::Group => None,
+        }
     };
 
     Some(serde_json::json!({
@@ -280,14 +339,16 @@ pub fn folding_range(
 pub fn location_link(
     result: DefinitionResult,
     line_index: &LineIndex,
+    encoding: PositionEncoding,
 ) -> Option<lsp_types::LocationLink> {
-    let origin_selection_range = line_index.line_col_lsp_range(result.origin_selection_range);
+    let origin_selection_range =
+        line_index.line_col_lsp_range(result.origin_selection_range, encoding);
 
     let target_line_index = &result.target.line_index;
     let target_uri = result.target.uri.clone();
-    let target_range = target_line_index.line_col_lsp_range(result.target_range)?;
+    let target_range = target_line_index.line_col_lsp_range(result.target_range, encoding)?;
     let target_selection_range =
-        target_line_index.line_col_lsp_range(result.target_selection_range)?;
+        target_line_index.line_col_lsp_range(result.target_selection_range, encoding)?;
 
     Some(lsp_types::LocationLink {
         origin_selection_range,
@@ -300,11 +361,12 @@ pub fn location_link(
 pub fn document_symbol(
     symbol: symbols::Symbol,
     line_index: &LineIndex,
+    encoding: PositionEncoding,
 ) -> Option<lsp_types::DocumentSymbol> {
     let children = symbol
         .children
         .into_iter()
-        .filter_map(|child| document_symbol(child, line_index))
+        .filter_map(|child| document_symbol(child, line_index, encoding))
         .collect();
 
     #[allow(deprecated)]
@@ -313,8 +375,8 @@ pub fn document_symbol(
         detail: symbol.label.map(|label| label.text),
         kind: symbol_kind(symbol.kind),
         deprecated: Some(false),
-        range: line_index.line_col_lsp_range(symbol.full_range)?,
-        selection_range: line_index.line_col_lsp_range(symbol.selection_range)?,
+        range: line_index.line_col_lsp_range(symbol.full_range, encoding)?,
+        selection_range: line_index.line_col_lsp_range(symbol.selection_range, encoding)?,
         children: Some(children),
         tags: None,
     })
@@ -324,8 +386,11 @@ pub fn symbol_information(
     symbol: symbols::Symbol,
     document: &Document,
     results: &mut Vec<lsp_types::SymbolInformation>,
+    encoding: PositionEncoding,
 ) -> Option<()> {
-    let range = document.line_index.line_col_lsp_range(symbol.full_range)?;
+    let range = document
+        .line_index
+        .line_col_lsp_range(symbol.full_range, encoding)?;
 
     #[allow(deprecated)]
     results.push(lsp_types::SymbolInformation {
@@ -338,7 +403,7 @@ pub fn symbol_information(
     });
 
     for child in symbol.children {
-        symbol_information(child, document, results);
+        symbol_information(child, document, results, encoding);
     }
 
     Some(())
@@ -355,6 +420,7 @@ pub fn symbol_kind(value: symbols::SymbolKind) -> lsp_types::SymbolKind {
         symbols::SymbolKind::EnumerationItem => lsp_types::SymbolKind::ENUM_MEMBER,
         symbols::SymbolKind::Theorem => lsp_types::SymbolKind::VARIABLE,
         symbols::SymbolKind::Equation => lsp_types::SymbolKind::CONSTANT,
+        symbols::SymbolKind::Frame => lsp_types::SymbolKind::NAMESPACE,
         symbols::SymbolKind::Entry(category) => match category {
             BibtexEntryTypeCategory::Misc => lsp_types::SymbolKind::INTERFACE,
             BibtexEntryTypeCategory::String => lsp_types::SymbolKind::STRING,
@@ -365,6 +431,9 @@ pub fn symbol_kind(value: symbols::SymbolKind) -> lsp_types::SymbolKind {
             BibtexEntryTypeCategory::Collection => lsp_types::SymbolKind::TYPE_PARAMETER,
         },
         symbols::SymbolKind::Field => lsp_types::SymbolKind::FIELD,
+        symbols::SymbolKind::Command => lsp_types::SymbolKind::FUNCTION,
+        symbols::SymbolKind::Environment => lsp_types::SymbolKind::CLASS,
+        symbols::SymbolKind::GlossaryEntry => lsp_types::SymbolKind::KEY,
     }
 }
 
@@ -373,30 +442,35 @@ pub fn document_symbol_response(
     symbols: Vec<symbols::Symbol>,
     client_flags: &ClientFlags,
 ) -> lsp_types::DocumentSymbolResponse {
+    let encoding = client_flags.position_encoding;
     if client_flags.hierarchical_document_symbols {
         let results = symbols
             .into_iter()
-            .filter_map(|symbol| document_symbol(symbol, &document.line_index))
+            .filter_map(|symbol| document_symbol(symbol, &document.line_index, encoding))
             .collect();
 
         lsp_types::DocumentSymbolResponse::Nested(results)
     } else {
         let mut results = Vec::new();
         for symbol in symbols {
-            symbol_information(symbol, document, &mut results);
+            symbol_information(symbol, document, &mut results, encoding);
         }
 
         lsp_types::DocumentSymbolResponse::Flat(results)
     }
 }
 
-pub fn workspace_edit(result: RenameResult, new_name: &str) -> lsp_types::WorkspaceEdit {
+pub fn workspace_edit(
+    result: RenameResult,
+    new_name: &str,
+    encoding: PositionEncoding,
+) -> lsp_types::WorkspaceEdit {
     let mut changes = HashMap::default();
     for (document, ranges) in result.changes {
         let mut edits = Vec::new();
         ranges
             .into_iter()
-            .filter_map(|range| document.line_index.line_col_lsp_range(range))
+            .filter_map(|range| document.line_index.line_col_lsp_range(range, encoding))
             .for_each(|range| edits.push(lsp_types::TextEdit::new(range, new_name.into())));
 
         changes.insert(document.uri.clone(), edits);
@@ -405,26 +479,59 @@ pub fn workspace_edit(result: RenameResult, new_name: &str) -> lsp_types::Worksp
     lsp_types::WorkspaceEdit::new(changes)
 }
 
-pub fn location(location: DocumentLocation) -> Option<lsp_types::Location> {
+pub fn merge_entries_code_action(
+    result: MergeEntriesResult,
+    encoding: PositionEncoding,
+) -> lsp_types::CodeAction {
+    let mut changes: HashMap<Url, Vec<lsp_types::TextEdit>> = HashMap::default();
+    for (document, range, text) in result.edits {
+        if let Some(range) = document.line_index.line_col_lsp_range(range, encoding) {
+            changes
+                .entry(document.uri.clone())
+                .or_default()
+                .push(lsp_types::TextEdit::new(range, text));
+        }
+    }
+
+    lsp_types::CodeAction {
+        title: format!("Merge entry `{}` into `{}`", result.duplicate, result.main),
+        kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+        edit: Some(lsp_types::WorkspaceEdit::new(changes)),
+        ..Default::default()
+    }
+}
+
+pub fn location(
+    location: DocumentLocation,
+    encoding: PositionEncoding,
+) -> Option<lsp_types::Location> {
     let document = location.document;
-    let range = document.line_index.line_col_lsp_range(location.range)?;
+    let range = document
+        .line_index
+        .line_col_lsp_range(location.range, encoding)?;
     Some(lsp_types::Location::new(document.uri.clone(), range))
 }
 
 pub fn document_highlight(
     highlight: Highlight,
     line_index: &LineIndex,
+    encoding: PositionEncoding,
 ) -> Option<lsp_types::DocumentHighlight> {
-    let range = line_index.line_col_lsp_range(highlight.range)?;
+    let range = line_index.line_col_lsp_range(highlight.range, encoding)?;
     let kind = Some(match highlight.kind {
         HighlightKind::Write => lsp_types::DocumentHighlightKind::WRITE,
         HighlightKind::Read => lsp_types::DocumentHighlightKind::READ,
+        HighlightKind::Text => lsp_types::DocumentHighlightKind::TEXT,
     });
 
     Some(lsp_types::DocumentHighlight { range, kind })
 }
 
-pub fn hover(hover: Hover, line_index: &LineIndex) -> Option<lsp_types::Hover> {
+pub fn hover(
+    hover: Hover,
+    line_index: &LineIndex,
+    encoding: PositionEncoding,
+) -> Option<lsp_types::Hover> {
     let contents = match hover.data {
         HoverData::Citation(text) => lsp_types::MarkupContent {
             kind: lsp_types::MarkupKind::Markdown,
@@ -450,10 +557,14 @@ pub fn hover(hover: Hover, line_index: &LineIndex) -> Option<lsp_types::Hover> {
             kind: lsp_types::MarkupKind::PlainText,
             value: text,
         },
+        HoverData::PackageOption(option) => lsp_types::MarkupContent {
+            kind: lsp_types::MarkupKind::Markdown,
+            value: option.description.to_string(),
+        },
     };
 
     Some(lsp_types::Hover {
         contents: lsp_types::HoverContents::Markup(contents),
-        range: line_index.line_col_lsp_range(hover.range),
+        range: line_index.line_col_lsp_range(hover.range, encoding),
     })
 }